@@ -0,0 +1,113 @@
+//! Fuzzes `CardinalityTracker::validate_batch` with byte-derived tag keys,
+//! values, and metric names.
+//!
+//! Run via `cargo fuzz run cardinality_tracker` from `apps/api/fuzz`.
+//!
+//! Checks, every run:
+//! - `validate_batch` never panics (this is how the `&key[..32]` /
+//!   `&name[..32]` multi-byte UTF-8 boundary panic in the warning-message
+//!   formatting was found - see `services::limits::truncate_for_display`).
+//! - `accepted + dropped == input` for both tags and metric names.
+//! - a `(key, value)` tag pair already accepted for a run in a project is
+//!   never later reported as dropped for that run and project (the
+//!   run-level key limit only ever applies to *new* keys, and the project
+//!   limit only to *new* pairs). A known key with a different value can
+//!   still be dropped, e.g. for an over-long value.
+
+#![no_main]
+
+use std::collections::{HashMap, HashSet};
+use std::sync::{Mutex, OnceLock};
+
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use tokio::runtime::Runtime;
+
+use mlrun_api::services::{CardinalityTracker, LimitsConfig};
+
+#[derive(Debug, Arbitrary)]
+struct FuzzBatch {
+    project_id: String,
+    run_id: String,
+    tags: Vec<(String, String)>,
+    metric_names: Vec<String>,
+}
+
+fn runtime() -> &'static Runtime {
+    static RT: OnceLock<Runtime> = OnceLock::new();
+    RT.get_or_init(|| Runtime::new().expect("tokio runtime"))
+}
+
+/// A single tracker shared across the whole fuzzing campaign (per
+/// process), so the "a pair, once accepted for a run, stays accepted"
+/// invariant is actually exercised across many calls rather than reset
+/// every iteration.
+fn tracker() -> &'static CardinalityTracker {
+    static TRACKER: OnceLock<CardinalityTracker> = OnceLock::new();
+    TRACKER.get_or_init(|| {
+        CardinalityTracker::new(LimitsConfig {
+            max_tag_keys_per_run: 64,
+            max_metric_names_per_run: 64,
+            max_tags_per_project: 256,
+            max_tag_key_length: 64,
+            max_tag_value_length: 64,
+            max_metric_name_length: 64,
+            ..Default::default()
+        })
+    })
+}
+
+/// Tag pairs seen accepted so far, per (project, run), for the "never
+/// un-accepts a pair" invariant below.
+fn accepted_pairs_by_run() -> &'static Mutex<HashMap<(String, String), HashSet<(String, String)>>> {
+    static SEEN: OnceLock<Mutex<HashMap<(String, String), HashSet<(String, String)>>>> =
+        OnceLock::new();
+    SEEN.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fuzz_target!(|data: &[u8]| {
+    let mut u = arbitrary::Unstructured::new(data);
+    let Ok(batch) = FuzzBatch::arbitrary(&mut u) else {
+        return;
+    };
+    if batch.project_id.is_empty() || batch.run_id.is_empty() {
+        return;
+    }
+
+    let input_tags = batch.tags.len();
+    let input_metric_names = batch.metric_names.len();
+
+    let result = runtime().block_on(tracker().validate_batch(
+        &batch.project_id,
+        &batch.run_id,
+        &batch.tags,
+        &batch.metric_names,
+    ));
+
+    assert_eq!(
+        result.accepted_tags.len() + result.dropped_tags.len(),
+        input_tags,
+        "every input tag must be accepted or dropped, never lost"
+    );
+    assert_eq!(
+        result.accepted_metrics.len() + result.dropped_metrics.len(),
+        input_metric_names,
+        "every input metric name must be accepted or dropped, never lost"
+    );
+
+    let mut seen = accepted_pairs_by_run().lock().unwrap();
+    let run_seen = seen
+        .entry((batch.project_id.clone(), batch.run_id.clone()))
+        .or_default();
+    for pair in &result.dropped_tags {
+        assert!(
+            !run_seen.contains(pair),
+            "tag {pair:?} was previously accepted for run {:?} in project {:?} but is now dropped",
+            batch.run_id,
+            batch.project_id
+        );
+    }
+    for pair in &result.accepted_tags {
+        run_seen.insert(pair.clone());
+    }
+});
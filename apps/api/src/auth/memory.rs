@@ -0,0 +1,264 @@
+//! In-memory `ApiKeyStore` implementation for alpha development.
+
+use std::collections::HashMap;
+
+use tokio::sync::RwLock;
+use tracing::{info, warn};
+
+use super::store::ApiKeyStore;
+use super::types::{generate_api_key, hash_api_key, verify_secret, ApiKey, ApiToken};
+
+/// In-memory API key store for alpha development.
+/// In production, use [`super::postgres::PostgresApiKeyStore`] instead.
+#[derive(Debug, Default)]
+pub struct InMemoryApiKeyStore {
+    /// Map from keyid (`ApiKey::id`) to ApiKey, for O(1) lookup on the
+    /// cleartext identifier embedded in a presented token.
+    keys: RwLock<HashMap<String, ApiKey>>,
+    /// Whether auth is disabled (for dev/testing)
+    pub auth_disabled: std::sync::atomic::AtomicBool,
+}
+
+impl InMemoryApiKeyStore {
+    /// Create a new API key store.
+    pub fn new() -> Self {
+        Self {
+            keys: RwLock::new(HashMap::new()),
+            auth_disabled: std::sync::atomic::AtomicBool::new(false),
+        }
+    }
+
+    /// Create a new API key store with auth disabled (for testing).
+    pub fn new_dev_mode() -> Self {
+        Self {
+            keys: RwLock::new(HashMap::new()),
+            auth_disabled: std::sync::atomic::AtomicBool::new(true),
+        }
+    }
+
+    /// Build an `ApiKey` record for a given `keyid`/`secret` pair, storing
+    /// only the hash of the secret.
+    fn build_key(
+        &self,
+        keyid: String,
+        secret: &str,
+        project_id: Option<String>,
+        name: Option<String>,
+        scopes: Vec<String>,
+        ttl: Option<std::time::Duration>,
+    ) -> ApiKey {
+        let key_hash = hash_api_key(secret);
+        let key_prefix = keyid.chars().take(8).collect();
+        let created_at = std::time::SystemTime::now();
+
+        ApiKey {
+            id: keyid,
+            key_hash,
+            key_prefix,
+            project_id,
+            name,
+            scopes,
+            created_at,
+            last_used_at: None,
+            revoked_at: None,
+            expires_at: ttl.map(|d| created_at + d),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl ApiKeyStore for InMemoryApiKeyStore {
+    /// Initialize the store with bootstrap keys from environment.
+    async fn init_from_env(&self) {
+        // Check for dev mode (no auth required)
+        if std::env::var("MLRUN_AUTH_DISABLED").map_or(false, |v| v == "true" || v == "1") {
+            self.auth_disabled
+                .store(true, std::sync::atomic::Ordering::Relaxed);
+            info!("Authentication disabled (dev mode)");
+        }
+
+        // Check for bootstrap key
+        if let Ok(bootstrap_key) = std::env::var("MLRUN_API_KEY") {
+            if !bootstrap_key.is_empty() {
+                match ApiToken::parse(&bootstrap_key) {
+                    Some(token) => {
+                        let key = self.build_key(
+                            token.keyid,
+                            &token.secret,
+                            None, // Global admin key
+                            Some("bootstrap".to_string()),
+                            vec!["admin".to_string()],
+                            None, // Bootstrap keys never expire
+                        );
+
+                        let mut keys = self.keys.write().await;
+                        keys.insert(key.id.clone(), key);
+                        info!("Loaded bootstrap API key from environment");
+                    }
+                    None => {
+                        warn!(
+                            "MLRUN_API_KEY is not a valid mlrun_<keyid>_<secret> token; ignoring"
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    /// Validate an API key and return the key info if valid.
+    async fn validate_key(&self, raw_key: &str) -> Option<ApiKey> {
+        let token = ApiToken::parse(raw_key)?;
+
+        let mut keys = self.keys.write().await;
+
+        if let Some(key) = keys.get_mut(&token.keyid) {
+            if verify_secret(&token.secret, &key.key_hash) && key.is_valid() {
+                // Update last used time
+                key.last_used_at = Some(std::time::SystemTime::now());
+                return Some(key.clone());
+            }
+        }
+
+        None
+    }
+
+    /// Create a new API key.
+    async fn create_key(
+        &self,
+        project_id: Option<String>,
+        name: Option<String>,
+        scopes: Vec<String>,
+        ttl: Option<std::time::Duration>,
+    ) -> (String, ApiKey) {
+        let (keyid, token) = generate_api_key();
+        let secret = ApiToken::parse(&token)
+            .expect("freshly generated token is well-formed")
+            .secret;
+        let key = self.build_key(keyid, &secret, project_id, name, scopes, ttl);
+
+        let mut keys = self.keys.write().await;
+        keys.insert(key.id.clone(), key.clone());
+
+        (token, key)
+    }
+
+    /// Revoke an API key.
+    async fn revoke_key(&self, key_hash: &str) -> bool {
+        let mut keys = self.keys.write().await;
+
+        if let Some(key) = keys.values_mut().find(|k| k.key_hash == key_hash) {
+            key.revoked_at = Some(std::time::SystemTime::now());
+            return true;
+        }
+
+        false
+    }
+
+    /// List all keys for a project.
+    async fn list_keys(&self, project_id: Option<&str>) -> Vec<ApiKey> {
+        let keys = self.keys.read().await;
+
+        keys.values()
+            .filter(|k| {
+                if let Some(pid) = project_id {
+                    k.project_id.as_ref().map_or(false, |p| p == pid)
+                } else {
+                    true
+                }
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Check if auth is disabled.
+    fn is_auth_disabled(&self) -> bool {
+        self.auth_disabled
+            .load(std::sync::atomic::Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_api_key_store() {
+        let store = InMemoryApiKeyStore::new();
+
+        // Create a key
+        let (raw_key, key) = store
+            .create_key(
+                Some("project-123".to_string()),
+                Some("test-key".to_string()),
+                vec!["ingest".to_string()],
+                None,
+            )
+            .await;
+
+        assert!(raw_key.starts_with("mlrun_"));
+        assert_eq!(key.project_id, Some("project-123".to_string()));
+
+        // Validate the key
+        let validated = store.validate_key(&raw_key).await;
+        assert!(validated.is_some());
+
+        // Invalid key should fail
+        let invalid = store.validate_key("invalid_key").await;
+        assert!(invalid.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_key_revocation() {
+        let store = InMemoryApiKeyStore::new();
+
+        // Create and revoke a key
+        let (raw_key, key) = store
+            .create_key(
+                None,
+                Some("to-revoke".to_string()),
+                vec!["admin".to_string()],
+                None,
+            )
+            .await;
+
+        // Should be valid before revocation
+        assert!(store.validate_key(&raw_key).await.is_some());
+
+        // Revoke
+        store.revoke_key(&key.key_hash).await;
+
+        // Should be invalid after revocation
+        assert!(store.validate_key(&raw_key).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_expiring_key() {
+        let store = InMemoryApiKeyStore::new();
+
+        // Key that already expired
+        let (raw_key, _) = store
+            .create_key(
+                None,
+                Some("short-lived".to_string()),
+                vec!["ingest".to_string()],
+                Some(std::time::Duration::from_secs(0)),
+            )
+            .await;
+
+        // A zero-duration TTL should already be expired by the time we check
+        std::thread::sleep(std::time::Duration::from_millis(1));
+        assert!(store.validate_key(&raw_key).await.is_none());
+
+        // Key with a long TTL is still valid
+        let (raw_key, key) = store
+            .create_key(
+                None,
+                Some("long-lived".to_string()),
+                vec!["ingest".to_string()],
+                Some(std::time::Duration::from_secs(3600)),
+            )
+            .await;
+        assert!(key.expires_at.is_some());
+        assert!(store.validate_key(&raw_key).await.is_some());
+    }
+}
@@ -0,0 +1,101 @@
+//! Axum middleware for API key authentication.
+
+use std::sync::Arc;
+
+use axum::{
+    extract::{Request, State},
+    http::StatusCode,
+    middleware::Next,
+    response::Response,
+};
+use tracing::{debug, warn};
+
+use super::store::ApiKeyStore;
+use super::types::{extract_api_key_from_headers, AuthContext, AuthError};
+
+/// Header a global admin key uses to act within a specific project's
+/// security context for one request, instead of minting a per-project key.
+const ON_BEHALF_OF_HEADER: &str = "x-on-behalf-of";
+
+/// Middleware for API key authentication.
+pub async fn auth_middleware(
+    State(key_store): State<Arc<dyn ApiKeyStore>>,
+    mut request: Request,
+    next: Next,
+) -> Result<Response, (StatusCode, String)> {
+    // Check if auth is disabled (dev mode)
+    if key_store.is_auth_disabled() {
+        // Insert dev mode context
+        request.extensions_mut().insert(AuthContext::dev_mode());
+        return Ok(next.run(request).await);
+    }
+
+    // Extract API key and any delegation header
+    let (raw_key, on_behalf_of) = {
+        let (parts, body) = request.into_parts();
+        let key = extract_api_key_from_headers(&parts);
+        let on_behalf_of = parts
+            .headers
+            .get(ON_BEHALF_OF_HEADER)
+            .and_then(|v| v.to_str().ok())
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty());
+        request = Request::from_parts(parts, body);
+        (key, on_behalf_of)
+    };
+
+    let raw_key = raw_key.ok_or_else(|| {
+        (
+            AuthError::MissingKey.status_code(),
+            AuthError::MissingKey.message().to_string(),
+        )
+    })?;
+
+    // Validate the key
+    let api_key = key_store.validate_key(&raw_key).await.ok_or_else(|| {
+        warn!(key_prefix = %raw_key.chars().take(8).collect::<String>(), "Invalid API key");
+        (
+            AuthError::InvalidKey.status_code(),
+            AuthError::InvalidKey.message().to_string(),
+        )
+    })?;
+
+    // Only global admin keys may delegate into a project's security
+    // context; anyone else presenting the header is rejected outright
+    // rather than having it silently ignored.
+    if let Some(ref project_id) = on_behalf_of {
+        if api_key.project_id.is_some() || !api_key.has_scope("admin") {
+            warn!(
+                key_prefix = %api_key.key_prefix,
+                project_id = %project_id,
+                "Rejected X-On-Behalf-Of from a non-admin key"
+            );
+            return Err((
+                AuthError::ProjectAccessDenied.status_code(),
+                AuthError::ProjectAccessDenied.message().to_string(),
+            ));
+        }
+    }
+
+    debug!(
+        key_prefix = %api_key.key_prefix,
+        project_id = ?api_key.project_id,
+        on_behalf_of = ?on_behalf_of,
+        "Authenticated request"
+    );
+
+    // Insert auth context into request extensions
+    request.extensions_mut().insert(AuthContext {
+        api_key,
+        is_dev_mode: false,
+        on_behalf_of_project: on_behalf_of,
+    });
+
+    Ok(next.run(request).await)
+}
+
+/// Extractor for getting AuthContext from request extensions.
+/// Use axum::Extension<AuthContext> instead, or access via request.extensions().
+pub fn get_auth_context(extensions: &axum::http::Extensions) -> Option<&AuthContext> {
+    extensions.get::<AuthContext>()
+}
@@ -0,0 +1,344 @@
+//! PostgreSQL-backed `ApiKeyStore` implementation.
+//!
+//! Persists API keys in an `api_keys` table, created by [`run_migrations`] on
+//! connect so a fresh database self-initializes (mirrors
+//! `storage::postgres::run_migrations`). Presented tokens are
+//! `mlrun_<keyid>_<secret>`: `id` is the cleartext `keyid`, so `validate_key`
+//! does a primary-key lookup and only then compares the stored hash of the
+//! secret in constant time, rather than hashing the entire presented token
+//! before it has been narrowed to a single row. `last_used_at` updates are
+//! batched through a background flush task so validating a key never blocks
+//! on a write.
+
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use sqlx::PgPool;
+use tokio::sync::mpsc;
+use tracing::{debug, info, warn};
+
+use super::store::ApiKeyStore;
+use super::types::{generate_api_key, hash_api_key, verify_secret, ApiKey, ApiToken};
+use crate::storage::{PostgresConfig, PostgresError};
+
+/// How often pending `last_used_at` updates are flushed to Postgres.
+const LAST_USED_FLUSH_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Create the `api_keys` table if it doesn't already exist. Run once per
+/// [`PostgresApiKeyStore::connect`] call; `CREATE TABLE IF NOT EXISTS` makes
+/// it safe to run on every boot.
+async fn run_migrations(pool: &PgPool) -> Result<(), PostgresError> {
+    const STATEMENTS: &[&str] = &[
+        r#"CREATE TABLE IF NOT EXISTS api_keys (
+            id TEXT PRIMARY KEY,
+            key_hash TEXT NOT NULL,
+            key_prefix TEXT NOT NULL,
+            project_id TEXT,
+            name TEXT,
+            scopes TEXT[] NOT NULL DEFAULT '{}',
+            created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+            last_used_at TIMESTAMPTZ,
+            revoked_at TIMESTAMPTZ,
+            expires_at TIMESTAMPTZ
+        )"#,
+        "CREATE INDEX IF NOT EXISTS api_keys_key_hash_idx ON api_keys (key_hash)",
+        "CREATE INDEX IF NOT EXISTS api_keys_project_id_idx ON api_keys (project_id)",
+    ];
+
+    for statement in STATEMENTS {
+        sqlx::query(statement)
+            .execute(pool)
+            .await
+            .map_err(|e| PostgresError::Database(e.to_string()))?;
+    }
+
+    Ok(())
+}
+
+/// Row shape matching the `api_keys` table.
+#[derive(sqlx::FromRow)]
+struct ApiKeyRow {
+    id: String,
+    key_hash: String,
+    key_prefix: String,
+    project_id: Option<String>,
+    name: Option<String>,
+    scopes: Vec<String>,
+    created_at: SystemTime,
+    last_used_at: Option<SystemTime>,
+    revoked_at: Option<SystemTime>,
+    expires_at: Option<SystemTime>,
+}
+
+impl From<ApiKeyRow> for ApiKey {
+    fn from(row: ApiKeyRow) -> Self {
+        Self {
+            id: row.id,
+            key_hash: row.key_hash,
+            key_prefix: row.key_prefix,
+            project_id: row.project_id,
+            name: row.name,
+            scopes: row.scopes,
+            created_at: row.created_at,
+            last_used_at: row.last_used_at,
+            revoked_at: row.revoked_at,
+            expires_at: row.expires_at,
+        }
+    }
+}
+
+/// Postgres-backed API key store.
+///
+/// `last_used_at` bumps are pushed onto an unbounded channel and applied in
+/// batches by a background task, so `validate_key` only ever does a single
+/// indexed `SELECT`.
+pub struct PostgresApiKeyStore {
+    pool: PgPool,
+    auth_disabled: std::sync::atomic::AtomicBool,
+    last_used_tx: mpsc::UnboundedSender<String>,
+}
+
+impl PostgresApiKeyStore {
+    /// Connect to `config`'s database, running migrations before returning.
+    pub async fn connect(config: &PostgresConfig) -> Result<Arc<Self>, PostgresError> {
+        let pool = config.connect().await?;
+        run_migrations(&pool).await?;
+        Ok(Self::new(pool))
+    }
+
+    /// Create a new store backed by `pool`, spawning the `last_used_at`
+    /// batching task.
+    pub fn new(pool: PgPool) -> Arc<Self> {
+        let (last_used_tx, last_used_rx) = mpsc::unbounded_channel();
+
+        let store = Arc::new(Self {
+            pool,
+            auth_disabled: std::sync::atomic::AtomicBool::new(false),
+            last_used_tx,
+        });
+
+        tokio::spawn(Self::run_last_used_flusher(
+            store.pool.clone(),
+            last_used_rx,
+        ));
+
+        store
+    }
+
+    /// Background task: drains `id` (keyid) values queued by `validate_key`
+    /// and batches them into a single `UPDATE ... WHERE id = ANY($1)` every
+    /// [`LAST_USED_FLUSH_INTERVAL`].
+    async fn run_last_used_flusher(pool: PgPool, mut rx: mpsc::UnboundedReceiver<String>) {
+        let mut pending: Vec<String> = Vec::new();
+        let mut tick = tokio::time::interval(LAST_USED_FLUSH_INTERVAL);
+
+        loop {
+            tokio::select! {
+                maybe_id = rx.recv() => {
+                    match maybe_id {
+                        Some(id) => pending.push(id),
+                        None => break, // sender dropped, store gone
+                    }
+                }
+                _ = tick.tick() => {
+                    if pending.is_empty() {
+                        continue;
+                    }
+                    let batch = std::mem::take(&mut pending);
+                    if let Err(e) = sqlx::query(
+                        "UPDATE api_keys SET last_used_at = now() WHERE id = ANY($1)",
+                    )
+                    .bind(&batch)
+                    .execute(&pool)
+                    .await
+                    {
+                        warn!(error = %e, batch_size = batch.len(), "Failed to flush last_used_at batch");
+                    } else {
+                        debug!(batch_size = batch.len(), "Flushed last_used_at batch");
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl ApiKeyStore for PostgresApiKeyStore {
+    async fn init_from_env(&self) {
+        if std::env::var("MLRUN_AUTH_DISABLED").map_or(false, |v| v == "true" || v == "1") {
+            self.auth_disabled
+                .store(true, std::sync::atomic::Ordering::Relaxed);
+            info!("Authentication disabled (dev mode)");
+        }
+
+        if let Ok(bootstrap_key) = std::env::var("MLRUN_API_KEY") {
+            if !bootstrap_key.is_empty() {
+                let Some(token) = ApiToken::parse(&bootstrap_key) else {
+                    warn!("MLRUN_API_KEY is not a valid mlrun_<keyid>_<secret> token; ignoring");
+                    return;
+                };
+
+                let key_hash = hash_api_key(&token.secret);
+                let key_prefix: String = token.keyid.chars().take(8).collect();
+
+                let result = sqlx::query(
+                    r#"
+                    INSERT INTO api_keys (id, key_hash, key_prefix, project_id, name, scopes, created_at)
+                    VALUES ($1, $2, $3, NULL, 'bootstrap', $4, now())
+                    ON CONFLICT (id) DO NOTHING
+                    "#,
+                )
+                .bind(&token.keyid)
+                .bind(&key_hash)
+                .bind(&key_prefix)
+                .bind(["admin".to_string()].as_slice())
+                .execute(&self.pool)
+                .await;
+
+                match result {
+                    Ok(_) => info!("Loaded bootstrap API key from environment"),
+                    Err(e) => warn!(error = %e, "Failed to load bootstrap API key"),
+                }
+            }
+        }
+    }
+
+    async fn validate_key(&self, raw_key: &str) -> Option<ApiKey> {
+        let token = ApiToken::parse(raw_key)?;
+
+        let row: ApiKeyRow = sqlx::query_as(
+            r#"
+            SELECT id, key_hash, key_prefix, project_id, name, scopes, created_at, last_used_at, revoked_at, expires_at
+            FROM api_keys
+            WHERE id = $1
+            "#,
+        )
+        .bind(&token.keyid)
+        .fetch_optional(&self.pool)
+        .await
+        .inspect_err(|e| warn!(error = %e, "Failed to query api_keys"))
+        .ok()
+        .flatten()?;
+
+        let key: ApiKey = row.into();
+        if !verify_secret(&token.secret, &key.key_hash) || !key.is_valid() {
+            return None;
+        }
+
+        // Don't block the hot path on a write; the flusher task applies this.
+        let _ = self.last_used_tx.send(key.id.clone());
+
+        Some(key)
+    }
+
+    async fn create_key(
+        &self,
+        project_id: Option<String>,
+        name: Option<String>,
+        scopes: Vec<String>,
+        ttl: Option<Duration>,
+    ) -> (String, ApiKey) {
+        let (id, token) = generate_api_key();
+        let secret = ApiToken::parse(&token)
+            .expect("freshly generated token is well-formed")
+            .secret;
+        let key_hash = hash_api_key(&secret);
+        let key_prefix: String = id.chars().take(8).collect();
+        let created_at = SystemTime::now();
+        let expires_at = ttl.map(|d| created_at + d);
+
+        let insert = sqlx::query(
+            r#"
+            INSERT INTO api_keys (id, key_hash, key_prefix, project_id, name, scopes, created_at, expires_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            "#,
+        )
+        .bind(&id)
+        .bind(&key_hash)
+        .bind(&key_prefix)
+        .bind(&project_id)
+        .bind(&name)
+        .bind(&scopes)
+        .bind(created_at)
+        .bind(expires_at)
+        .execute(&self.pool)
+        .await;
+
+        if let Err(e) = insert {
+            warn!(error = %e, "Failed to persist new API key");
+        }
+
+        (
+            token,
+            ApiKey {
+                id,
+                key_hash,
+                key_prefix,
+                project_id,
+                name,
+                scopes,
+                created_at,
+                expires_at,
+                last_used_at: None,
+                revoked_at: None,
+            },
+        )
+    }
+
+    async fn revoke_key(&self, key_hash: &str) -> bool {
+        let result = sqlx::query("UPDATE api_keys SET revoked_at = now() WHERE key_hash = $1")
+            .bind(key_hash)
+            .execute(&self.pool)
+            .await;
+
+        match result {
+            Ok(r) => r.rows_affected() > 0,
+            Err(e) => {
+                warn!(error = %e, "Failed to revoke API key");
+                false
+            }
+        }
+    }
+
+    async fn list_keys(&self, project_id: Option<&str>) -> Vec<ApiKey> {
+        let rows: Result<Vec<ApiKeyRow>, sqlx::Error> = match project_id {
+            Some(pid) => {
+                sqlx::query_as(
+                    r#"
+                    SELECT id, key_hash, key_prefix, project_id, name, scopes, created_at, last_used_at, revoked_at, expires_at
+                    FROM api_keys
+                    WHERE project_id = $1
+                    ORDER BY created_at DESC
+                    "#,
+                )
+                .bind(pid)
+                .fetch_all(&self.pool)
+                .await
+            }
+            None => {
+                sqlx::query_as(
+                    r#"
+                    SELECT id, key_hash, key_prefix, project_id, name, scopes, created_at, last_used_at, revoked_at, expires_at
+                    FROM api_keys
+                    ORDER BY created_at DESC
+                    "#,
+                )
+                .fetch_all(&self.pool)
+                .await
+            }
+        };
+
+        match rows {
+            Ok(rows) => rows.into_iter().map(ApiKey::from).collect(),
+            Err(e) => {
+                warn!(error = %e, "Failed to list API keys");
+                vec![]
+            }
+        }
+    }
+
+    fn is_auth_disabled(&self) -> bool {
+        self.auth_disabled
+            .load(std::sync::atomic::Ordering::Relaxed)
+    }
+}
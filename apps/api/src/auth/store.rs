@@ -0,0 +1,39 @@
+//! `ApiKeyStore` trait: storage-agnostic API key management.
+//!
+//! Allows the deployment to pick an in-memory store (alpha/dev) or a
+//! persistent backend (Postgres) via config, without changing the
+//! authentication middleware or handlers.
+
+use super::types::ApiKey;
+
+/// Storage backend for API keys.
+///
+/// Implementations must be safe to share behind an `Arc` and called
+/// concurrently from every request handling the auth middleware.
+#[async_trait::async_trait]
+pub trait ApiKeyStore: Send + Sync {
+    /// Initialize the store with bootstrap keys/config from environment.
+    async fn init_from_env(&self);
+
+    /// Validate an API key and return the key info if valid.
+    async fn validate_key(&self, raw_key: &str) -> Option<ApiKey>;
+
+    /// Create a new API key. `ttl` sets `expires_at` to `now + ttl` when
+    /// given; `None` creates a key that never expires.
+    async fn create_key(
+        &self,
+        project_id: Option<String>,
+        name: Option<String>,
+        scopes: Vec<String>,
+        ttl: Option<std::time::Duration>,
+    ) -> (String, ApiKey);
+
+    /// Revoke an API key.
+    async fn revoke_key(&self, key_hash: &str) -> bool;
+
+    /// List all keys for a project (or all keys if `project_id` is `None`).
+    async fn list_keys(&self, project_id: Option<&str>) -> Vec<ApiKey>;
+
+    /// Check if auth is disabled (dev mode).
+    fn is_auth_disabled(&self) -> bool;
+}
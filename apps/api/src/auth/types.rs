@@ -0,0 +1,502 @@
+//! Core authentication types shared by all `ApiKeyStore` backends.
+
+use axum::http::{request::Parts, StatusCode};
+use sha2::{Digest, Sha256};
+use subtle::ConstantTimeEq;
+
+/// An API key entry stored in the system.
+#[derive(Debug, Clone)]
+pub struct ApiKey {
+    /// Unique identifier for the key
+    pub id: String,
+    /// SHA-256 hash of the token's secret part
+    pub key_hash: String,
+    /// First 8 chars of the keyid for identification
+    pub key_prefix: String,
+    /// Project this key is scoped to (None = global admin)
+    pub project_id: Option<String>,
+    /// Human-readable name
+    pub name: Option<String>,
+    /// Permitted scopes
+    pub scopes: Vec<String>,
+    /// When the key was created
+    pub created_at: std::time::SystemTime,
+    /// When the key was last used
+    pub last_used_at: Option<std::time::SystemTime>,
+    /// When the key was revoked (None = active)
+    pub revoked_at: Option<std::time::SystemTime>,
+    /// When the key expires (None = never). Useful for short-lived CI or
+    /// bootstrap credentials.
+    pub expires_at: Option<std::time::SystemTime>,
+}
+
+impl ApiKey {
+    /// Check if the key is valid: not revoked and not past `expires_at`.
+    pub fn is_valid(&self) -> bool {
+        if self.revoked_at.is_some() {
+            return false;
+        }
+        match self.expires_at {
+            Some(expires_at) => std::time::SystemTime::now() < expires_at,
+            None => true,
+        }
+    }
+
+    /// Check if the key has expired (distinct from revocation, for
+    /// reporting purposes).
+    pub fn is_expired(&self) -> bool {
+        self.expires_at
+            .is_some_and(|expires_at| std::time::SystemTime::now() >= expires_at)
+    }
+
+    /// Check if the key has a specific scope.
+    pub fn has_scope(&self, scope: &str) -> bool {
+        // Admin scope grants all permissions
+        if self.scopes.contains(&"admin".to_string()) {
+            return true;
+        }
+        self.scopes.contains(&scope.to_string())
+    }
+
+    /// Check if the key can access a project.
+    pub fn can_access_project(&self, project_id: &str) -> bool {
+        // Global admin keys can access all projects
+        if self.project_id.is_none() {
+            return true;
+        }
+        // Otherwise, must match the project
+        self.project_id.as_ref().map_or(false, |p| p == project_id)
+    }
+}
+
+/// Authenticated user context extracted from request.
+#[derive(Debug, Clone)]
+pub struct AuthContext {
+    /// The API key used for authentication
+    pub api_key: ApiKey,
+    /// Whether authentication is bypassed (dev mode)
+    pub is_dev_mode: bool,
+    /// Set when a global admin key presented `X-On-Behalf-Of: <project_id>`
+    /// to act within a specific project's security context for this
+    /// request. `api_key` still identifies the real, presenting key - this
+    /// only narrows what it may access, it never widens it.
+    pub on_behalf_of_project: Option<String>,
+}
+
+impl AuthContext {
+    /// Create a dev mode context (no authentication).
+    pub fn dev_mode() -> Self {
+        Self {
+            api_key: ApiKey {
+                id: "dev".to_string(),
+                key_hash: "dev".to_string(),
+                key_prefix: "dev".to_string(),
+                project_id: None,
+                name: Some("Dev Mode".to_string()),
+                scopes: vec!["admin".to_string()],
+                created_at: std::time::SystemTime::now(),
+                last_used_at: None,
+                revoked_at: None,
+                expires_at: None,
+            },
+            is_dev_mode: true,
+            on_behalf_of_project: None,
+        }
+    }
+
+    /// Whether this request may access `project_id`, honoring any active
+    /// `X-On-Behalf-Of` delegation: a delegated admin key is restricted to
+    /// exactly the delegated project, even though the underlying key could
+    /// otherwise access any project.
+    pub fn can_access_project(&self, project_id: &str) -> bool {
+        match &self.on_behalf_of_project {
+            Some(delegated) => delegated == project_id,
+            None => self.api_key.can_access_project(project_id),
+        }
+    }
+
+    /// The single project this context is restricted to, if any: the
+    /// delegated project under `X-On-Behalf-Of`, else the key's own
+    /// `project_id`. `None` means unrestricted (a global admin key with no
+    /// active delegation) - useful for defaulting a listing query's project
+    /// filter to the caller's own project instead of leaking every project.
+    pub fn restricted_project(&self) -> Option<&str> {
+        self.on_behalf_of_project
+            .as_deref()
+            .or(self.api_key.project_id.as_deref())
+    }
+}
+
+/// Authentication error types.
+#[derive(Debug, Clone)]
+pub enum AuthError {
+    /// No API key provided
+    MissingKey,
+    /// Invalid API key
+    InvalidKey,
+    /// Key doesn't have required scope
+    InsufficientScope,
+    /// Key cannot access requested project
+    ProjectAccessDenied,
+}
+
+impl AuthError {
+    pub fn status_code(&self) -> StatusCode {
+        match self {
+            AuthError::MissingKey => StatusCode::UNAUTHORIZED,
+            AuthError::InvalidKey => StatusCode::UNAUTHORIZED,
+            AuthError::InsufficientScope => StatusCode::FORBIDDEN,
+            AuthError::ProjectAccessDenied => StatusCode::FORBIDDEN,
+        }
+    }
+
+    pub fn message(&self) -> &'static str {
+        match self {
+            AuthError::MissingKey => {
+                "API key required. Use Authorization: Bearer <key> or X-API-Key header."
+            }
+            AuthError::InvalidKey => "Invalid API key.",
+            AuthError::InsufficientScope => "Insufficient permissions.",
+            AuthError::ProjectAccessDenied => "Access to project denied.",
+        }
+    }
+}
+
+/// Hash a string (the secret portion of a token) using SHA-256.
+pub fn hash_api_key(key: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(key.as_bytes());
+    hex::encode(hasher.finalize())
+}
+
+/// A parsed `mlrun_<keyid>_<secret>` token.
+///
+/// `keyid` is a UUIDv7 stored in cleartext so lookups are a cheap indexed
+/// (or primary-key) fetch; `secret` is the random portion that gets hashed
+/// and compared in constant time, so presenting an attacker-controlled
+/// token never requires hashing against every stored key or leaks timing
+/// information about how much of the secret matched.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiToken {
+    pub keyid: String,
+    pub secret: String,
+}
+
+impl ApiToken {
+    /// Parse a presented token into its `keyid` and `secret` parts.
+    pub fn parse(raw: &str) -> Option<Self> {
+        let rest = raw.strip_prefix("mlrun_")?;
+        let (keyid, secret) = rest.split_once('_')?;
+        if keyid.is_empty() || secret.is_empty() {
+            return None;
+        }
+        Some(Self {
+            keyid: keyid.to_string(),
+            secret: secret.to_string(),
+        })
+    }
+
+    /// Compose a token string from its parts.
+    pub fn compose(keyid: &str, secret: &str) -> String {
+        format!("mlrun_{keyid}_{secret}")
+    }
+}
+
+/// Generate a new API token. Returns `(keyid, token)`: `keyid` is the
+/// cleartext identifier to use as the storage key, `token` is the full
+/// `mlrun_<keyid>_<secret>` string returned to the caller (the secret is
+/// never stored - only its hash is, via [`hash_api_key`]).
+pub fn generate_api_key() -> (String, String) {
+    let keyid = uuid::Uuid::now_v7().to_string();
+    let secret = generate_secret();
+    let token = ApiToken::compose(&keyid, &secret);
+    (keyid, token)
+}
+
+/// Generate the random secret portion of a token.
+fn generate_secret() -> String {
+    use rand::Rng;
+    let mut rng = rand::rng();
+    let bytes: Vec<u8> = (0..32).map(|_| rng.random()).collect();
+    hex::encode(bytes)
+}
+
+/// Compare a presented secret against its stored hash in constant time, so
+/// validation doesn't leak how many leading bytes of the hash matched.
+pub fn verify_secret(secret: &str, expected_hash: &str) -> bool {
+    let actual_hash = hash_api_key(secret);
+    actual_hash
+        .as_bytes()
+        .ct_eq(expected_hash.as_bytes())
+        .into()
+}
+
+/// Extract API key from request headers.
+pub fn extract_api_key_from_headers(parts: &Parts) -> Option<String> {
+    // Try Authorization: Bearer <key>
+    if let Some(auth_header) = parts.headers.get("authorization") {
+        if let Ok(auth_str) = auth_header.to_str() {
+            if let Some(key) = auth_str.strip_prefix("Bearer ") {
+                return Some(key.trim().to_string());
+            }
+        }
+    }
+
+    // Try X-API-Key header
+    if let Some(key_header) = parts.headers.get("x-api-key") {
+        if let Ok(key_str) = key_header.to_str() {
+            return Some(key_str.trim().to_string());
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hash_api_key() {
+        let key = "mlrun_test123";
+        let hash = hash_api_key(key);
+
+        // Same key should produce same hash
+        assert_eq!(hash, hash_api_key(key));
+
+        // Different key should produce different hash
+        assert_ne!(hash, hash_api_key("mlrun_test456"));
+    }
+
+    #[test]
+    fn test_generate_api_key() {
+        let (keyid1, token1) = generate_api_key();
+        let (keyid2, token2) = generate_api_key();
+
+        // Keys should be unique
+        assert_ne!(keyid1, keyid2);
+        assert_ne!(token1, token2);
+
+        // Tokens should start with prefix and embed the keyid
+        assert!(token1.starts_with("mlrun_"));
+        assert!(token1.contains(&keyid1));
+
+        // Tokens should be reasonable length
+        assert!(token1.len() > 40);
+    }
+
+    #[test]
+    fn test_token_parse_roundtrip() {
+        let (keyid, token) = generate_api_key();
+        let parsed = ApiToken::parse(&token).unwrap();
+
+        assert_eq!(parsed.keyid, keyid);
+        assert_eq!(ApiToken::compose(&parsed.keyid, &parsed.secret), token);
+    }
+
+    #[test]
+    fn test_token_parse_rejects_malformed() {
+        assert!(ApiToken::parse("not-a-token").is_none());
+        assert!(ApiToken::parse("mlrun_").is_none());
+        assert!(ApiToken::parse("mlrun_keyid-only").is_none());
+        assert!(ApiToken::parse("wrongprefix_keyid_secret").is_none());
+    }
+
+    #[test]
+    fn test_verify_secret() {
+        let secret = "super-secret-value";
+        let hash = hash_api_key(secret);
+
+        assert!(verify_secret(secret, &hash));
+        assert!(!verify_secret("wrong-secret", &hash));
+    }
+
+    #[test]
+    fn test_api_key_scopes() {
+        let key = ApiKey {
+            id: "test".to_string(),
+            key_hash: "hash".to_string(),
+            key_prefix: "mlrun_te".to_string(),
+            project_id: Some("project-123".to_string()),
+            name: Some("test".to_string()),
+            scopes: vec!["ingest".to_string(), "query".to_string()],
+            created_at: std::time::SystemTime::now(),
+            last_used_at: None,
+            revoked_at: None,
+            expires_at: None,
+        };
+
+        assert!(key.has_scope("ingest"));
+        assert!(key.has_scope("query"));
+        assert!(!key.has_scope("admin"));
+
+        // Admin key should have all scopes
+        let admin_key = ApiKey {
+            id: "admin".to_string(),
+            key_hash: "hash".to_string(),
+            key_prefix: "mlrun_ad".to_string(),
+            project_id: None,
+            name: Some("admin".to_string()),
+            scopes: vec!["admin".to_string()],
+            created_at: std::time::SystemTime::now(),
+            last_used_at: None,
+            revoked_at: None,
+            expires_at: None,
+        };
+
+        assert!(admin_key.has_scope("anything"));
+        assert!(admin_key.has_scope("admin"));
+    }
+
+    #[test]
+    fn test_project_access() {
+        // Project-scoped key
+        let project_key = ApiKey {
+            id: "test".to_string(),
+            key_hash: "hash".to_string(),
+            key_prefix: "mlrun_te".to_string(),
+            project_id: Some("project-123".to_string()),
+            name: None,
+            scopes: vec!["ingest".to_string()],
+            created_at: std::time::SystemTime::now(),
+            last_used_at: None,
+            revoked_at: None,
+            expires_at: None,
+        };
+
+        assert!(project_key.can_access_project("project-123"));
+        assert!(!project_key.can_access_project("project-456"));
+
+        // Global admin key
+        let admin_key = ApiKey {
+            id: "admin".to_string(),
+            key_hash: "hash".to_string(),
+            key_prefix: "mlrun_ad".to_string(),
+            project_id: None,
+            name: None,
+            scopes: vec!["admin".to_string()],
+            created_at: std::time::SystemTime::now(),
+            last_used_at: None,
+            revoked_at: None,
+            expires_at: None,
+        };
+
+        assert!(admin_key.can_access_project("project-123"));
+        assert!(admin_key.can_access_project("project-456"));
+    }
+
+    #[test]
+    fn test_key_expiration() {
+        let now = std::time::SystemTime::now();
+
+        let expired_key = ApiKey {
+            id: "test".to_string(),
+            key_hash: "hash".to_string(),
+            key_prefix: "mlrun_te".to_string(),
+            project_id: None,
+            name: None,
+            scopes: vec!["ingest".to_string()],
+            created_at: now,
+            last_used_at: None,
+            revoked_at: None,
+            expires_at: Some(now - std::time::Duration::from_secs(60)),
+        };
+        assert!(!expired_key.is_valid());
+        assert!(expired_key.is_expired());
+
+        let live_key = ApiKey {
+            expires_at: Some(now + std::time::Duration::from_secs(3600)),
+            ..expired_key
+        };
+        assert!(live_key.is_valid());
+        assert!(!live_key.is_expired());
+    }
+
+    #[test]
+    fn test_on_behalf_of_restricts_admin_context() {
+        let admin_key = ApiKey {
+            id: "admin".to_string(),
+            key_hash: "hash".to_string(),
+            key_prefix: "mlrun_ad".to_string(),
+            project_id: None,
+            name: None,
+            scopes: vec!["admin".to_string()],
+            created_at: std::time::SystemTime::now(),
+            last_used_at: None,
+            revoked_at: None,
+            expires_at: None,
+        };
+
+        // Without delegation, a global admin key can access any project.
+        let ctx = AuthContext {
+            api_key: admin_key.clone(),
+            is_dev_mode: false,
+            on_behalf_of_project: None,
+        };
+        assert!(ctx.can_access_project("project-123"));
+        assert!(ctx.can_access_project("project-456"));
+
+        // Delegated to a specific project, it's restricted to that one.
+        let delegated_ctx = AuthContext {
+            api_key: admin_key,
+            is_dev_mode: false,
+            on_behalf_of_project: Some("project-123".to_string()),
+        };
+        assert!(delegated_ctx.can_access_project("project-123"));
+        assert!(!delegated_ctx.can_access_project("project-456"));
+    }
+
+    #[test]
+    fn test_restricted_project() {
+        let global_admin = ApiKey {
+            id: "admin".to_string(),
+            key_hash: "hash".to_string(),
+            key_prefix: "mlrun_ad".to_string(),
+            project_id: None,
+            name: None,
+            scopes: vec!["admin".to_string()],
+            created_at: std::time::SystemTime::now(),
+            last_used_at: None,
+            revoked_at: None,
+            expires_at: None,
+        };
+
+        // No project and no delegation: unrestricted.
+        let ctx = AuthContext {
+            api_key: global_admin.clone(),
+            is_dev_mode: false,
+            on_behalf_of_project: None,
+        };
+        assert_eq!(ctx.restricted_project(), None);
+
+        // Delegated: restricted to the delegated project, even though the
+        // underlying key is a global admin.
+        let delegated_ctx = AuthContext {
+            api_key: global_admin,
+            is_dev_mode: false,
+            on_behalf_of_project: Some("project-123".to_string()),
+        };
+        assert_eq!(delegated_ctx.restricted_project(), Some("project-123"));
+
+        // A project-scoped key (no delegation) is restricted to its own
+        // project.
+        let project_key = ApiKey {
+            id: "test".to_string(),
+            key_hash: "hash".to_string(),
+            key_prefix: "mlrun_te".to_string(),
+            project_id: Some("project-456".to_string()),
+            name: None,
+            scopes: vec!["ingest".to_string()],
+            created_at: std::time::SystemTime::now(),
+            last_used_at: None,
+            revoked_at: None,
+            expires_at: None,
+        };
+        let project_ctx = AuthContext {
+            api_key: project_key,
+            is_dev_mode: false,
+            on_behalf_of_project: None,
+        };
+        assert_eq!(project_ctx.restricted_project(), Some("project-456"));
+    }
+}
@@ -0,0 +1,266 @@
+//! Policy-based authorization enforcer.
+//!
+//! A small, in-process Casbin-style RBAC engine: policies and groupings are
+//! held in memory and consulted on every request via [`Authorizer::enforce`].
+//! `scopes` on an `ApiKey` are mapped onto `role:{scope}` subjects so keys
+//! created before this subsystem existed keep working unchanged - see
+//! [`Authorizer::with_scope_compat`].
+
+use std::collections::{HashSet, VecDeque};
+
+use tokio::sync::RwLock;
+
+use crate::auth::ApiKey;
+
+use super::model::{Grouping, Policy};
+
+/// Policy-based enforcer: resolves a requester's roles via the grouping
+/// (`g`) relation, then checks whether any policy held by a resolved
+/// subject grants the requested `(object, action)`.
+#[derive(Debug, Default)]
+pub struct Authorizer {
+    policies: RwLock<Vec<Policy>>,
+    groupings: RwLock<Vec<Grouping>>,
+}
+
+impl Authorizer {
+    /// An enforcer with no policies; everything is denied until policies
+    /// and groupings are added.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// An enforcer seeded with the default scope -> role compatibility
+    /// policies, so existing `admin` / `ingest` / `query` scoped keys
+    /// continue to work unchanged.
+    pub fn with_scope_compat() -> Self {
+        let authorizer = Self::new();
+        authorizer.seed_scope_compat();
+        authorizer
+    }
+
+    /// Seed the well-known scope-derived roles. Only called during
+    /// construction, before the lock can be contended.
+    fn seed_scope_compat(&self) {
+        let mut policies = self
+            .policies
+            .try_write()
+            .expect("lock is uncontended during construction");
+        policies.push(Policy::new("role:admin", "*", "*"));
+        policies.push(Policy::new("role:ingest", "project:*", "ingest"));
+        policies.push(Policy::new("role:query", "project:*", "query"));
+    }
+
+    /// Add a policy tuple at runtime (e.g. loaded from config).
+    pub async fn add_policy(
+        &self,
+        subject: impl Into<String>,
+        object: impl Into<String>,
+        action: impl Into<String>,
+    ) {
+        self.policies
+            .write()
+            .await
+            .push(Policy::new(subject, object, action));
+    }
+
+    /// Add a grouping (`g`) tuple: `subject` inherits everything granted
+    /// to `role`.
+    pub async fn add_grouping(&self, subject: impl Into<String>, role: impl Into<String>) {
+        self.groupings
+            .write()
+            .await
+            .push(Grouping::new(subject, role));
+    }
+
+    /// Resolve the full set of subjects a requester may act as: itself,
+    /// plus every role reachable through the grouping relation (BFS, so
+    /// roles may nest - a role inheriting another role's policies).
+    async fn resolve_subjects(&self, subject: &str) -> HashSet<String> {
+        let groupings = self.groupings.read().await;
+        let mut resolved = HashSet::new();
+        let mut queue = VecDeque::new();
+        queue.push_back(subject.to_string());
+        resolved.insert(subject.to_string());
+
+        while let Some(current) = queue.pop_front() {
+            for grouping in groupings.iter().filter(|g| g.subject == current) {
+                if resolved.insert(grouping.role.clone()) {
+                    queue.push_back(grouping.role.clone());
+                }
+            }
+        }
+
+        resolved
+    }
+
+    /// `enforce(subject, object, action) -> bool`: may `subject` perform
+    /// `action` on `object`, after resolving roles it inherits?
+    pub async fn enforce(&self, subject: &str, object: &str, action: &str) -> bool {
+        let subjects = self.resolve_subjects(subject).await;
+        let policies = self.policies.read().await;
+
+        policies
+            .iter()
+            .any(|p| subjects.contains(&p.subject) && p.grants(object, action))
+    }
+
+    /// Convenience: enforce against every subject an `ApiKey` resolves to
+    /// (its id, `role:{scope}` for each of its scopes, and - for
+    /// project-scoped keys - a `project:{id}:member` subject).
+    ///
+    /// Project-scoped keys are grounded to their own project before any
+    /// policy is consulted: `object` is only ever checked as the key's own
+    /// `project:{id}`, never the literal value passed in. Without this, the
+    /// `role:ingest`/`role:query` compat policies - seeded with the
+    /// `project:*` pattern so pre-RBAC keys keep working - would let a
+    /// project-scoped key act on every project, since `project:*` matches
+    /// any concrete object and `authz_middleware` falls back to exactly
+    /// that wildcard for routes keyed by request body rather than path.
+    pub async fn enforce_for_key(&self, key: &ApiKey, object: &str, action: &str) -> bool {
+        let grounded_object = match &key.project_id {
+            Some(project_id) => {
+                let own_project = format!("project:{project_id}");
+                if object != "project:*" && object != own_project {
+                    return false;
+                }
+                own_project
+            }
+            None => object.to_string(),
+        };
+
+        for subject in subjects_for_key(key) {
+            if self.enforce(&subject, &grounded_object, action).await {
+                return true;
+            }
+        }
+        false
+    }
+}
+
+/// The subjects a given API key maps onto: its bare id, a `role:{scope}`
+/// subject for every scope it carries, and - for project-scoped keys - a
+/// `project:{id}:member` subject. `scopes` stay a compatibility shim on top
+/// of the policy engine - callers that want finer-grained rules can grant
+/// policies directly to `key.id` or `project:{id}:member`.
+pub fn subjects_for_key(key: &ApiKey) -> Vec<String> {
+    let mut subjects: Vec<String> = vec![key.id.clone()];
+    subjects.extend(key.scopes.iter().map(|scope| format!("role:{scope}")));
+    if let Some(project_id) = &key.project_id {
+        subjects.push(format!("project:{project_id}:member"));
+    }
+    subjects
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_key(scopes: Vec<&str>, project_id: Option<&str>) -> ApiKey {
+        ApiKey {
+            id: "key-1".to_string(),
+            key_hash: "hash".to_string(),
+            key_prefix: "mlrun_te".to_string(),
+            project_id: project_id.map(str::to_string),
+            name: None,
+            scopes: scopes.into_iter().map(str::to_string).collect(),
+            created_at: std::time::SystemTime::now(),
+            last_used_at: None,
+            revoked_at: None,
+            expires_at: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_admin_role_grants_everything() {
+        let authorizer = Authorizer::with_scope_compat();
+        let key = test_key(vec!["admin"], None);
+
+        assert!(
+            authorizer
+                .enforce_for_key(&key, "project:x", "ingest")
+                .await
+        );
+        assert!(authorizer.enforce_for_key(&key, "project:y", "query").await);
+    }
+
+    #[tokio::test]
+    async fn test_scope_compat_limits_action() {
+        let authorizer = Authorizer::with_scope_compat();
+        let ingest_key = test_key(vec!["ingest"], None);
+
+        assert!(
+            authorizer
+                .enforce_for_key(&ingest_key, "project:x", "ingest")
+                .await
+        );
+        assert!(
+            !authorizer
+                .enforce_for_key(&ingest_key, "project:x", "query")
+                .await
+        );
+    }
+
+    #[tokio::test]
+    async fn test_no_scopes_denies_everything() {
+        let authorizer = Authorizer::with_scope_compat();
+        let key = test_key(vec![], None);
+
+        assert!(
+            !authorizer
+                .enforce_for_key(&key, "project:x", "ingest")
+                .await
+        );
+        assert!(!authorizer.enforce_for_key(&key, "project:x", "query").await);
+    }
+
+    #[tokio::test]
+    async fn test_project_scoped_key_is_grounded_to_its_own_project() {
+        let authorizer = Authorizer::with_scope_compat();
+        let key = test_key(vec!["ingest"], Some("proj-a"));
+
+        // Its own project - whether resolved to a concrete object or left
+        // as the coarse `project:*` fallback `authz_middleware` uses for
+        // body-keyed routes - is granted.
+        assert!(
+            authorizer
+                .enforce_for_key(&key, "project:proj-a", "ingest")
+                .await
+        );
+        assert!(authorizer.enforce_for_key(&key, "project:*", "ingest").await);
+
+        // A different, concrete project is denied even though the seeded
+        // `role:ingest` policy's object pattern (`project:*`) would
+        // otherwise match it - this is the regression the grounding fixes.
+        assert!(
+            !authorizer
+                .enforce_for_key(&key, "project:proj-b", "ingest")
+                .await
+        );
+    }
+
+    #[tokio::test]
+    async fn test_global_key_keeps_cross_project_compat_grant() {
+        let authorizer = Authorizer::with_scope_compat();
+        let key = test_key(vec!["query"], None);
+
+        // A key with no `project_id` is the pre-RBAC "global" concept -
+        // unrestricted by any single project, matching `ApiKey::
+        // can_access_project`'s treatment of `project_id: None`.
+        assert!(authorizer.enforce_for_key(&key, "project:x", "query").await);
+        assert!(authorizer.enforce_for_key(&key, "project:y", "query").await);
+    }
+
+    #[tokio::test]
+    async fn test_grouping_resolves_nested_roles() {
+        let authorizer = Authorizer::new();
+        authorizer
+            .add_policy("role:reader", "project:x", "query")
+            .await;
+        authorizer.add_grouping("role:support", "role:reader").await;
+        authorizer.add_grouping("alice", "role:support").await;
+
+        assert!(authorizer.enforce("alice", "project:x", "query").await);
+        assert!(!authorizer.enforce("alice", "project:x", "ingest").await);
+    }
+}
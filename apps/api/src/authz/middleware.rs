@@ -0,0 +1,111 @@
+//! Axum middleware that enforces policy after [`auth_middleware`] has
+//! populated the request's [`AuthContext`].
+//!
+//! [`auth_middleware`]: crate::auth::auth_middleware
+//! [`AuthContext`]: crate::auth::AuthContext
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, Request, State},
+    http::{Method, StatusCode},
+    middleware::Next,
+    response::Response,
+    RequestExt,
+};
+
+use crate::auth::{AuthContext, AuthError};
+use crate::storage::RunStore;
+
+use super::enforcer::Authorizer;
+
+/// State `authz_middleware` needs: the policy engine plus the run store,
+/// used to resolve a concrete `project:{id}` object for routes keyed by
+/// `run_id` (see [`project_object`]).
+#[derive(Clone)]
+pub struct AuthzState {
+    pub authorizer: Arc<Authorizer>,
+    pub run_store: Arc<dyn RunStore>,
+}
+
+/// Middleware: look up the route's `(object, action)` pair and deny the
+/// request unless the authenticated key's resolved subjects are granted
+/// it by the [`Authorizer`].
+///
+/// The object is `project:{id}` whenever the route names a `run_id` path
+/// param (finish/stream/get/artifacts) - resolved via the run store so
+/// policies like "ingest only in project Y" are actually enforceable, not
+/// just a coarse allow-everything wildcard. Routes that only carry the
+/// project in the JSON body (e.g. `InitRunHttpRequest::project`) still
+/// fall back to the coarse `project:*` object here; handlers call
+/// `AuthContext::can_access_project` once they've parsed the body for
+/// per-project enforcement on those (honoring any `X-On-Behalf-Of`
+/// delegation too, which this coarse middleware check alone cannot).
+/// `project:*` itself is never a free pass for a project-scoped key - see
+/// [`super::enforcer::Authorizer::enforce_for_key`], which grounds it down
+/// to the key's own project before consulting policy.
+pub async fn authz_middleware(
+    State(authz): State<AuthzState>,
+    mut request: Request,
+    next: Next,
+) -> Result<Response, (StatusCode, String)> {
+    let Some(ctx) = request.extensions().get::<AuthContext>() else {
+        // auth_middleware should always run first and insert this; if it
+        // didn't, fail closed rather than silently allowing the request.
+        return Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "missing auth context".to_string(),
+        ));
+    };
+
+    if ctx.is_dev_mode {
+        return Ok(next.run(request).await);
+    }
+
+    let action = action_for_method(request.method());
+    let api_key = ctx.api_key.clone();
+    let object = project_object(&mut request, &authz.run_store).await;
+
+    if !authz
+        .authorizer
+        .enforce_for_key(&api_key, &object, action)
+        .await
+    {
+        return Err((
+            AuthError::InsufficientScope.status_code(),
+            AuthError::InsufficientScope.message().to_string(),
+        ));
+    }
+
+    Ok(next.run(request).await)
+}
+
+/// Resolve the policy object for `request`: `project:{id}` if its path has
+/// a `run_id` param that resolves to a real run, `project:*` otherwise
+/// (no `run_id` in the path, or the id doesn't resolve - the handler's own
+/// 404 takes care of that case, this just falls back to the coarse grant
+/// check rather than denying on a bad id authz shouldn't be the one to
+/// judge).
+async fn project_object(request: &mut Request, run_store: &Arc<dyn RunStore>) -> String {
+    let Ok(Path(params)) = request.extract_parts::<Path<HashMap<String, String>>>().await else {
+        return "project:*".to_string();
+    };
+    let Some(run_id) = params.get("run_id") else {
+        return "project:*".to_string();
+    };
+    match run_store.get_run(run_id).await {
+        Some(run) => format!("project:{}", run.project_id),
+        None => "project:*".to_string(),
+    }
+}
+
+/// Map an HTTP method to the coarse action it represents for policy
+/// purposes. Mutating verbs require `ingest`; read-only verbs require
+/// `query`.
+fn action_for_method(method: &Method) -> &'static str {
+    match *method {
+        Method::GET | Method::HEAD | Method::OPTIONS => "query",
+        _ => "ingest",
+    }
+}
@@ -0,0 +1,15 @@
+//! Policy-based authorization.
+//!
+//! Replaces flat `ApiKey::scopes` string matching with a small Casbin-style
+//! RBAC engine: an [`Authorizer`] holds policy and grouping tuples, and
+//! [`Authorizer::enforce`] answers `(subject, object, action)` requests.
+//! `scopes` are kept as a compatibility shim (`Authorizer::with_scope_compat`)
+//! so existing keys keep working unchanged.
+
+mod enforcer;
+mod middleware;
+mod model;
+
+pub use enforcer::{subjects_for_key, Authorizer};
+pub use middleware::{authz_middleware, AuthzState};
+pub use model::{Grouping, Policy};
@@ -0,0 +1,88 @@
+//! Core RBAC model types: policy and grouping (`g`) tuples.
+//!
+//! Mirrors the request-definition / policy-definition / role-definition
+//! split of a Casbin model: a request is `(subject, object, action)`, a
+//! policy grants `action` on `object` to `subject`, and a grouping says
+//! `subject` inherits everything granted to `role` (so roles can nest,
+//! e.g. a `support_engineer` role inheriting the `query` role).
+
+/// A single policy tuple: `subject` may do `action` on `object`.
+///
+/// `object` and `action` may be the wildcard `"*"`, which matches any
+/// value. `subject` is matched against the full set of subjects resolved
+/// for a request (the requester plus every role it inherits via `g`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Policy {
+    pub subject: String,
+    pub object: String,
+    pub action: String,
+}
+
+impl Policy {
+    pub fn new(
+        subject: impl Into<String>,
+        object: impl Into<String>,
+        action: impl Into<String>,
+    ) -> Self {
+        Self {
+            subject: subject.into(),
+            object: object.into(),
+            action: action.into(),
+        }
+    }
+
+    /// Whether this policy grants `action` on `object`, given that its
+    /// `subject` is already known to be in the requester's resolved set.
+    pub fn grants(&self, object: &str, action: &str) -> bool {
+        matches_pattern(&self.object, object) && matches_pattern(&self.action, action)
+    }
+}
+
+/// A grouping (`g`) tuple: `subject` inherits the `role`'s policies.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grouping {
+    pub subject: String,
+    pub role: String,
+}
+
+impl Grouping {
+    pub fn new(subject: impl Into<String>, role: impl Into<String>) -> Self {
+        Self {
+            subject: subject.into(),
+            role: role.into(),
+        }
+    }
+}
+
+/// `"*"` matches anything; a pattern ending in `*` is a prefix match (e.g.
+/// `"project:*"` matches `"project:123"`); otherwise an exact string match.
+fn matches_pattern(pattern: &str, value: &str) -> bool {
+    match pattern.strip_suffix('*') {
+        Some(prefix) => value.starts_with(prefix),
+        None => pattern == value,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wildcard_matches_anything() {
+        assert!(matches_pattern("*", "project:x"));
+        assert!(matches_pattern("*", ""));
+    }
+
+    #[test]
+    fn prefix_pattern_matches_concrete_objects() {
+        assert!(matches_pattern("project:*", "project:123"));
+        assert!(matches_pattern("project:*", "project:"));
+        assert!(!matches_pattern("project:*", "run:123"));
+    }
+
+    #[test]
+    fn exact_pattern_requires_exact_match() {
+        assert!(matches_pattern("ingest", "ingest"));
+        assert!(!matches_pattern("ingest", "query"));
+    }
+}
@@ -44,6 +44,237 @@ impl IngestMode {
     }
 }
 
+/// Which backend persists run lifecycle metadata (see `storage::RunStore`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RunStoreBackend {
+    /// In-memory map: runs vanish on restart (alpha/dev default).
+    Memory,
+    /// Durable PostgreSQL-backed store.
+    Postgres,
+}
+
+impl Default for RunStoreBackend {
+    fn default() -> Self {
+        Self::Memory
+    }
+}
+
+impl RunStoreBackend {
+    /// Parse from the `RUN_STORE_BACKEND` environment variable.
+    pub fn from_env() -> Self {
+        std::env::var("RUN_STORE_BACKEND")
+            .ok()
+            .and_then(|s| match s.to_lowercase().as_str() {
+                "memory" => Some(Self::Memory),
+                "postgres" => Some(Self::Postgres),
+                _ => None,
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Memory => "memory",
+            Self::Postgres => "postgres",
+        }
+    }
+}
+
+/// Which backend persists artifact bytes (see `storage::ArtifactBackend`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArtifactBackendKind {
+    /// MinIO/S3-compatible object storage (production default).
+    S3,
+    /// Local disk, under `ARTIFACT_LOCAL_ROOT`: no MinIO server required,
+    /// for dev/offline use.
+    LocalFs,
+}
+
+impl Default for ArtifactBackendKind {
+    fn default() -> Self {
+        Self::S3
+    }
+}
+
+impl ArtifactBackendKind {
+    /// Parse from the `ARTIFACT_BACKEND` environment variable.
+    pub fn from_env() -> Self {
+        std::env::var("ARTIFACT_BACKEND")
+            .ok()
+            .and_then(|s| match s.to_lowercase().as_str() {
+                "s3" => Some(Self::S3),
+                "local" | "localfs" => Some(Self::LocalFs),
+                _ => None,
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::S3 => "s3",
+            Self::LocalFs => "local",
+        }
+    }
+}
+
+/// Which backend persists logged metric points (see `storage::MetricsRepo`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetricsRepoBackend {
+    /// In-memory map: metrics vanish on restart (alpha/dev default).
+    Memory,
+    /// Durable PostgreSQL-backed store.
+    Postgres,
+}
+
+impl Default for MetricsRepoBackend {
+    fn default() -> Self {
+        Self::Memory
+    }
+}
+
+impl MetricsRepoBackend {
+    /// Parse from the `METRICS_STORE_BACKEND` environment variable.
+    pub fn from_env() -> Self {
+        std::env::var("METRICS_STORE_BACKEND")
+            .ok()
+            .and_then(|s| match s.to_lowercase().as_str() {
+                "memory" => Some(Self::Memory),
+                "postgres" => Some(Self::Postgres),
+                _ => None,
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Memory => "memory",
+            Self::Postgres => "postgres",
+        }
+    }
+}
+
+/// Which backend persists batch/sequence idempotency state (see
+/// `services::idempotency_wal::IdempotencyBackend`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IdempotencyStoreBackend {
+    /// In-memory map: dedup state vanishes on restart (alpha/dev default).
+    Memory,
+    /// Durable write-ahead log, recovered on startup (see
+    /// `services::idempotency_wal`).
+    Wal,
+}
+
+impl Default for IdempotencyStoreBackend {
+    fn default() -> Self {
+        Self::Memory
+    }
+}
+
+impl IdempotencyStoreBackend {
+    /// Parse from the `IDEMPOTENCY_STORE_BACKEND` environment variable.
+    pub fn from_env() -> Self {
+        std::env::var("IDEMPOTENCY_STORE_BACKEND")
+            .ok()
+            .and_then(|s| match s.to_lowercase().as_str() {
+                "memory" => Some(Self::Memory),
+                "wal" => Some(Self::Wal),
+                _ => None,
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Memory => "memory",
+            Self::Wal => "wal",
+        }
+    }
+}
+
+/// Which backend persists API keys (see `auth::ApiKeyStore`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApiKeyStoreBackend {
+    /// In-memory map: keys vanish on restart (alpha/dev default).
+    Memory,
+    /// Durable PostgreSQL-backed store.
+    Postgres,
+}
+
+impl Default for ApiKeyStoreBackend {
+    fn default() -> Self {
+        Self::Memory
+    }
+}
+
+impl ApiKeyStoreBackend {
+    /// Parse from the `API_KEY_STORE_BACKEND` environment variable.
+    pub fn from_env() -> Self {
+        std::env::var("API_KEY_STORE_BACKEND")
+            .ok()
+            .and_then(|s| match s.to_lowercase().as_str() {
+                "memory" => Some(Self::Memory),
+                "postgres" => Some(Self::Postgres),
+                _ => None,
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Memory => "memory",
+            Self::Postgres => "postgres",
+        }
+    }
+}
+
+/// Which write-ahead queue backend serves `IngestMode::Queued`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QueueKind {
+    /// Redis Streams, consumed via a consumer group.
+    Redis,
+    /// Kafka, consumed via a consumer group with manual offset commits.
+    Kafka,
+}
+
+impl QueueKind {
+    fn from_str(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "redis" => Some(Self::Redis),
+            "kafka" => Some(Self::Kafka),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Redis => "redis",
+            Self::Kafka => "kafka",
+        }
+    }
+}
+
+/// Write-ahead queue settings, required when `ingest_mode` is `Queued`.
+#[derive(Debug, Clone)]
+pub struct QueueConfig {
+    pub kind: QueueKind,
+    pub url: String,
+}
+
+impl QueueConfig {
+    /// Parse from `INGEST_QUEUE_KIND`/`INGEST_QUEUE_URL`. Returns `None`
+    /// if either is missing, empty, or `INGEST_QUEUE_KIND` isn't a
+    /// recognized backend.
+    fn from_env() -> Option<Self> {
+        let kind = std::env::var("INGEST_QUEUE_KIND")
+            .ok()
+            .and_then(|s| QueueKind::from_str(&s))?;
+        let url = std::env::var("INGEST_QUEUE_URL")
+            .ok()
+            .filter(|s| !s.is_empty())?;
+        Some(Self { kind, url })
+    }
+}
+
 /// Server configuration.
 #[derive(Debug, Clone)]
 pub struct ServerConfig {
@@ -53,6 +284,23 @@ pub struct ServerConfig {
     pub grpc_addr: SocketAddr,
     /// Ingest mode (direct or queued)
     pub ingest_mode: IngestMode,
+    /// Write-ahead queue settings; `Some` only when `ingest_mode` is
+    /// `Queued` and the settings validated.
+    pub queue: Option<QueueConfig>,
+    /// Which backend persists run lifecycle metadata.
+    pub run_store_backend: RunStoreBackend,
+    /// Which backend persists artifact bytes.
+    pub artifact_backend: ArtifactBackendKind,
+    /// Root directory for `ArtifactBackendKind::LocalFs`.
+    pub artifact_local_root: String,
+    /// Which backend persists logged metric points.
+    pub metrics_repo_backend: MetricsRepoBackend,
+    /// Which backend persists batch/sequence idempotency state.
+    pub idempotency_store_backend: IdempotencyStoreBackend,
+    /// Directory for `IdempotencyStoreBackend::Wal`'s segment files.
+    pub idempotency_wal_dir: String,
+    /// Which backend persists API keys.
+    pub api_key_store_backend: ApiKeyStoreBackend,
     /// Log level
     pub log_level: String,
 }
@@ -63,6 +311,14 @@ impl Default for ServerConfig {
             http_addr: "0.0.0.0:3001".parse().unwrap(),
             grpc_addr: "0.0.0.0:50051".parse().unwrap(),
             ingest_mode: IngestMode::Direct,
+            queue: None,
+            run_store_backend: RunStoreBackend::Memory,
+            artifact_backend: ArtifactBackendKind::S3,
+            artifact_local_root: "./data/artifacts".to_string(),
+            metrics_repo_backend: MetricsRepoBackend::Memory,
+            idempotency_store_backend: IdempotencyStoreBackend::Memory,
+            idempotency_wal_dir: "./data/idempotency_wal".to_string(),
+            api_key_store_backend: ApiKeyStoreBackend::Memory,
             log_level: "info,mlrun_api=debug".to_string(),
         }
     }
@@ -83,10 +339,39 @@ impl ServerConfig {
 
         let host = std::env::var("API_HOST").unwrap_or_else(|_| "0.0.0.0".to_string());
 
+        let requested_mode = IngestMode::from_env();
+        let queue = QueueConfig::from_env();
+
+        // Queued mode needs a validated queue backend; fall back to
+        // direct mode rather than starting up unable to ingest anything.
+        let ingest_mode = if requested_mode == IngestMode::Queued && queue.is_none() {
+            tracing::warn!(
+                "INGEST_MODE=queued requires INGEST_QUEUE_KIND (redis|kafka) and a non-empty \
+                 INGEST_QUEUE_URL; falling back to direct mode"
+            );
+            IngestMode::Direct
+        } else {
+            requested_mode
+        };
+
         Self {
             http_addr: format!("{}:{}", host, http_port).parse().unwrap(),
             grpc_addr: format!("{}:{}", host, grpc_port).parse().unwrap(),
-            ingest_mode: IngestMode::from_env(),
+            ingest_mode,
+            queue: if ingest_mode == IngestMode::Queued {
+                queue
+            } else {
+                None
+            },
+            run_store_backend: RunStoreBackend::from_env(),
+            artifact_backend: ArtifactBackendKind::from_env(),
+            artifact_local_root: std::env::var("ARTIFACT_LOCAL_ROOT")
+                .unwrap_or_else(|_| "./data/artifacts".to_string()),
+            metrics_repo_backend: MetricsRepoBackend::from_env(),
+            idempotency_store_backend: IdempotencyStoreBackend::from_env(),
+            idempotency_wal_dir: std::env::var("IDEMPOTENCY_WAL_DIR")
+                .unwrap_or_else(|_| "./data/idempotency_wal".to_string()),
+            api_key_store_backend: ApiKeyStoreBackend::from_env(),
             log_level: std::env::var("RUST_LOG")
                 .unwrap_or_else(|_| "info,mlrun_api=debug".to_string()),
         }
@@ -105,6 +390,17 @@ impl ServerConfig {
                 IngestMode::Queued => "writes through queue",
             }
         );
+        if let Some(queue) = &self.queue {
+            info!("  Ingest Queue: {} ({})", queue.url, queue.kind.as_str());
+        }
+        info!("  Run Store: {}", self.run_store_backend.as_str());
+        info!("  Artifact Backend: {}", self.artifact_backend.as_str());
+        info!("  Metrics Store: {}", self.metrics_repo_backend.as_str());
+        info!(
+            "  Idempotency Store: {}",
+            self.idempotency_store_backend.as_str()
+        );
+        info!("  API Key Store: {}", self.api_key_store_backend.as_str());
     }
 }
 
@@ -129,4 +425,97 @@ mod tests {
         // Default is direct
         assert_eq!(IngestMode::default(), IngestMode::Direct);
     }
+
+    #[test]
+    fn test_queue_kind_from_str() {
+        assert_eq!(QueueKind::from_str("redis"), Some(QueueKind::Redis));
+        assert_eq!(QueueKind::from_str("KAFKA"), Some(QueueKind::Kafka));
+        assert_eq!(QueueKind::from_str("rabbitmq"), None);
+    }
+
+    #[test]
+    fn test_queue_kind_as_str_roundtrip() {
+        for kind in [QueueKind::Redis, QueueKind::Kafka] {
+            assert_eq!(QueueKind::from_str(kind.as_str()), Some(kind));
+        }
+    }
+
+    #[test]
+    fn test_artifact_backend_kind_defaults_to_s3() {
+        assert_eq!(ArtifactBackendKind::default(), ArtifactBackendKind::S3);
+    }
+
+    #[test]
+    fn test_artifact_backend_kind_as_str_roundtrip() {
+        for kind in [ArtifactBackendKind::S3, ArtifactBackendKind::LocalFs] {
+            assert_eq!(
+                match kind.as_str() {
+                    "s3" => ArtifactBackendKind::S3,
+                    "local" => ArtifactBackendKind::LocalFs,
+                    other => panic!("unexpected as_str: {other}"),
+                },
+                kind
+            );
+        }
+    }
+
+    #[test]
+    fn test_idempotency_store_backend_defaults_to_memory() {
+        assert_eq!(
+            IdempotencyStoreBackend::default(),
+            IdempotencyStoreBackend::Memory
+        );
+    }
+
+    #[test]
+    fn test_idempotency_store_backend_as_str_roundtrip() {
+        for backend in [IdempotencyStoreBackend::Memory, IdempotencyStoreBackend::Wal] {
+            assert_eq!(
+                match backend.as_str() {
+                    "memory" => IdempotencyStoreBackend::Memory,
+                    "wal" => IdempotencyStoreBackend::Wal,
+                    other => panic!("unexpected as_str: {other}"),
+                },
+                backend
+            );
+        }
+    }
+
+    #[test]
+    fn test_api_key_store_backend_defaults_to_memory() {
+        assert_eq!(ApiKeyStoreBackend::default(), ApiKeyStoreBackend::Memory);
+    }
+
+    #[test]
+    fn test_api_key_store_backend_as_str_roundtrip() {
+        for backend in [ApiKeyStoreBackend::Memory, ApiKeyStoreBackend::Postgres] {
+            assert_eq!(
+                match backend.as_str() {
+                    "memory" => ApiKeyStoreBackend::Memory,
+                    "postgres" => ApiKeyStoreBackend::Postgres,
+                    other => panic!("unexpected as_str: {other}"),
+                },
+                backend
+            );
+        }
+    }
+
+    #[test]
+    fn test_metrics_repo_backend_defaults_to_memory() {
+        assert_eq!(MetricsRepoBackend::default(), MetricsRepoBackend::Memory);
+    }
+
+    #[test]
+    fn test_metrics_repo_backend_as_str_roundtrip() {
+        for backend in [MetricsRepoBackend::Memory, MetricsRepoBackend::Postgres] {
+            assert_eq!(
+                match backend.as_str() {
+                    "memory" => MetricsRepoBackend::Memory,
+                    "postgres" => MetricsRepoBackend::Postgres,
+                    other => panic!("unexpected as_str: {other}"),
+                },
+                backend
+            );
+        }
+    }
 }
@@ -0,0 +1,15 @@
+//! Library surface for the `mlrun-api` server binary.
+//!
+//! `main.rs` builds the HTTP/gRPC stack around these modules directly; this
+//! crate root exists so sibling tools - notably `xtask bench` - can reuse
+//! server-side subsystems like the cardinality guardrail without dragging
+//! in the whole binary.
+
+pub mod auth;
+pub mod authz;
+pub mod config;
+pub mod metrics;
+pub mod notifier;
+pub mod queue;
+pub mod services;
+pub mod storage;
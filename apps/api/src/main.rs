@@ -7,6 +7,11 @@
 //! Architecture: Single binary serving both protocols on different ports.
 
 mod auth;
+mod authz;
+mod config;
+mod metrics;
+mod notifier;
+mod queue;
 mod services;
 mod storage;
 
@@ -17,33 +22,77 @@ use axum::{
     extract::State,
     http::StatusCode,
     middleware,
-    routing::{get, post},
-    Json, Router,
+    routing::{get, post, put},
+    Extension, Json, Router,
 };
+use futures::StreamExt;
 use serde::{Deserialize, Serialize};
 use tonic::transport::Server as TonicServer;
 use tracing::{info, warn};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
+use auth::{auth_middleware, ApiKeyStore, AuthContext, InMemoryApiKeyStore, PostgresApiKeyStore};
+use authz::{authz_middleware, AuthzState, Authorizer};
+use config::{
+    ApiKeyStoreBackend, ArtifactBackendKind, IdempotencyStoreBackend, IngestMode,
+    MetricsRepoBackend, RunStoreBackend,
+};
+use metrics::Metrics;
 use mlrun_proto::mlrun::v1::ingest_service_server::IngestServiceServer;
+use notifier::{Notifier, RunLifecycleEvent};
+use queue::{QueueMessage, QueueProducer, QueuedMetricPoint};
 use services::{
-    compute_payload_hash, ingest::InMemoryStore, IdempotencyResult, IdempotencyStore,
-    IngestServiceImpl, MetricPayload, ParamPayload, TagPayload,
+    compute_payload_hash, ingest::InMemoryStore, CardinalityTracker, IdempotencyBackend,
+    IdempotencyResult, IdempotencyStore, IngestServiceImpl, MetricPayload, ParamPayload,
+    TagCardinalityEstimator, TagPayload,
+};
+use storage::{
+    parse_run_status, run_status_as_str, ArtifactBackend, ArtifactRepository, ArtifactStore,
+    ArtifactType, BatchDelta, CreateArtifactInput, InMemoryMetricsRepo, InMemoryRunStore,
+    InitRunParams, ListRunsFilter, LocalFsBackend, MetricsRepo, MinioClient, MinioConfig,
+    PostgresMetricsRepo, PostgresRunStore, RunStore,
 };
-use auth::{ApiKeyStore, auth_middleware};
 
 /// Application state shared across handlers.
 #[derive(Clone)]
 pub struct AppState {
     store: Arc<InMemoryStore>,
-    key_store: Arc<ApiKeyStore>,
-    idempotency_store: Arc<IdempotencyStore>,
+    /// Durable run lifecycle metadata (in-memory or Postgres, see
+    /// [`storage::RunStore`]).
+    run_store: Arc<dyn RunStore>,
+    key_store: Arc<dyn ApiKeyStore>,
+    authorizer: Arc<Authorizer>,
+    idempotency_store: Arc<IdempotencyBackend>,
+    ingest_mode: IngestMode,
+    /// Set when `ingest_mode` is `Queued`: batches are enqueued here
+    /// instead of being written to the store synchronously.
+    queue_producer: Option<Arc<dyn QueueProducer>>,
+    /// Delivers signed webhook notifications on run lifecycle events.
+    notifier: Arc<Notifier>,
+    /// Counters/gauges exposed via `GET /metrics`.
+    metrics: Arc<Metrics>,
+    /// Mints presigned upload/download URLs for artifacts.
+    artifact_store: Arc<ArtifactStore>,
+    /// Tag/metric-name cardinality guardrail, also contributing counters
+    /// and gauges to `GET /metrics`.
+    cardinality_tracker: Arc<CardinalityTracker>,
 }
 
 // =============================================================================
 // HTTP Handlers
 // =============================================================================
 
+/// Map a [`storage::RunStoreError`] to the HTTP status it represents: a
+/// missing run is a 404, a backend failure is a 500.
+fn run_store_error_to_http(err: storage::RunStoreError) -> (StatusCode, String) {
+    match err {
+        storage::RunStoreError::NotFound(run_id) => {
+            (StatusCode::NOT_FOUND, format!("Run not found: {run_id}"))
+        }
+        storage::RunStoreError::Backend(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg),
+    }
+}
+
 async fn health() -> &'static str {
     "ok"
 }
@@ -52,6 +101,15 @@ async fn root() -> &'static str {
     "MLRun API v0.1.0"
 }
 
+/// Render server-internal counters/gauges in Prometheus text exposition
+/// format, so operators can scrape the ingest server directly instead of
+/// inferring health from logs.
+async fn prometheus_metrics(State(state): State<AppState>) -> String {
+    let mut out = state.metrics.render();
+    out.push_str(&state.cardinality_tracker.render_metrics().await);
+    out
+}
+
 /// Request to initialize a run via HTTP.
 #[derive(Debug, Deserialize)]
 struct InitRunHttpRequest {
@@ -72,39 +130,33 @@ struct InitRunHttpResponse {
 /// Initialize a run via HTTP (for SDK HTTP transport).
 async fn http_init_run(
     State(state): State<AppState>,
+    Extension(auth_ctx): Extension<AuthContext>,
     Json(req): Json<InitRunHttpRequest>,
 ) -> Result<Json<InitRunHttpResponse>, (StatusCode, String)> {
-    let run_id = req.run_id.unwrap_or_else(|| uuid::Uuid::now_v7().to_string());
-
-    let mut runs = state.store.runs.write().await;
+    check_project_access(&auth_ctx, &req.project)?;
+
+    let outcome = state
+        .run_store
+        .init_run(InitRunParams {
+            run_id: req.run_id,
+            project_id: req.project.clone(),
+            name: req.name.clone(),
+            tags: req.tags.unwrap_or_default(),
+        })
+        .await;
 
-    // Check if exists (idempotent)
-    if runs.contains_key(&run_id) {
+    if outcome.resumed {
         return Ok(Json(InitRunHttpResponse {
-            run_id,
+            run_id: outcome.record.run_id,
             offline: false,
         }));
     }
 
-    // Create new run
-    let now = std::time::SystemTime::now();
-    let run_state = services::ingest::RunState {
-        run_id: run_id.clone(),
-        project_id: req.project.clone(),
-        name: req.name.clone(),
-        status: mlrun_proto::mlrun::v1::RunStatus::Running,
-        created_at: now,
-        updated_at: now,
-        metrics_count: 0,
-        params_count: 0,
-        tags: req.tags.unwrap_or_default(),
-    };
-
-    runs.insert(run_id.clone(), run_state);
-    info!(run_id = %run_id, project = %req.project, "HTTP: Initialized run");
+    state.metrics.record_run_started();
+    info!(run_id = %outcome.record.run_id, project = %req.project, "HTTP: Initialized run");
 
     Ok(Json(InitRunHttpResponse {
-        run_id,
+        run_id: outcome.record.run_id,
         offline: false,
     }))
 }
@@ -168,14 +220,19 @@ struct IngestBatchHttpResponse {
 /// Ingest a batch of events via HTTP (for SDK HTTP transport).
 async fn http_ingest_batch(
     State(state): State<AppState>,
+    Extension(auth_ctx): Extension<AuthContext>,
     Json(req): Json<IngestBatchHttpRequest>,
-) -> Result<Json<IngestBatchHttpResponse>, (StatusCode, String)> {
+) -> Result<(StatusCode, Json<IngestBatchHttpResponse>), (StatusCode, String)> {
     // Generate batch_id if not provided
-    let batch_id = req.batch_id.unwrap_or_else(|| uuid::Uuid::now_v7().to_string());
+    let batch_id = req
+        .batch_id
+        .unwrap_or_else(|| uuid::Uuid::now_v7().to_string());
     let seq = req.seq.unwrap_or(0);
 
     // Convert request data for hashing
-    let metric_payloads: Vec<MetricPayload> = req.metrics.iter()
+    let metric_payloads: Vec<MetricPayload> = req
+        .metrics
+        .iter()
         .map(|m| MetricPayload {
             name: m.name.clone(),
             value: m.value,
@@ -183,14 +240,18 @@ async fn http_ingest_batch(
         })
         .collect();
 
-    let param_payloads: Vec<ParamPayload> = req.params.iter()
+    let param_payloads: Vec<ParamPayload> = req
+        .params
+        .iter()
         .map(|p| ParamPayload {
             name: p.name.clone(),
             value: p.value.clone(),
         })
         .collect();
 
-    let tag_payloads: Vec<TagPayload> = req.tags.iter()
+    let tag_payloads: Vec<TagPayload> = req
+        .tags
+        .iter()
         .map(|t| TagPayload {
             key: t.key.clone(),
             value: t.value.clone(),
@@ -205,43 +266,183 @@ async fn http_ingest_batch(
     let param_count = req.params.len();
     let tag_count = req.tags.len();
 
-    // Get project_id from run (read lock first)
-    let project_id = {
-        let runs = state.store.runs.read().await;
-        let run = runs.get(&req.run_id).ok_or_else(|| {
+    // Fetch the run up front for its project_id and to confirm it exists.
+    let run = state.run_store.get_run(&req.run_id).await.ok_or_else(|| {
+        (
+            StatusCode::NOT_FOUND,
+            format!("Run not found: {}", req.run_id),
+        )
+    })?;
+    let project_id = run.project_id.clone();
+    check_project_access(&auth_ctx, &project_id)?;
+
+    // Queued mode: hand the batch to the write-ahead queue and return
+    // immediately - the background consumer performs the write that the
+    // direct path below does synchronously.
+    if state.ingest_mode == IngestMode::Queued {
+        let producer = state
+            .queue_producer
+            .as_ref()
+            .expect("queue_producer is set whenever ingest_mode is Queued");
+
+        // Idempotency check: same as the direct path below - run it before
+        // enqueueing, not just before the direct write, so an SDK retry in
+        // Queued mode is deduplicated instead of landing in the queue (and
+        // eventually the store) a second time.
+        let idempotency_result = state
+            .idempotency_store
+            .check_and_record(
+                &project_id,
+                &req.run_id,
+                &batch_id,
+                seq,
+                &payload_hash,
+                metric_count as i32,
+                param_count as i32,
+                tag_count as i32,
+            )
+            .await;
+
+        let mut warnings = Vec::new();
+        match idempotency_result {
+            IdempotencyResult::Duplicate => {
+                state.metrics.record_batch_duplicate();
+                return Ok((
+                    StatusCode::OK,
+                    Json(IngestBatchHttpResponse {
+                        status: "duplicate".to_string(),
+                        accepted: 0,
+                        duplicate: true,
+                        warnings: vec![],
+                    }),
+                ));
+            }
+            IdempotencyResult::Conflict {
+                expected_hash,
+                actual_hash,
+            } => {
+                state.metrics.record_batch_conflict();
+                return Err((
+                    StatusCode::CONFLICT,
+                    format!(
+                        "Batch {} conflicts with existing batch (expected hash {}, got {})",
+                        batch_id, expected_hash, actual_hash
+                    ),
+                ));
+            }
+            IdempotencyResult::OutOfOrder {
+                expected_seq,
+                actual_seq,
+            } => {
+                warnings.push(format!(
+                    "Batch received out of order (expected seq >= {}, got {})",
+                    expected_seq, actual_seq
+                ));
+            }
+            IdempotencyResult::New => {
+                // New batch - proceed normally
+            }
+        }
+
+        // Cardinality guardrail: same as the direct path below - items over
+        // the limit are dropped from the batch before it's enqueued rather
+        // than silently over-counted once the consumer applies it.
+        let tag_pairs: Vec<(String, String)> = req
+            .tags
+            .iter()
+            .map(|t| (t.key.clone(), t.value.clone()))
+            .collect();
+        let metric_names: Vec<String> = req.metrics.iter().map(|m| m.name.clone()).collect();
+        let validation = state
+            .cardinality_tracker
+            .validate_batch(&project_id, &req.run_id, &tag_pairs, &metric_names)
+            .await;
+        warnings.extend(validation.warnings.clone());
+        let dropped_metric_names: std::collections::HashSet<&str> = validation
+            .dropped_metrics
+            .iter()
+            .map(String::as_str)
+            .collect();
+        let accepted_metrics: Vec<&MetricData> = req
+            .metrics
+            .iter()
+            .filter(|m| !dropped_metric_names.contains(m.name.as_str()))
+            .collect();
+        let accepted_metric_count = accepted_metrics.len();
+
+        let message = QueueMessage {
+            sequence: 0, // assigned by the producer
+            run_id: req.run_id.clone(),
+            batch_id: batch_id.clone(),
+            points: accepted_metrics
+                .iter()
+                .map(|m| QueuedMetricPoint {
+                    name: m.name.clone(),
+                    step: m.step,
+                    value: m.value,
+                    timestamp: m.timestamp,
+                })
+                .collect(),
+        };
+
+        producer.enqueue(message).await.map_err(|e| {
             (
-                StatusCode::NOT_FOUND,
-                format!("Run not found: {}", req.run_id),
+                StatusCode::SERVICE_UNAVAILABLE,
+                format!("Failed to enqueue batch: {e}"),
             )
         })?;
-        run.project_id.clone()
-    };
 
-    let idempotency_result = state.idempotency_store.check_and_record(
-        &project_id,
-        &req.run_id,
-        &batch_id,
-        seq,
-        &payload_hash,
-        metric_count as i32,
-        param_count as i32,
-        tag_count as i32,
-    ).await;
+        state
+            .metrics
+            .record_batch_ingested(accepted_metric_count as u64);
+
+        return Ok((
+            StatusCode::ACCEPTED,
+            Json(IngestBatchHttpResponse {
+                status: "queued".to_string(),
+                accepted: 0,
+                duplicate: false,
+                warnings,
+            }),
+        ));
+    }
+
+    let idempotency_result = state
+        .idempotency_store
+        .check_and_record(
+            &project_id,
+            &req.run_id,
+            &batch_id,
+            seq,
+            &payload_hash,
+            metric_count as i32,
+            param_count as i32,
+            tag_count as i32,
+        )
+        .await;
 
     // Handle idempotency results
     let mut warnings = Vec::new();
 
     match &idempotency_result {
         IdempotencyResult::Duplicate => {
+            state.metrics.record_batch_duplicate();
             // Duplicate batch - return success without processing
-            return Ok(Json(IngestBatchHttpResponse {
-                status: "ok".to_string(),
-                accepted: 0,
-                duplicate: true,
-                warnings: vec![],
-            }));
+            return Ok((
+                StatusCode::OK,
+                Json(IngestBatchHttpResponse {
+                    status: "ok".to_string(),
+                    accepted: 0,
+                    duplicate: true,
+                    warnings: vec![],
+                }),
+            ));
         }
-        IdempotencyResult::Conflict { expected_hash, actual_hash } => {
+        IdempotencyResult::Conflict {
+            expected_hash,
+            actual_hash,
+        } => {
+            state.metrics.record_batch_conflict();
             // Conflicting batch - error
             return Err((
                 StatusCode::CONFLICT,
@@ -251,7 +452,10 @@ async fn http_ingest_batch(
                 ),
             ));
         }
-        IdempotencyResult::OutOfOrder { expected_seq, actual_seq } => {
+        IdempotencyResult::OutOfOrder {
+            expected_seq,
+            actual_seq,
+        } => {
             warnings.push(format!(
                 "Batch received out of order (expected seq >= {}, got {})",
                 expected_seq, actual_seq
@@ -263,15 +467,6 @@ async fn http_ingest_batch(
     }
 
     // Now process the batch
-    let mut runs = state.store.runs.write().await;
-
-    let run = runs.get_mut(&req.run_id).ok_or_else(|| {
-        (
-            StatusCode::NOT_FOUND,
-            format!("Run not found: {}", req.run_id),
-        )
-    })?;
-
     if run.status != mlrun_proto::mlrun::v1::RunStatus::Running {
         return Err((
             StatusCode::PRECONDITION_FAILED,
@@ -279,34 +474,96 @@ async fn http_ingest_batch(
         ));
     }
 
-    run.metrics_count += metric_count as u64;
-    run.params_count += param_count as u64;
-
-    // Update tags
-    for tag in &req.tags {
-        run.tags.insert(tag.key.clone(), tag.value.clone());
+    // Cardinality guardrail: cap distinct tag keys/pairs and metric names
+    // per run/project before they're written. Items over the limit are
+    // dropped from the batch rather than just logged about, the same as
+    // the guardrail's own `validate_batch` doc promises.
+    let tag_pairs: Vec<(String, String)> = req
+        .tags
+        .iter()
+        .map(|t| (t.key.clone(), t.value.clone()))
+        .collect();
+    let metric_names: Vec<String> = req.metrics.iter().map(|m| m.name.clone()).collect();
+    let validation = state
+        .cardinality_tracker
+        .validate_batch(&project_id, &req.run_id, &tag_pairs, &metric_names)
+        .await;
+    warnings.extend(validation.warnings.clone());
+    let dropped_tags: std::collections::HashSet<&(String, String)> =
+        validation.dropped_tags.iter().collect();
+    let dropped_metric_names: std::collections::HashSet<&str> = validation
+        .dropped_metrics
+        .iter()
+        .map(String::as_str)
+        .collect();
+    let accepted_tags: Vec<(String, String)> = tag_pairs
+        .into_iter()
+        .filter(|pair| !dropped_tags.contains(pair))
+        .collect();
+    let accepted_metrics: Vec<&MetricData> = req
+        .metrics
+        .iter()
+        .filter(|m| !dropped_metric_names.contains(m.name.as_str()))
+        .collect();
+    let accepted_metric_count = accepted_metrics.len();
+    let accepted_tag_count = accepted_tags.len();
+
+    state
+        .run_store
+        .ingest_batch(
+            &req.run_id,
+            BatchDelta {
+                metrics: accepted_metric_count as u64,
+                params: param_count as u64,
+                upsert_tags: accepted_tags,
+                remove_tags: vec![],
+            },
+        )
+        .await
+        .map_err(run_store_error_to_http)?;
+
+    state
+        .metrics
+        .record_batch_ingested(accepted_metric_count as u64);
+
+    // Publish each accepted point to the run's live-tail channel (SSE), if
+    // anyone is subscribed.
+    for metric in &accepted_metrics {
+        state
+            .store
+            .publish_metric_point(
+                &req.run_id,
+                services::metrics::MetricPoint {
+                    name: metric.name.clone(),
+                    step: metric.step,
+                    value: metric.value,
+                    timestamp: metric.timestamp,
+                },
+            )
+            .await;
     }
 
-    run.updated_at = std::time::SystemTime::now();
-
-    let total = metric_count + param_count + tag_count;
+    let total = accepted_metric_count + param_count + accepted_tag_count;
 
     tracing::debug!(
         run_id = %req.run_id,
         batch_id = %batch_id,
         seq = seq,
-        metrics = metric_count,
+        metrics = accepted_metric_count,
         params = param_count,
-        tags = tag_count,
+        tags = accepted_tag_count,
         "HTTP: Ingested batch"
     );
 
-    Ok(Json(IngestBatchHttpResponse {
-        status: "ok".to_string(),
-        accepted: total as i64,
-        duplicate: false,
-        warnings,
-    }))
+    Ok((
+        StatusCode::OK,
+        Json(IngestBatchHttpResponse {
+            status: "ok".to_string(),
+            accepted: total as i64,
+            duplicate: false,
+            warnings,
+        }),
+    ))
 }
 
 /// Request to finish a run via HTTP.
@@ -323,22 +580,42 @@ struct FinishRunHttpResponse {
 /// Finish a run via HTTP.
 async fn http_finish_run(
     State(state): State<AppState>,
+    Extension(auth_ctx): Extension<AuthContext>,
     axum::extract::Path(run_id): axum::extract::Path<String>,
     Json(req): Json<FinishRunHttpRequest>,
 ) -> Result<Json<FinishRunHttpResponse>, (StatusCode, String)> {
-    let mut runs = state.store.runs.write().await;
-
-    let run = runs.get_mut(&run_id).ok_or_else(|| {
-        (StatusCode::NOT_FOUND, format!("Run not found: {}", run_id))
-    })?;
+    if let Some(existing) = state.run_store.get_run(&run_id).await {
+        check_project_access(&auth_ctx, &existing.project_id)?;
+    }
 
-    run.status = match req.status.as_str() {
+    let status = match req.status.as_str() {
         "finished" => mlrun_proto::mlrun::v1::RunStatus::Finished,
         "failed" => mlrun_proto::mlrun::v1::RunStatus::Failed,
         "killed" => mlrun_proto::mlrun::v1::RunStatus::Killed,
         _ => mlrun_proto::mlrun::v1::RunStatus::Finished,
     };
-    run.updated_at = std::time::SystemTime::now();
+
+    let run = state
+        .run_store
+        .finish_run(&run_id, status)
+        .await
+        .map_err(run_store_error_to_http)?;
+
+    state.metrics.record_run_finished(run.status);
+
+    let event = RunLifecycleEvent {
+        run_id: run.run_id.clone(),
+        project_id: run.project_id.clone(),
+        status: run_status_as_str(run.status).to_string(),
+        metrics_count: run.metrics_count,
+        occurred_at: format!("{:?}", run.updated_at),
+    };
+
+    // The run left `Running`: close its live-tail channel so any open SSE
+    // streams end gracefully instead of waiting on a keep-alive forever.
+    state.store.close_metric_stream(&run_id).await;
+
+    state.notifier.notify(event);
 
     info!(run_id = %run_id, status = %req.status, "HTTP: Finished run");
 
@@ -347,6 +624,41 @@ async fn http_finish_run(
     }))
 }
 
+/// Stream metric points for a run in real time via Server-Sent Events, so
+/// dashboards can follow a running job without polling [`http_get_run`].
+/// The stream ends once the run leaves `Running` (see [`http_finish_run`]).
+async fn http_stream_metrics(
+    State(state): State<AppState>,
+    Extension(auth_ctx): Extension<AuthContext>,
+    axum::extract::Path(run_id): axum::extract::Path<String>,
+) -> Result<
+    axum::response::sse::Sse<
+        impl futures::Stream<Item = Result<axum::response::sse::Event, std::convert::Infallible>>,
+    >,
+    (StatusCode, String),
+> {
+    let run = state
+        .run_store
+        .get_run(&run_id)
+        .await
+        .ok_or_else(|| (StatusCode::NOT_FOUND, format!("Run not found: {}", run_id)))?;
+    check_project_access(&auth_ctx, &run.project_id)?;
+
+    let receiver = state.store.subscribe_metric_stream(&run_id).await;
+    let stream = tokio_stream::wrappers::BroadcastStream::new(receiver).filter_map(|result| {
+        futures::future::ready(match result {
+            Ok(point) => serde_json::to_string(&point)
+                .ok()
+                .map(|data| Ok(axum::response::sse::Event::default().data(data))),
+            // A lagged subscriber missed points; skip the gap rather than
+            // ending the stream over it.
+            Err(_) => None,
+        })
+    });
+
+    Ok(axum::response::sse::Sse::new(stream).keep_alive(axum::response::sse::KeepAlive::default()))
+}
+
 // =============================================================================
 // Query API Handlers
 // =============================================================================
@@ -388,89 +700,93 @@ struct ListRunsResponse {
     offset: usize,
 }
 
+/// Convert a storage-layer [`storage::RunRecord`] to its HTTP response
+/// shape, shared by the list/get/batch-query handlers below.
+fn run_to_response(run: storage::RunRecord) -> RunResponse {
+    let duration = run
+        .updated_at
+        .duration_since(run.created_at)
+        .ok()
+        .map(|d| d.as_secs_f64());
+
+    RunResponse {
+        run_id: run.run_id,
+        project_id: run.project_id,
+        name: run.name,
+        status: run_status_as_str(run.status).to_string(),
+        metrics_count: run.metrics_count,
+        params_count: run.params_count,
+        tags: run.tags,
+        created_at: format!("{:?}", run.created_at),
+        updated_at: format!("{:?}", run.updated_at),
+        duration_seconds: duration,
+    }
+}
+
+/// Run the `list_runs` query against `state`, shared by [`http_list_runs`]
+/// and the `list_runs` sub-query of [`http_query_batch`].
+async fn query_list_runs(
+    state: &AppState,
+    project: Option<String>,
+    status: Option<String>,
+    limit: usize,
+    offset: usize,
+) -> ListRunsResponse {
+    let page = state
+        .run_store
+        .list_runs(ListRunsFilter {
+            project,
+            status: status.as_deref().and_then(parse_run_status),
+            limit,
+            offset,
+        })
+        .await;
+
+    ListRunsResponse {
+        runs: page.runs.into_iter().map(run_to_response).collect(),
+        total: page.total,
+        limit,
+        offset,
+    }
+}
+
+/// Resolve the effective project filter for a run-listing query, honoring
+/// the caller's own project restriction (see
+/// [`AuthContext::restricted_project`]): a requested project outside it is
+/// denied, and no requested project defaults to the caller's own, so a
+/// project-scoped key's listing never leaks runs from other projects.
+fn scoped_project_filter(
+    auth_ctx: &AuthContext,
+    requested: Option<String>,
+) -> Result<Option<String>, (StatusCode, String)> {
+    let Some(restricted) = auth_ctx.restricted_project() else {
+        // Unrestricted (global admin, no delegation): whatever the caller
+        // asked for, or everything if they didn't filter.
+        return Ok(requested);
+    };
+
+    match requested {
+        Some(project) if project != restricted => Err((
+            StatusCode::FORBIDDEN,
+            format!("Key cannot access project: {project}"),
+        )),
+        _ => Ok(Some(restricted.to_string())),
+    }
+}
+
 /// List runs with optional filtering.
 async fn http_list_runs(
     State(state): State<AppState>,
+    Extension(auth_ctx): Extension<AuthContext>,
     axum::extract::Query(query): axum::extract::Query<ListRunsQuery>,
 ) -> Result<Json<ListRunsResponse>, (StatusCode, String)> {
-    let runs = state.store.runs.read().await;
-
     let limit = query.limit.unwrap_or(100).min(1000);
     let offset = query.offset.unwrap_or(0);
+    let project = scoped_project_filter(&auth_ctx, query.project)?;
 
-    // Filter runs
-    let mut filtered_runs: Vec<_> = runs
-        .values()
-        .filter(|run| {
-            // Filter by project
-            if let Some(ref project) = query.project {
-                if &run.project_id != project {
-                    return false;
-                }
-            }
-
-            // Filter by status
-            if let Some(ref status) = query.status {
-                let run_status = match run.status {
-                    mlrun_proto::mlrun::v1::RunStatus::Running => "running",
-                    mlrun_proto::mlrun::v1::RunStatus::Finished => "finished",
-                    mlrun_proto::mlrun::v1::RunStatus::Failed => "failed",
-                    mlrun_proto::mlrun::v1::RunStatus::Killed => "killed",
-                    _ => "pending",
-                };
-                if run_status != status {
-                    return false;
-                }
-            }
-
-            true
-        })
-        .collect();
-
-    // Sort by created_at descending (newest first)
-    filtered_runs.sort_by(|a, b| b.created_at.cmp(&a.created_at));
-
-    let total = filtered_runs.len();
-
-    // Apply pagination
-    let page_runs: Vec<_> = filtered_runs
-        .into_iter()
-        .skip(offset)
-        .take(limit)
-        .map(|run| {
-            let duration = run
-                .updated_at
-                .duration_since(run.created_at)
-                .ok()
-                .map(|d| d.as_secs_f64());
-
-            RunResponse {
-                run_id: run.run_id.clone(),
-                project_id: run.project_id.clone(),
-                name: run.name.clone(),
-                status: match run.status {
-                    mlrun_proto::mlrun::v1::RunStatus::Running => "running".to_string(),
-                    mlrun_proto::mlrun::v1::RunStatus::Finished => "finished".to_string(),
-                    mlrun_proto::mlrun::v1::RunStatus::Failed => "failed".to_string(),
-                    mlrun_proto::mlrun::v1::RunStatus::Killed => "killed".to_string(),
-                    _ => "pending".to_string(),
-                },
-                metrics_count: run.metrics_count,
-                params_count: run.params_count,
-                tags: run.tags.clone(),
-                created_at: format!("{:?}", run.created_at),
-                updated_at: format!("{:?}", run.updated_at),
-                duration_seconds: duration,
-            }
-        })
-        .collect();
-
-    Ok(Json(ListRunsResponse {
-        runs: page_runs,
-        total,
-        limit,
-        offset,
-    }))
+    Ok(Json(
+        query_list_runs(&state, project, query.status, limit, offset).await,
+    ))
 }
 
 /// Detailed run response including metrics summary.
@@ -500,45 +816,483 @@ struct MetricSummaryResponse {
 /// Get run detail by ID.
 async fn http_get_run(
     State(state): State<AppState>,
+    Extension(auth_ctx): Extension<AuthContext>,
     axum::extract::Path(run_id): axum::extract::Path<String>,
 ) -> Result<Json<RunDetailResponse>, (StatusCode, String)> {
-    let runs = state.store.runs.read().await;
+    let run = state
+        .run_store
+        .get_run(&run_id)
+        .await
+        .ok_or_else(|| (StatusCode::NOT_FOUND, format!("Run not found: {}", run_id)))?;
+    check_project_access(&auth_ctx, &run.project_id)?;
+
+    let RunResponse {
+        run_id,
+        project_id,
+        name,
+        status,
+        metrics_count,
+        params_count,
+        tags,
+        created_at,
+        updated_at,
+        duration_seconds,
+    } = run_to_response(run);
 
-    let run = runs.get(&run_id).ok_or_else(|| {
-        (StatusCode::NOT_FOUND, format!("Run not found: {}", run_id))
-    })?;
+    Ok(Json(RunDetailResponse {
+        run_id,
+        project_id,
+        name,
+        status,
+        metrics_count,
+        params_count,
+        tags,
+        created_at,
+        updated_at,
+        duration_seconds,
+        // TODO: Get actual metrics summary from ClickHouse. For now, return
+        // an empty list (metrics are tracked in-memory as count only).
+        metrics_summary: fetch_metrics_summary(),
+    }))
+}
 
-    let duration = run
-        .updated_at
-        .duration_since(run.created_at)
-        .ok()
-        .map(|d| d.as_secs_f64());
+/// Fetch per-metric last-value/last-step summaries for a run.
+///
+/// TODO: Get actual metrics summary from ClickHouse. For now, returns an
+/// empty list (metrics are tracked in-memory as count only).
+fn fetch_metrics_summary() -> Vec<MetricSummaryResponse> {
+    vec![]
+}
 
-    // TODO: Get actual metrics summary from ClickHouse
-    // For now, return empty list (metrics are tracked in-memory as count only)
-    let metrics_summary = vec![];
+/// One sub-query within a [`BatchQueryRequest`].
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum BatchQuery {
+    /// Look up a single run by ID.
+    GetRun { run_id: String },
+    /// A filtered, paginated `list_runs`, same fields as [`ListRunsQuery`].
+    ListRuns {
+        project: Option<String>,
+        status: Option<String>,
+        limit: Option<usize>,
+        offset: Option<usize>,
+    },
+    /// Per-metric last-value/last-step summary for a run.
+    MetricsSummary { run_id: String },
+}
 
-    Ok(Json(RunDetailResponse {
-        run_id: run.run_id.clone(),
-        project_id: run.project_id.clone(),
-        name: run.name.clone(),
-        status: match run.status {
-            mlrun_proto::mlrun::v1::RunStatus::Running => "running".to_string(),
-            mlrun_proto::mlrun::v1::RunStatus::Finished => "finished".to_string(),
-            mlrun_proto::mlrun::v1::RunStatus::Failed => "failed".to_string(),
-            mlrun_proto::mlrun::v1::RunStatus::Killed => "killed".to_string(),
-            _ => "pending".to_string(),
-        },
-        metrics_count: run.metrics_count,
-        params_count: run.params_count,
-        tags: run.tags.clone(),
-        created_at: format!("{:?}", run.created_at),
-        updated_at: format!("{:?}", run.updated_at),
-        duration_seconds: duration,
-        metrics_summary,
+/// Request body for `POST /api/v1/query/batch`.
+#[derive(Debug, Deserialize)]
+struct BatchQueryRequest {
+    queries: Vec<BatchQuery>,
+}
+
+/// Outcome of one [`BatchQuery`]: exactly one of `run`/`runs`/
+/// `metrics_summary`/`error` is set, so a missing run in one sub-query
+/// doesn't fail the others.
+#[derive(Debug, Default, Serialize)]
+struct BatchQueryResult {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    run: Option<RunResponse>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    runs: Option<ListRunsResponse>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    metrics_summary: Option<Vec<MetricSummaryResponse>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// Response body for `POST /api/v1/query/batch`: one result per input
+/// query, in the same order.
+#[derive(Debug, Serialize)]
+struct BatchQueryResponse {
+    results: Vec<BatchQueryResult>,
+}
+
+/// Run a batch of independent sub-queries (run lookups, `list_runs`
+/// filters, metrics summaries) in one request, modeled on the batched
+/// read/write APIs offered by key-value stores. Sub-queries fan out
+/// concurrently against the shared store and each reports its own
+/// success/error, so one missing run doesn't fail the whole batch.
+async fn http_query_batch(
+    State(state): State<AppState>,
+    Extension(auth_ctx): Extension<AuthContext>,
+    Json(req): Json<BatchQueryRequest>,
+) -> Json<BatchQueryResponse> {
+    let pending = req.queries.into_iter().map(|query| {
+        let state = state.clone();
+        let auth_ctx = auth_ctx.clone();
+        async move {
+            match query {
+                BatchQuery::GetRun { run_id } => match state.run_store.get_run(&run_id).await {
+                    Some(run) => match check_project_access(&auth_ctx, &run.project_id) {
+                        Ok(()) => BatchQueryResult {
+                            run: Some(run_to_response(run)),
+                            ..Default::default()
+                        },
+                        Err((_, message)) => BatchQueryResult {
+                            error: Some(message),
+                            ..Default::default()
+                        },
+                    },
+                    None => BatchQueryResult {
+                        error: Some(format!("Run not found: {run_id}")),
+                        ..Default::default()
+                    },
+                },
+                BatchQuery::ListRuns {
+                    project,
+                    status,
+                    limit,
+                    offset,
+                } => {
+                    let limit = limit.unwrap_or(100).min(1000);
+                    let offset = offset.unwrap_or(0);
+                    match scoped_project_filter(&auth_ctx, project) {
+                        Ok(project) => BatchQueryResult {
+                            runs: Some(
+                                query_list_runs(&state, project, status, limit, offset).await,
+                            ),
+                            ..Default::default()
+                        },
+                        Err((_, message)) => BatchQueryResult {
+                            error: Some(message),
+                            ..Default::default()
+                        },
+                    }
+                }
+                BatchQuery::MetricsSummary { run_id } => {
+                    match state.run_store.get_run(&run_id).await {
+                        Some(run) => match check_project_access(&auth_ctx, &run.project_id) {
+                            Ok(()) => BatchQueryResult {
+                                metrics_summary: Some(fetch_metrics_summary()),
+                                ..Default::default()
+                            },
+                            Err((_, message)) => BatchQueryResult {
+                                error: Some(message),
+                                ..Default::default()
+                            },
+                        },
+                        None => BatchQueryResult {
+                            error: Some(format!("Run not found: {run_id}")),
+                            ..Default::default()
+                        },
+                    }
+                }
+            }
+        }
+    });
+
+    Json(BatchQueryResponse {
+        results: futures::future::join_all(pending).await,
+    })
+}
+
+// =============================================================================
+// Artifact Handlers
+// =============================================================================
+
+/// Deny unless `auth_ctx` may access `project_id` (honoring any active
+/// `X-On-Behalf-Of` delegation - see [`AuthContext::can_access_project`]).
+/// Shared by every handler that resolves a project from the request body or
+/// a looked-up run: `authz_middleware` only sees the coarse `project:*`
+/// object for body-keyed routes, and it does not apply delegation at all.
+fn check_project_access(
+    auth_ctx: &AuthContext,
+    project_id: &str,
+) -> Result<(), (StatusCode, String)> {
+    if auth_ctx.can_access_project(project_id) {
+        Ok(())
+    } else {
+        Err((
+            StatusCode::FORBIDDEN,
+            format!("Key cannot access project: {project_id}"),
+        ))
+    }
+}
+
+/// Fetch `run_id`, checking it exists and the caller's project may access
+/// it. Shared by the artifact upload/download handlers.
+async fn require_owned_run(
+    state: &AppState,
+    auth_ctx: &AuthContext,
+    run_id: &str,
+) -> Result<storage::RunRecord, (StatusCode, String)> {
+    let run = state
+        .run_store
+        .get_run(run_id)
+        .await
+        .ok_or_else(|| (StatusCode::NOT_FOUND, format!("Run not found: {run_id}")))?;
+
+    check_project_access(auth_ctx, &run.project_id)?;
+
+    Ok(run)
+}
+
+/// Request to mint an artifact upload URL.
+#[derive(Debug, Deserialize)]
+struct CreateArtifactHttpRequest {
+    name: String,
+    #[serde(default)]
+    artifact_type: Option<ArtifactType>,
+    content_type: Option<String>,
+    content_length: Option<u64>,
+}
+
+/// A presigned URL plus the metadata a client needs to use and retry it.
+#[derive(Debug, Serialize)]
+struct PresignedUrlResponse {
+    url: String,
+    method: String,
+    expires_in_secs: u64,
+    headers: std::collections::HashMap<String, String>,
+    key: String,
+}
+
+/// Mint a presigned PUT URL for uploading an artifact, so SDK clients write
+/// large files (model weights, plots) directly to object storage instead
+/// of streaming them through the API server. Records the artifact's key,
+/// size, and type via [`ArtifactRepository`].
+async fn http_create_artifact(
+    State(state): State<AppState>,
+    Extension(auth_ctx): Extension<AuthContext>,
+    axum::extract::Path(run_id): axum::extract::Path<String>,
+    Json(req): Json<CreateArtifactHttpRequest>,
+) -> Result<Json<PresignedUrlResponse>, (StatusCode, String)> {
+    require_owned_run(&state, &auth_ctx, &run_id).await?;
+
+    let (location, presigned) = state
+        .artifact_store
+        .create_upload_url(
+            &run_id,
+            &req.name,
+            req.content_type.as_deref(),
+            req.content_length,
+        )
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    // Best-effort: the richer entity model `ArtifactRepository` writes to
+    // isn't wired to a live connection yet (see storage::postgres), so a
+    // failure here is logged, not surfaced - the presigned URL is still
+    // good and the upload may proceed.
+    if let Err(e) = ArtifactRepository::create(CreateArtifactInput {
+        run_id: uuid::Uuid::nil(),
+        name: req.name.clone(),
+        artifact_type: req.artifact_type.unwrap_or(ArtifactType::Other),
+        description: None,
+        storage_path: location.key.clone(),
+        storage_type: Some("s3".to_string()),
+        size_bytes: req.content_length.map(|n| n as i64),
+        mime_type: req.content_type.clone(),
+        checksum_md5: None,
+        checksum_sha256: None,
+        metadata: None,
+    })
+    .await
+    {
+        warn!(run_id = %run_id, name = %req.name, error = %e, "Failed to record artifact metadata");
+    }
+
+    Ok(Json(PresignedUrlResponse {
+        url: presigned.url,
+        method: presigned.method,
+        expires_in_secs: presigned.expires_in_secs,
+        headers: presigned.headers,
+        key: location.key,
+    }))
+}
+
+/// Mint a presigned GET URL for downloading an artifact.
+async fn http_download_artifact(
+    State(state): State<AppState>,
+    Extension(auth_ctx): Extension<AuthContext>,
+    axum::extract::Path((run_id, name)): axum::extract::Path<(String, String)>,
+) -> Result<Json<PresignedUrlResponse>, (StatusCode, String)> {
+    require_owned_run(&state, &auth_ctx, &run_id).await?;
+
+    let location = state.artifact_store.get_location(&run_id, &name);
+    let presigned = state
+        .artifact_store
+        .create_download_url(&run_id, &name)
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    Ok(Json(PresignedUrlResponse {
+        url: presigned.url,
+        method: presigned.method,
+        expires_in_secs: presigned.expires_in_secs,
+        headers: presigned.headers,
+        key: location.key,
+    }))
+}
+
+// =============================================================================
+// Admin Handlers
+// =============================================================================
+//
+// Operational escape hatch for the cardinality guardrail: inspect a
+// project/run's current usage against its limits, evict a finished (or
+// runaway) run's tracking, and hot-swap limits mid-incident without a
+// restart. Gated on the `admin` scope directly rather than the coarse
+// `project:*` policy `authz_middleware` enforces on `protected_routes` -
+// these routes aren't project-scoped at all.
+
+/// Require the authenticated key to hold the `admin` scope (dev mode keys
+/// always do - see `AuthContext::dev_mode`).
+fn require_admin(auth_ctx: &AuthContext) -> Result<(), (StatusCode, String)> {
+    if auth_ctx.api_key.has_scope("admin") {
+        Ok(())
+    } else {
+        Err((
+            StatusCode::FORBIDDEN,
+            "This endpoint requires the 'admin' scope".to_string(),
+        ))
+    }
+}
+
+/// Current usage vs. limit for one cardinality dimension.
+#[derive(Debug, Serialize)]
+struct CardinalityUsage {
+    current: usize,
+    limit: usize,
+    utilization_pct: f64,
+}
+
+impl CardinalityUsage {
+    fn new(current: usize, limit: usize) -> Self {
+        let utilization_pct = if limit == 0 {
+            0.0
+        } else {
+            (current as f64 / limit as f64) * 100.0
+        };
+        Self {
+            current,
+            limit,
+            utilization_pct,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct AdminProjectCardinalityResponse {
+    project_id: String,
+    tag_pairs: CardinalityUsage,
+}
+
+/// `GET /admin/cardinality/projects/{id}`: current (or HyperLogLog-estimated)
+/// tag-pair usage for a project against `max_tags_per_project`.
+async fn admin_get_project_cardinality(
+    State(state): State<AppState>,
+    Extension(auth_ctx): Extension<AuthContext>,
+    axum::extract::Path(project_id): axum::extract::Path<String>,
+) -> Result<Json<AdminProjectCardinalityResponse>, (StatusCode, String)> {
+    require_admin(&auth_ctx)?;
+
+    let limits = state.cardinality_tracker.config().await;
+    let current = state
+        .cardinality_tracker
+        .get_project_stats(&project_id)
+        .await
+        .unwrap_or(0);
+
+    Ok(Json(AdminProjectCardinalityResponse {
+        project_id,
+        tag_pairs: CardinalityUsage::new(current, limits.max_tags_per_project),
+    }))
+}
+
+#[derive(Debug, Serialize)]
+struct AdminRunCardinalityResponse {
+    run_id: String,
+    tag_keys: CardinalityUsage,
+    metric_names: CardinalityUsage,
+}
+
+/// `GET /admin/cardinality/runs/{id}`: current tag-key/metric-name usage
+/// for a run against its per-run limits.
+async fn admin_get_run_cardinality(
+    State(state): State<AppState>,
+    Extension(auth_ctx): Extension<AuthContext>,
+    axum::extract::Path(run_id): axum::extract::Path<String>,
+) -> Result<Json<AdminRunCardinalityResponse>, (StatusCode, String)> {
+    require_admin(&auth_ctx)?;
+
+    let limits = state.cardinality_tracker.config().await;
+    let (tag_keys, metric_names) = state
+        .cardinality_tracker
+        .get_run_stats(&run_id)
+        .await
+        .unwrap_or((0, 0));
+
+    Ok(Json(AdminRunCardinalityResponse {
+        run_id,
+        tag_keys: CardinalityUsage::new(tag_keys, limits.max_tag_keys_per_run),
+        metric_names: CardinalityUsage::new(metric_names, limits.max_metric_names_per_run),
     }))
 }
 
+/// `POST /admin/cardinality/runs/{id}/clear`: evict a run's cardinality
+/// tracking (e.g. after it finishes, or to recover from a runaway run
+/// mid-incident without waiting for a restart).
+async fn admin_clear_run_cardinality(
+    State(state): State<AppState>,
+    Extension(auth_ctx): Extension<AuthContext>,
+    axum::extract::Path(run_id): axum::extract::Path<String>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    require_admin(&auth_ctx)?;
+
+    state.cardinality_tracker.clear_run(&run_id).await;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Request to hot-swap cardinality limits. Only the limit thresholds
+/// themselves are swappable this way - `store_backend`/`store_path`
+/// require a restart, since switching them means reconnecting the store.
+#[derive(Debug, Deserialize)]
+struct UpdateLimitsRequest {
+    max_tag_keys_per_run: usize,
+    max_metric_names_per_run: usize,
+    max_tags_per_project: usize,
+    max_tag_key_length: usize,
+    max_tag_value_length: usize,
+    max_metric_name_length: usize,
+    /// `"exact"` or `"hyperloglog"` - see `TagCardinalityEstimator`.
+    project_tag_estimator: String,
+}
+
+/// `PUT /admin/limits`: hot-swap the cardinality guardrail's limits at
+/// runtime, e.g. to raise a limit for a legitimately large project mid-
+/// incident without a restart.
+async fn admin_update_limits(
+    State(state): State<AppState>,
+    Extension(auth_ctx): Extension<AuthContext>,
+    Json(req): Json<UpdateLimitsRequest>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    require_admin(&auth_ctx)?;
+
+    let project_tag_estimator = match req.project_tag_estimator.to_lowercase().as_str() {
+        "exact" => TagCardinalityEstimator::Exact,
+        "hyperloglog" | "hll" => TagCardinalityEstimator::HyperLogLog,
+        other => {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                format!("Unknown project_tag_estimator: {other}"),
+            ))
+        }
+    };
+
+    let mut updated = state.cardinality_tracker.config().await;
+    updated.max_tag_keys_per_run = req.max_tag_keys_per_run;
+    updated.max_metric_names_per_run = req.max_metric_names_per_run;
+    updated.max_tags_per_project = req.max_tags_per_project;
+    updated.max_tag_key_length = req.max_tag_key_length;
+    updated.max_tag_value_length = req.max_tag_value_length;
+    updated.max_metric_name_length = req.max_metric_name_length;
+    updated.project_tag_estimator = project_tag_estimator;
+
+    state.cardinality_tracker.update_config(updated).await;
+    Ok(StatusCode::NO_CONTENT)
+}
+
 // =============================================================================
 // Server Setup
 // =============================================================================
@@ -550,9 +1304,52 @@ fn build_http_router(state: AppState) -> Router {
         .route("/api/v1/runs", post(http_init_run))
         .route("/api/v1/ingest/batch", post(http_ingest_batch))
         .route("/api/v1/runs/{run_id}/finish", post(http_finish_run))
+        .route(
+            "/api/v1/runs/{run_id}/metrics/stream",
+            get(http_stream_metrics),
+        )
         // Query API endpoints
         .route("/api/v1/runs", get(http_list_runs))
         .route("/api/v1/runs/{run_id}", get(http_get_run))
+        .route("/api/v1/query/batch", post(http_query_batch))
+        .route(
+            "/api/v1/runs/{run_id}/artifacts",
+            post(http_create_artifact),
+        )
+        .route(
+            "/api/v1/runs/{run_id}/artifacts/{name}",
+            get(http_download_artifact),
+        )
+        .layer(middleware::from_fn_with_state(
+            AuthzState {
+                authorizer: state.authorizer.clone(),
+                run_store: state.run_store.clone(),
+            },
+            authz_middleware,
+        ))
+        .layer(middleware::from_fn_with_state(
+            state.key_store.clone(),
+            auth_middleware,
+        ));
+
+    // Admin routes: authenticated but not subject to the coarse
+    // `project:*` authz_middleware policy - each handler checks the
+    // `admin` scope directly via `require_admin`, since these aren't
+    // scoped to a project at all.
+    let admin_routes = Router::new()
+        .route(
+            "/admin/cardinality/projects/{id}",
+            get(admin_get_project_cardinality),
+        )
+        .route(
+            "/admin/cardinality/runs/{id}",
+            get(admin_get_run_cardinality),
+        )
+        .route(
+            "/admin/cardinality/runs/{id}/clear",
+            post(admin_clear_run_cardinality),
+        )
+        .route("/admin/limits", put(admin_update_limits))
         .layer(middleware::from_fn_with_state(
             state.key_store.clone(),
             auth_middleware,
@@ -561,12 +1358,14 @@ fn build_http_router(state: AppState) -> Router {
     // Public routes (no auth required)
     let public_routes = Router::new()
         .route("/", get(root))
-        .route("/health", get(health));
+        .route("/health", get(health))
+        .route("/metrics", get(prometheus_metrics));
 
     // Combine routes
     Router::new()
         .merge(public_routes)
         .merge(protected_routes)
+        .merge(admin_routes)
         .with_state(state)
 }
 
@@ -581,32 +1380,191 @@ async fn main() {
         )
         .init();
 
-    // Initialize API key store
-    let key_store = Arc::new(ApiKeyStore::new());
+    // Load server configuration (ingest mode, queue settings, ports) from
+    // the environment and report what we ended up with.
+    let config = config::ServerConfig::from_env();
+    config.log_startup();
+
+    // Select the API key store. Postgres is durable across restarts; if
+    // it's configured but unreachable, fall back to in-memory rather than
+    // failing to start (mirrors the run/metrics store fallbacks below).
+    let key_store: Arc<dyn ApiKeyStore> = match config.api_key_store_backend {
+        ApiKeyStoreBackend::Postgres => {
+            let pg_config = storage::PostgresConfig::from_env();
+            match PostgresApiKeyStore::connect(&pg_config).await {
+                Ok(store) => store,
+                Err(e) => {
+                    warn!(
+                        "Failed to connect to Postgres API key store, falling back to in-memory: {}",
+                        e
+                    );
+                    Arc::new(InMemoryApiKeyStore::new())
+                }
+            }
+        }
+        ApiKeyStoreBackend::Memory => Arc::new(InMemoryApiKeyStore::new()),
+    };
     key_store.init_from_env().await;
 
-    // Initialize idempotency store
-    let idempotency_store = Arc::new(IdempotencyStore::new());
+    // Initialize the policy-based authorizer, seeded with the scope
+    // compatibility policies so existing admin/ingest/query keys keep
+    // working unchanged.
+    let authorizer = Arc::new(Authorizer::with_scope_compat());
+
+    // Select the idempotency store. The WAL backend is durable across
+    // restarts; if it can't open its directory, `connect` falls back to
+    // in-memory rather than failing to start (mirrors the run/metrics
+    // store fallbacks below).
+    let idempotency_store = Arc::new(
+        IdempotencyBackend::connect(
+            config.idempotency_store_backend,
+            config.idempotency_wal_dir.clone(),
+        )
+        .await,
+    );
 
-    // Create shared state
+    // Select the run lifecycle store. Postgres is durable across restarts;
+    // if it's configured but unreachable, fall back to in-memory rather than
+    // failing to start (mirrors the queue producer fallback below).
+    let run_store: Arc<dyn RunStore> = match config.run_store_backend {
+        RunStoreBackend::Postgres => {
+            let pg_config = storage::PostgresConfig::from_env();
+            match PostgresRunStore::connect(&pg_config).await {
+                Ok(store) => Arc::new(store),
+                Err(e) => {
+                    warn!(
+                        "Failed to connect to Postgres run store, falling back to in-memory: {}",
+                        e
+                    );
+                    Arc::new(InMemoryRunStore::new())
+                }
+            }
+        }
+        RunStoreBackend::Memory => Arc::new(InMemoryRunStore::new()),
+    };
+
+    // Select the metrics store. Postgres is durable across restarts; if
+    // it's configured but unreachable, fall back to in-memory rather than
+    // failing to start (mirrors the run store fallback above).
+    let metrics_repo: Arc<dyn MetricsRepo> = match config.metrics_repo_backend {
+        MetricsRepoBackend::Postgres => {
+            let pg_config = storage::PostgresConfig::from_env();
+            match PostgresMetricsRepo::connect(&pg_config).await {
+                Ok(repo) => Arc::new(repo),
+                Err(e) => {
+                    warn!(
+                        "Failed to connect to Postgres metrics store, falling back to in-memory: {}",
+                        e
+                    );
+                    Arc::new(InMemoryMetricsRepo::new())
+                }
+            }
+        }
+        MetricsRepoBackend::Memory => Arc::new(InMemoryMetricsRepo::new()),
+    };
+
+    // When queued ingest is configured, build and initialize the producer
+    // up front and spawn the background consumer that drains it.
     let store = Arc::new(InMemoryStore::new());
+    let queue_producer: Option<Arc<dyn QueueProducer>> = if let Some(queue_config) = &config.queue {
+        match queue::build(queue_config) {
+            Err(e) => {
+                warn!(
+                    "Failed to build ingest queue producer, falling back to direct mode: {}",
+                    e
+                );
+                None
+            }
+            Ok(producer) => {
+                if let Err(e) = producer.init_from_env().await {
+                    warn!(
+                        "Failed to initialize ingest queue, falling back to direct mode: {}",
+                        e
+                    );
+                    None
+                } else {
+                    queue::spawn_consumer(producer.clone(), run_store.clone(), metrics_repo.clone());
+                    Some(producer)
+                }
+            }
+        }
+    } else {
+        None
+    };
+    let ingest_mode = if queue_producer.is_some() {
+        config.ingest_mode
+    } else {
+        IngestMode::Direct
+    };
+
+    // Load webhook notification endpoints.
+    let notifier = Arc::new(Notifier::from_env());
+
+    // Counters/gauges exposed via GET /metrics, shared between the HTTP
+    // and gRPC ingest paths.
+    let metrics = Arc::new(Metrics::new());
+
+    // Mints presigned artifact upload/download URLs (or, for the local-fs
+    // backend, direct file:// paths), selected via `ARTIFACT_BACKEND`.
+    let artifact_backend: Arc<dyn ArtifactBackend> = match config.artifact_backend {
+        ArtifactBackendKind::S3 => {
+            // Ensure the bucket exists up front so the first upload
+            // doesn't pay that cost.
+            let minio_client = MinioClient::new(MinioConfig::from_env());
+            if let Err(e) = minio_client.ensure_bucket().await {
+                warn!("Failed to ensure artifact bucket exists: {}", e);
+            }
+            Arc::new(minio_client)
+        }
+        ArtifactBackendKind::LocalFs => Arc::new(
+            LocalFsBackend::new(&config.artifact_local_root)
+                .expect("failed to create local artifact storage root"),
+        ),
+    };
+    let artifact_store = Arc::new(ArtifactStore::new(artifact_backend));
+
+    // Tracks tag/metric-name cardinality for the guardrail counters and
+    // gauges exposed on GET /metrics; see CardinalityTracker::validate_batch.
+    // `connect` honors MLRUN_CARDINALITY_STORE so project tag-pair state
+    // survives restarts, falling back to in-memory if it can't connect.
+    let cardinality_tracker =
+        Arc::new(CardinalityTracker::connect(services::LimitsConfig::from_env()).await);
+
+    // Create shared state
     let app_state = AppState {
         store: store.clone(),
+        run_store: run_store.clone(),
         key_store: key_store.clone(),
+        authorizer,
         idempotency_store,
+        ingest_mode,
+        queue_producer: queue_producer.clone(),
+        notifier: notifier.clone(),
+        metrics: metrics.clone(),
+        artifact_store,
+        cardinality_tracker: cardinality_tracker.clone(),
     };
 
     // HTTP server address
-    let http_addr = SocketAddr::from(([0, 0, 0, 0], 3001));
+    let http_addr = config.http_addr;
 
     // gRPC server address
-    let grpc_addr = SocketAddr::from(([0, 0, 0, 0], 50051));
+    let grpc_addr = config.grpc_addr;
 
     // Build HTTP router
     let http_app = build_http_router(app_state);
 
     // Build gRPC service
-    let ingest_service = IngestServiceImpl::new(store);
+    let ingest_service = IngestServiceImpl::with_queue(
+        store,
+        run_store,
+        metrics_repo,
+        ingest_mode,
+        queue_producer,
+        notifier,
+        metrics,
+        cardinality_tracker,
+    );
     let grpc_service = IngestServiceServer::new(ingest_service);
 
     info!("Starting MLRun API server");
@@ -648,10 +1606,26 @@ mod tests {
 
     fn test_app() -> Router {
         let store = Arc::new(InMemoryStore::new());
+        let run_store: Arc<dyn RunStore> = Arc::new(InMemoryRunStore::new());
         // Use dev mode for tests (auth disabled)
-        let key_store = Arc::new(ApiKeyStore::new_dev_mode());
-        let idempotency_store = Arc::new(IdempotencyStore::new());
-        let state = AppState { store, key_store, idempotency_store };
+        let key_store: Arc<dyn ApiKeyStore> = Arc::new(InMemoryApiKeyStore::new_dev_mode());
+        let authorizer = Arc::new(Authorizer::with_scope_compat());
+        let idempotency_store = Arc::new(IdempotencyBackend::Memory(IdempotencyStore::new()));
+        let state = AppState {
+            store,
+            run_store,
+            key_store,
+            authorizer,
+            idempotency_store,
+            ingest_mode: IngestMode::Direct,
+            queue_producer: None,
+            notifier: Arc::new(Notifier::from_env()),
+            metrics: Arc::new(Metrics::new()),
+            artifact_store: Arc::new(ArtifactStore::new(Arc::new(MinioClient::new(
+                MinioConfig::default(),
+            )))),
+            cardinality_tracker: Arc::new(CardinalityTracker::default()),
+        };
         build_http_router(state)
     }
 
@@ -701,4 +1675,236 @@ mod tests {
 
         assert_eq!(response.status(), StatusCode::OK);
     }
+
+    #[tokio::test]
+    async fn test_prometheus_metrics_endpoint() {
+        let app = test_app();
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/metrics")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let text = String::from_utf8(body.to_vec()).unwrap();
+        assert!(text.contains("mlrun_active_runs"));
+    }
+
+    #[tokio::test]
+    async fn test_stream_metrics_unknown_run_returns_404() {
+        let app = test_app();
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/api/v1/runs/does-not-exist/metrics/stream")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_query_batch_reports_independent_errors() {
+        let app = test_app();
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/api/v1/query/batch")
+                    .header("content-type", "application/json")
+                    .body(Body::from(
+                        r#"{"queries": [
+                            {"type": "get_run", "run_id": "does-not-exist"},
+                            {"type": "list_runs"}
+                        ]}"#,
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let parsed: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let results = parsed["results"].as_array().unwrap();
+        assert_eq!(results.len(), 2);
+        assert!(results[0]["error"].as_str().unwrap().contains("not found"));
+        assert!(results[1]["runs"].is_object());
+    }
+
+    #[tokio::test]
+    async fn test_create_artifact_unknown_run_returns_404() {
+        let app = test_app();
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/api/v1/runs/does-not-exist/artifacts")
+                    .header("content-type", "application/json")
+                    .body(Body::from(r#"{"name": "model.pt"}"#))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_create_and_download_artifact_url() {
+        let app = test_app();
+
+        let init_response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/api/v1/runs")
+                    .header("content-type", "application/json")
+                    .body(Body::from(r#"{"project": "test-project"}"#))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let body = axum::body::to_bytes(init_response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let run_id = serde_json::from_slice::<serde_json::Value>(&body).unwrap()["run_id"]
+            .as_str()
+            .unwrap()
+            .to_string();
+
+        let upload_response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri(format!("/api/v1/runs/{run_id}/artifacts"))
+                    .header("content-type", "application/json")
+                    .body(Body::from(
+                        r#"{"name": "model.pt", "content_type": "application/octet-stream"}"#,
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(upload_response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(upload_response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let parsed: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(parsed["method"], "PUT");
+        assert!(parsed["key"].as_str().unwrap().contains("model.pt"));
+
+        let download_response = app
+            .oneshot(
+                Request::builder()
+                    .uri(format!("/api/v1/runs/{run_id}/artifacts/model.pt"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(download_response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(download_response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let parsed: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(parsed["method"], "GET");
+    }
+
+    #[tokio::test]
+    async fn test_admin_get_project_cardinality_defaults_to_zero() {
+        let app = test_app();
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/admin/cardinality/projects/unknown-project")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let parsed: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(parsed["tag_pairs"]["current"], 0);
+        assert_eq!(parsed["tag_pairs"]["limit"], 10000);
+    }
+
+    #[tokio::test]
+    async fn test_admin_clear_run_cardinality() {
+        let app = test_app();
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/admin/cardinality/runs/some-run/clear")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    }
+
+    #[tokio::test]
+    async fn test_admin_update_limits_hot_swaps_config() {
+        let app = test_app();
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method("PUT")
+                    .uri("/admin/limits")
+                    .header("content-type", "application/json")
+                    .body(Body::from(
+                        r#"{
+                            "max_tag_keys_per_run": 1,
+                            "max_metric_names_per_run": 1,
+                            "max_tags_per_project": 1,
+                            "max_tag_key_length": 256,
+                            "max_tag_value_length": 1024,
+                            "max_metric_name_length": 256,
+                            "project_tag_estimator": "exact"
+                        }"#,
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/admin/cardinality/projects/unknown-project")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let parsed: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(parsed["tag_pairs"]["limit"], 1);
+    }
 }
@@ -0,0 +1,168 @@
+//! Prometheus-format counters for server observability.
+//!
+//! Mirrors the admin metrics surface common in storage servers: a public
+//! `GET /metrics` route that operators can scrape directly instead of
+//! inferring health from logs. Counters live as atomics in [`Metrics`],
+//! shared via `AppState`, and are incremented inline by the HTTP handlers
+//! in `main.rs` and the gRPC `IngestServiceImpl`.
+
+use std::fmt::Write as _;
+use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
+
+use mlrun_proto::mlrun::v1::RunStatus;
+
+/// Server-internal counters/gauges, rendered in Prometheus text exposition
+/// format by the `/metrics` handler.
+#[derive(Debug, Default)]
+pub struct Metrics {
+    runs_finished_total: AtomicU64,
+    runs_failed_total: AtomicU64,
+    runs_killed_total: AtomicU64,
+    batches_ingested_total: AtomicU64,
+    batches_duplicate_total: AtomicU64,
+    batches_conflict_total: AtomicU64,
+    metrics_ingested_total: AtomicU64,
+    active_runs: AtomicI64,
+}
+
+impl Metrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a run entering `Running` (via `init_run`/`http_init_run`).
+    pub fn record_run_started(&self) {
+        self.active_runs.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Record a run leaving `Running` (via `finish_run`/`http_finish_run`).
+    pub fn record_run_finished(&self, status: RunStatus) {
+        match status {
+            RunStatus::Finished => {
+                self.runs_finished_total.fetch_add(1, Ordering::Relaxed);
+            }
+            RunStatus::Failed => {
+                self.runs_failed_total.fetch_add(1, Ordering::Relaxed);
+            }
+            RunStatus::Killed => {
+                self.runs_killed_total.fetch_add(1, Ordering::Relaxed);
+            }
+            _ => {}
+        }
+        self.active_runs.fetch_sub(1, Ordering::Relaxed);
+    }
+
+    /// Record a batch that was accepted (written directly or enqueued).
+    pub fn record_batch_ingested(&self, metric_count: u64) {
+        self.batches_ingested_total.fetch_add(1, Ordering::Relaxed);
+        self.metrics_ingested_total
+            .fetch_add(metric_count, Ordering::Relaxed);
+    }
+
+    /// Record a batch rejected as a duplicate of one already seen.
+    pub fn record_batch_duplicate(&self) {
+        self.batches_duplicate_total.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Record a batch whose `batch_id` collided with a different payload.
+    pub fn record_batch_conflict(&self) {
+        self.batches_conflict_total.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Render all counters/gauges in Prometheus text exposition format.
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+
+        let _ = writeln!(
+            out,
+            "# HELP mlrun_runs_total Total runs that left the Running status, by terminal status."
+        );
+        let _ = writeln!(out, "# TYPE mlrun_runs_total counter");
+        let _ = writeln!(
+            out,
+            "mlrun_runs_total{{status=\"finished\"}} {}",
+            self.runs_finished_total.load(Ordering::Relaxed)
+        );
+        let _ = writeln!(
+            out,
+            "mlrun_runs_total{{status=\"failed\"}} {}",
+            self.runs_failed_total.load(Ordering::Relaxed)
+        );
+        let _ = writeln!(
+            out,
+            "mlrun_runs_total{{status=\"killed\"}} {}",
+            self.runs_killed_total.load(Ordering::Relaxed)
+        );
+
+        let _ = writeln!(out, "# HELP mlrun_batches_ingested_total Total batches accepted (written directly or enqueued).");
+        let _ = writeln!(out, "# TYPE mlrun_batches_ingested_total counter");
+        let _ = writeln!(
+            out,
+            "mlrun_batches_ingested_total {}",
+            self.batches_ingested_total.load(Ordering::Relaxed)
+        );
+
+        let _ = writeln!(out, "# HELP mlrun_batches_duplicate_total Total batches rejected as duplicates of one already seen.");
+        let _ = writeln!(out, "# TYPE mlrun_batches_duplicate_total counter");
+        let _ = writeln!(
+            out,
+            "mlrun_batches_duplicate_total {}",
+            self.batches_duplicate_total.load(Ordering::Relaxed)
+        );
+
+        let _ = writeln!(out, "# HELP mlrun_batches_conflict_total Total batches whose batch_id collided with a different payload.");
+        let _ = writeln!(out, "# TYPE mlrun_batches_conflict_total counter");
+        let _ = writeln!(
+            out,
+            "mlrun_batches_conflict_total {}",
+            self.batches_conflict_total.load(Ordering::Relaxed)
+        );
+
+        let _ = writeln!(
+            out,
+            "# HELP mlrun_metrics_ingested_total Total metric points accepted across all batches."
+        );
+        let _ = writeln!(out, "# TYPE mlrun_metrics_ingested_total counter");
+        let _ = writeln!(
+            out,
+            "mlrun_metrics_ingested_total {}",
+            self.metrics_ingested_total.load(Ordering::Relaxed)
+        );
+
+        let _ = writeln!(
+            out,
+            "# HELP mlrun_active_runs Runs currently in the Running status."
+        );
+        let _ = writeln!(out, "# TYPE mlrun_active_runs gauge");
+        let _ = writeln!(
+            out,
+            "mlrun_active_runs {}",
+            self.active_runs.load(Ordering::Relaxed)
+        );
+
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_reflects_recorded_events() {
+        let metrics = Metrics::new();
+        metrics.record_run_started();
+        metrics.record_batch_ingested(3);
+        metrics.record_batch_duplicate();
+        metrics.record_batch_conflict();
+        metrics.record_run_finished(RunStatus::Finished);
+
+        let rendered = metrics.render();
+        assert!(rendered.contains("mlrun_runs_total{status=\"finished\"} 1"));
+        assert!(rendered.contains("mlrun_batches_ingested_total 1"));
+        assert!(rendered.contains("mlrun_batches_duplicate_total 1"));
+        assert!(rendered.contains("mlrun_batches_conflict_total 1"));
+        assert!(rendered.contains("mlrun_metrics_ingested_total 3"));
+        assert!(rendered.contains("mlrun_active_runs 0"));
+    }
+}
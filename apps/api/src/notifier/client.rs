@@ -0,0 +1,130 @@
+//! Signed webhook delivery.
+//!
+//! Each delivery runs on its own `tokio::spawn` task so a slow or dead
+//! receiver never blocks the ingest path. Failures are retried with
+//! exponential backoff up to a bounded number of attempts, then dropped
+//! (lost deliveries are logged via `tracing::warn`, not retried forever).
+
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use tracing::warn;
+
+use super::config::WebhookEndpoint;
+use super::event::RunLifecycleEvent;
+
+type HmacSha256 = Hmac<Sha256>;
+
+const MAX_ATTEMPTS: u32 = 5;
+const INITIAL_BACKOFF: std::time::Duration = std::time::Duration::from_millis(500);
+const MAX_BACKOFF: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// Notifies a fixed set of webhook endpoints about run lifecycle events.
+#[derive(Clone)]
+pub struct Notifier {
+    endpoints: Vec<WebhookEndpoint>,
+    client: reqwest::Client,
+}
+
+impl Notifier {
+    /// Load endpoints from `MLRUN_WEBHOOK_ENDPOINTS`.
+    pub fn from_env() -> Self {
+        Self {
+            endpoints: WebhookEndpoint::load_from_env(),
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// Asynchronously deliver `event` to every configured endpoint. Returns
+    /// immediately; delivery (including retries) happens on background
+    /// tasks.
+    pub fn notify(&self, event: RunLifecycleEvent) {
+        for endpoint in &self.endpoints {
+            let client = self.client.clone();
+            let endpoint = endpoint.clone();
+            let event = event.clone();
+            tokio::spawn(async move {
+                deliver_with_retry(&client, &endpoint, &event).await;
+            });
+        }
+    }
+}
+
+/// Sign `body` with the endpoint's shared secret: `HMAC-SHA256(secret, body)`
+/// hex-encoded, in the `sha256=<hex>` format expected by the
+/// `X-MLRun-Signature` header.
+fn sign(secret: &str, body: &[u8]) -> String {
+    let mut mac =
+        HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(body);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+async fn deliver_with_retry(
+    client: &reqwest::Client,
+    endpoint: &WebhookEndpoint,
+    event: &RunLifecycleEvent,
+) {
+    let body = match serde_json::to_vec(event) {
+        Ok(body) => body,
+        Err(e) => {
+            warn!(url = %endpoint.url, error = %e, "Failed to serialize webhook payload");
+            return;
+        }
+    };
+    let signature = sign(&endpoint.secret, &body);
+
+    let mut backoff = INITIAL_BACKOFF;
+
+    for attempt in 1..=MAX_ATTEMPTS {
+        let result = client
+            .post(&endpoint.url)
+            .header("Content-Type", "application/json")
+            .header("X-MLRun-Signature", &signature)
+            .body(body.clone())
+            .send()
+            .await;
+
+        match result {
+            Ok(response) if response.status().is_success() => return,
+            Ok(response) => {
+                warn!(
+                    url = %endpoint.url,
+                    status = %response.status(),
+                    attempt,
+                    "Webhook delivery rejected by receiver"
+                );
+            }
+            Err(e) => {
+                warn!(url = %endpoint.url, error = %e, attempt, "Webhook delivery failed");
+            }
+        }
+
+        if attempt == MAX_ATTEMPTS {
+            warn!(url = %endpoint.url, run_id = %event.run_id, "Giving up on webhook delivery after {} attempts", MAX_ATTEMPTS);
+            return;
+        }
+
+        tokio::time::sleep(backoff).await;
+        backoff = (backoff * 2).min(MAX_BACKOFF);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sign_is_deterministic_and_hex_prefixed() {
+        let sig1 = sign("my-secret", b"payload");
+        let sig2 = sign("my-secret", b"payload");
+        assert_eq!(sig1, sig2);
+        assert!(sig1.starts_with("sha256="));
+    }
+
+    #[test]
+    fn test_sign_differs_by_secret() {
+        let sig1 = sign("secret-a", b"payload");
+        let sig2 = sign("secret-b", b"payload");
+        assert_ne!(sig1, sig2);
+    }
+}
@@ -0,0 +1,71 @@
+//! Webhook endpoint configuration.
+
+/// A webhook endpoint and the shared secret used to sign deliveries to it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WebhookEndpoint {
+    pub url: String,
+    pub secret: String,
+}
+
+impl WebhookEndpoint {
+    /// Parse `MLRUN_WEBHOOK_ENDPOINTS`: a comma-separated list of
+    /// `url=secret` pairs, e.g. `https://a.test/hook=secretA,https://b.test/hook=secretB`.
+    pub fn load_from_env() -> Vec<Self> {
+        std::env::var("MLRUN_WEBHOOK_ENDPOINTS")
+            .ok()
+            .map(|raw| Self::parse(&raw))
+            .unwrap_or_default()
+    }
+
+    /// Parse the comma-separated `url=secret` list. Malformed entries
+    /// (missing `=`, empty url/secret) are skipped with a warning rather
+    /// than failing startup.
+    fn parse(raw: &str) -> Vec<Self> {
+        raw.split(',')
+            .filter_map(|entry| {
+                let entry = entry.trim();
+                if entry.is_empty() {
+                    return None;
+                }
+                let (url, secret) = entry.split_once('=')?;
+                let (url, secret) = (url.trim(), secret.trim());
+                if url.is_empty() || secret.is_empty() {
+                    tracing::warn!(entry = %entry, "Skipping malformed MLRUN_WEBHOOK_ENDPOINTS entry");
+                    return None;
+                }
+                Some(Self {
+                    url: url.to_string(),
+                    secret: secret.to_string(),
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_pairs() {
+        let endpoints =
+            WebhookEndpoint::parse("https://a.test/hook=secretA, https://b.test/hook=secretB");
+        assert_eq!(endpoints.len(), 2);
+        assert_eq!(endpoints[0].url, "https://a.test/hook");
+        assert_eq!(endpoints[0].secret, "secretA");
+        assert_eq!(endpoints[1].url, "https://b.test/hook");
+        assert_eq!(endpoints[1].secret, "secretB");
+    }
+
+    #[test]
+    fn test_parse_empty_string_yields_no_endpoints() {
+        assert!(WebhookEndpoint::parse("").is_empty());
+    }
+
+    #[test]
+    fn test_parse_skips_malformed_entries() {
+        let endpoints = WebhookEndpoint::parse("no-equals-sign,https://a.test/hook=secretA");
+        assert_eq!(endpoints.len(), 1);
+        assert_eq!(endpoints[0].url, "https://a.test/hook");
+    }
+}
@@ -0,0 +1,14 @@
+//! Webhook payload shapes.
+
+use serde::Serialize;
+
+/// Payload delivered to webhook endpoints when a run leaves `Running`.
+#[derive(Debug, Clone, Serialize)]
+pub struct RunLifecycleEvent {
+    pub run_id: String,
+    pub project_id: String,
+    /// One of `"finished"`, `"failed"`, `"killed"`.
+    pub status: String,
+    pub metrics_count: u64,
+    pub occurred_at: String,
+}
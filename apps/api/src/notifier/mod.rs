@@ -0,0 +1,13 @@
+//! Signed HTTP webhook notifications on run lifecycle events.
+//!
+//! Loaded once at startup from `MLRUN_WEBHOOK_ENDPOINTS` into [`Notifier`],
+//! which `http_finish_run` (and the gRPC `finish_run`) call to fan out a
+//! `RunLifecycleEvent` to every configured endpoint.
+
+mod client;
+mod config;
+mod event;
+
+pub use client::Notifier;
+pub use config::WebhookEndpoint;
+pub use event::RunLifecycleEvent;
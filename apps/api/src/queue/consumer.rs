@@ -0,0 +1,136 @@
+//! Background consumer: drains the ingest queue and applies batches to the
+//! run store, acking only after a successful write (at-least-once
+//! delivery). Failures are retried with exponential backoff rather than
+//! dropping the message.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use tracing::{error, info, warn};
+
+use crate::services::metrics::MetricPoint;
+use crate::storage::{BatchDelta, MetricsRepo, RunStore};
+
+use super::message::QueueMessage;
+use super::producer::QueueProducer;
+
+const INITIAL_BACKOFF: Duration = Duration::from_millis(100);
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+const EMPTY_POLL_DELAY: Duration = Duration::from_millis(200);
+
+/// Spawn the background consumer task. Runs until the process exits.
+pub fn spawn_consumer(
+    producer: Arc<dyn QueueProducer>,
+    run_store: Arc<dyn RunStore>,
+    metrics_repo: Arc<dyn MetricsRepo>,
+) {
+    tokio::spawn(async move {
+        info!(endpoint = %producer.endpoint(), "Starting ingest queue consumer");
+
+        loop {
+            match producer.poll().await {
+                Ok(Some(message)) => {
+                    apply_with_retry(producer.as_ref(), &run_store, &metrics_repo, message).await
+                }
+                Ok(None) => tokio::time::sleep(EMPTY_POLL_DELAY).await,
+                Err(e) => {
+                    warn!(error = %e, "Queue poll failed, backing off");
+                    tokio::time::sleep(Duration::from_secs(1)).await;
+                }
+            }
+        }
+    });
+}
+
+/// Apply one message to the store, retrying with exponential backoff on
+/// failure. The message is never dropped: this only returns once the
+/// write has succeeded and been acked.
+///
+/// `apply` writes to the run store and the metrics repo as two separate
+/// steps; a retry only re-runs whichever step didn't already succeed last
+/// time (tracked in `progress`), so a failure partway through - e.g.
+/// `add_points` erroring after `ingest_batch` already committed - doesn't
+/// double-apply the step that already landed.
+async fn apply_with_retry(
+    producer: &dyn QueueProducer,
+    run_store: &Arc<dyn RunStore>,
+    metrics_repo: &Arc<dyn MetricsRepo>,
+    message: QueueMessage,
+) {
+    let mut backoff = INITIAL_BACKOFF;
+    let mut progress = ApplyProgress::default();
+
+    loop {
+        match apply(run_store, metrics_repo, &message, &mut progress).await {
+            Ok(()) => {
+                if let Err(e) = producer.ack(message.sequence).await {
+                    warn!(error = %e, sequence = message.sequence, "Failed to ack applied message");
+                }
+                return;
+            }
+            Err(e) => {
+                error!(
+                    error = %e,
+                    sequence = message.sequence,
+                    retry_in = ?backoff,
+                    "Failed to apply queued batch, retrying"
+                );
+                tokio::time::sleep(backoff).await;
+                backoff = (backoff * 2).min(MAX_BACKOFF);
+            }
+        }
+    }
+}
+
+/// Tracks which steps of [`apply`] have already succeeded for a message
+/// being retried, so `apply_with_retry` doesn't redo them.
+#[derive(Default)]
+struct ApplyProgress {
+    run_store_applied: bool,
+}
+
+/// Write a queued batch to the run store and metrics repo - the same
+/// writes the direct (synchronous) path performs in `log_metrics`, so
+/// `Direct` and `Queued` modes converge on identical storage semantics.
+///
+/// Each step only runs once across retries of the same message (see
+/// `progress`): if this is called again after `ingest_batch` already
+/// succeeded but `add_points` failed, only `add_points` is retried.
+async fn apply(
+    run_store: &Arc<dyn RunStore>,
+    metrics_repo: &Arc<dyn MetricsRepo>,
+    message: &QueueMessage,
+    progress: &mut ApplyProgress,
+) -> Result<(), String> {
+    if !progress.run_store_applied {
+        run_store
+            .ingest_batch(
+                &message.run_id,
+                BatchDelta {
+                    metrics: message.points.len() as u64,
+                    ..Default::default()
+                },
+            )
+            .await
+            .map_err(|e| e.to_string())?;
+        progress.run_store_applied = true;
+    }
+
+    let points: Vec<MetricPoint> = message
+        .points
+        .iter()
+        .map(|point| MetricPoint {
+            name: point.name.clone(),
+            step: point.step,
+            value: point.value,
+            timestamp: point.timestamp,
+        })
+        .collect();
+
+    metrics_repo
+        .add_points(&message.run_id, points)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
@@ -0,0 +1,126 @@
+//! Kafka-backed `QueueProducer`, built on `rdkafka`.
+//!
+//! Enqueue goes through a `FutureProducer`; the consumer side commits
+//! offsets manually (`enable.auto.commit=false`) so a message is only
+//! marked delivered once [`QueueProducer::ack`] has been called after a
+//! successful write, giving at-least-once delivery across restarts.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use rdkafka::consumer::{Consumer, StreamConsumer};
+use rdkafka::producer::{FutureProducer, FutureRecord};
+use rdkafka::{ClientConfig, Message, TopicPartitionList};
+use tokio::sync::Mutex;
+
+use super::message::QueueMessage;
+use super::producer::{QueueError, QueueProducer};
+
+const TOPIC: &str = "mlrun-ingest";
+const CONSUMER_GROUP: &str = "mlrun-ingest-workers";
+const SEND_TIMEOUT: Duration = Duration::from_secs(5);
+
+pub struct KafkaProducer {
+    brokers: String,
+    producer: FutureProducer,
+    consumer: StreamConsumer,
+    next_sequence: AtomicU64,
+    /// Maps our `QueueMessage::sequence` to the (partition, offset) it was
+    /// delivered at, so `ack` can commit the right offset.
+    pending: Mutex<HashMap<u64, (i32, i64)>>,
+}
+
+impl KafkaProducer {
+    /// Build the producer/consumer clients from `brokers`. Fallible rather
+    /// than panicking, so a malformed `INGEST_QUEUE_URL` takes the same
+    /// "fall back to direct mode" path in `main.rs` as every other
+    /// optional-backend failure instead of taking down the whole server.
+    pub fn new(brokers: String) -> Result<Self, QueueError> {
+        let producer: FutureProducer = ClientConfig::new()
+            .set("bootstrap.servers", &brokers)
+            .create()
+            .map_err(|e| QueueError::Unavailable(format!("failed to build Kafka producer client: {e}")))?;
+
+        let consumer: StreamConsumer = ClientConfig::new()
+            .set("bootstrap.servers", &brokers)
+            .set("group.id", CONSUMER_GROUP)
+            .set("enable.auto.commit", "false")
+            .set("auto.offset.reset", "earliest")
+            .create()
+            .map_err(|e| QueueError::Unavailable(format!("failed to build Kafka consumer client: {e}")))?;
+
+        Ok(Self {
+            brokers,
+            producer,
+            consumer,
+            next_sequence: AtomicU64::new(1),
+            pending: Mutex::new(HashMap::new()),
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl QueueProducer for KafkaProducer {
+    async fn init_from_env(&self) -> Result<(), QueueError> {
+        self.consumer
+            .subscribe(&[TOPIC])
+            .map_err(|e| QueueError::Unavailable(e.to_string()))
+    }
+
+    async fn enqueue(&self, mut message: QueueMessage) -> Result<u64, QueueError> {
+        let sequence = self.next_sequence.fetch_add(1, Ordering::SeqCst);
+        message.sequence = sequence;
+        let payload = message.encode()?;
+
+        self.producer
+            .send(
+                FutureRecord::to(TOPIC)
+                    .key(&message.run_id)
+                    .payload(&payload),
+                SEND_TIMEOUT,
+            )
+            .await
+            .map_err(|(e, _)| QueueError::Unavailable(e.to_string()))?;
+
+        Ok(sequence)
+    }
+
+    async fn poll(&self) -> Result<Option<QueueMessage>, QueueError> {
+        let record =
+            match tokio::time::timeout(Duration::from_millis(200), self.consumer.recv()).await {
+                Ok(result) => result.map_err(|e| QueueError::Unavailable(e.to_string()))?,
+                Err(_) => return Ok(None), // no message within the poll window
+            };
+
+        let payload = record.payload().ok_or_else(|| {
+            QueueError::Unavailable("received Kafka message with no payload".to_string())
+        })?;
+        let message = QueueMessage::decode(payload)?;
+
+        self.pending
+            .lock()
+            .await
+            .insert(message.sequence, (record.partition(), record.offset()));
+
+        Ok(Some(message))
+    }
+
+    async fn ack(&self, sequence: u64) -> Result<(), QueueError> {
+        let Some((partition, offset)) = self.pending.lock().await.remove(&sequence) else {
+            return Ok(());
+        };
+
+        let mut tpl = TopicPartitionList::new();
+        tpl.add_partition_offset(TOPIC, partition, rdkafka::Offset::Offset(offset + 1))
+            .map_err(|e| QueueError::Unavailable(e.to_string()))?;
+
+        self.consumer
+            .commit(&tpl, rdkafka::consumer::CommitMode::Async)
+            .map_err(|e| QueueError::Unavailable(e.to_string()))
+    }
+
+    fn endpoint(&self) -> &str {
+        &self.brokers
+    }
+}
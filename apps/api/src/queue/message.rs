@@ -0,0 +1,66 @@
+//! Wire format for messages written to the ingest write-ahead queue.
+
+use serde::{Deserialize, Serialize};
+
+/// A metric point as carried through the queue (decoupled from the proto
+/// type so the queue doesn't depend on the wire format of either the HTTP
+/// or gRPC ingest transport).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueuedMetricPoint {
+    pub name: String,
+    pub step: i64,
+    pub value: f64,
+    pub timestamp: Option<f64>,
+}
+
+/// A batch of metric points enqueued for asynchronous ingestion.
+///
+/// `sequence` is assigned by the producer at enqueue time and is
+/// monotonically increasing per-process; the backends use it to find the
+/// queue entry `ack` has to acknowledge. Deduplication by `batch_id`
+/// happens in the ingest handlers before a batch is enqueued; the consumer
+/// does not check `batch_id`, so a redelivered message is applied again.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueueMessage {
+    pub sequence: u64,
+    pub run_id: String,
+    pub batch_id: String,
+    pub points: Vec<QueuedMetricPoint>,
+}
+
+impl QueueMessage {
+    pub fn encode(&self) -> Result<Vec<u8>, serde_json::Error> {
+        serde_json::to_vec(self)
+    }
+
+    pub fn decode(bytes: &[u8]) -> Result<Self, serde_json::Error> {
+        serde_json::from_slice(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_decode_roundtrip() {
+        let message = QueueMessage {
+            sequence: 7,
+            run_id: "run-1".to_string(),
+            batch_id: "batch-1".to_string(),
+            points: vec![QueuedMetricPoint {
+                name: "loss".to_string(),
+                step: 3,
+                value: 0.42,
+                timestamp: Some(1_700_000_000.0),
+            }],
+        };
+
+        let encoded = message.encode().unwrap();
+        let decoded = QueueMessage::decode(&encoded).unwrap();
+
+        assert_eq!(decoded.sequence, message.sequence);
+        assert_eq!(decoded.run_id, message.run_id);
+        assert_eq!(decoded.points.len(), 1);
+    }
+}
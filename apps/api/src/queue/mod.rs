@@ -0,0 +1,35 @@
+//! Write-ahead queue backing `IngestMode::Queued`.
+//!
+//! Ingest handlers enqueue a batch via a [`QueueProducer`] and return
+//! immediately; a background task (see [`spawn_consumer`]) drains the
+//! queue and performs the writes that the direct path would otherwise do
+//! synchronously, acking only once a write succeeds.
+
+mod consumer;
+mod kafka;
+mod message;
+mod producer;
+mod redis_streams;
+
+pub use consumer::spawn_consumer;
+pub use kafka::KafkaProducer;
+pub use message::{QueueMessage, QueuedMetricPoint};
+pub use producer::{QueueError, QueueProducer};
+pub use redis_streams::RedisStreamsProducer;
+
+use std::sync::Arc;
+
+use crate::config::QueueConfig;
+
+/// Build the configured `QueueProducer` from a validated [`QueueConfig`].
+///
+/// Fallible - e.g. a malformed `INGEST_QUEUE_URL` fails client
+/// construction for the Kafka backend - so callers can fall back to
+/// direct mode the same way they already do for `init_from_env` errors,
+/// instead of the server dying at startup.
+pub fn build(config: &QueueConfig) -> Result<Arc<dyn QueueProducer>, QueueError> {
+    Ok(match config.kind {
+        crate::config::QueueKind::Redis => Arc::new(RedisStreamsProducer::new(config.url.clone())),
+        crate::config::QueueKind::Kafka => Arc::new(KafkaProducer::new(config.url.clone())?),
+    })
+}
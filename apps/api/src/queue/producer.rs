@@ -0,0 +1,45 @@
+//! `QueueProducer` trait: write-ahead queue backend abstraction.
+//!
+//! Selected by `INGEST_QUEUE_KIND` (`redis` or `kafka`) via
+//! [`super::build`]. Ingest handlers call [`QueueProducer::enqueue`]
+//! and return immediately; the background consumer (see
+//! [`super::spawn_consumer`]) calls `poll`/`ack` to apply batches to storage
+//! with at-least-once delivery.
+
+use thiserror::Error;
+
+use super::message::QueueMessage;
+
+/// Errors talking to the queue backend.
+#[derive(Debug, Error)]
+pub enum QueueError {
+    #[error("queue backend unavailable: {0}")]
+    Unavailable(String),
+    #[error("failed to encode/decode queue message: {0}")]
+    Codec(#[from] serde_json::Error),
+}
+
+/// Storage-agnostic write-ahead queue producer/consumer.
+///
+/// Implementations must be safe to share behind an `Arc` and polled
+/// concurrently with handlers enqueueing new batches.
+#[async_trait::async_trait]
+pub trait QueueProducer: Send + Sync {
+    /// Connect to the backend and perform any one-time setup (e.g.
+    /// creating a consumer group or subscribing to a topic).
+    async fn init_from_env(&self) -> Result<(), QueueError>;
+
+    /// Enqueue a batch, returning the sequence id assigned to it.
+    async fn enqueue(&self, message: QueueMessage) -> Result<u64, QueueError>;
+
+    /// Poll for the next unacked message, if any. Returns `Ok(None)` when
+    /// the queue is currently empty rather than blocking indefinitely, so
+    /// callers can interleave polling with shutdown checks.
+    async fn poll(&self) -> Result<Option<QueueMessage>, QueueError>;
+
+    /// Acknowledge a message as durably applied, so it isn't redelivered.
+    async fn ack(&self, sequence: u64) -> Result<(), QueueError>;
+
+    /// Backend endpoint, for startup/diagnostic logging.
+    fn endpoint(&self) -> &str;
+}
@@ -0,0 +1,143 @@
+//! Redis Streams-backed `QueueProducer`.
+//!
+//! Uses a single stream (`mlrun:ingest`) with `XADD` to enqueue and a
+//! consumer group (`XREADGROUP`/`XACK`) to consume: a message stays
+//! pending in the group until acked. `poll` only reads entries that were
+//! never delivered before (`>`), so entries left pending by a consumer that
+//! stopped before acking them are not read again by this producer.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use redis::AsyncCommands;
+use tokio::sync::Mutex;
+
+use super::message::QueueMessage;
+use super::producer::{QueueError, QueueProducer};
+
+const STREAM_KEY: &str = "mlrun:ingest";
+const CONSUMER_GROUP: &str = "mlrun-ingest-workers";
+const CONSUMER_NAME: &str = "mlrun-api";
+
+pub struct RedisStreamsProducer {
+    url: String,
+    connection: Mutex<Option<redis::aio::ConnectionManager>>,
+    next_sequence: AtomicU64,
+    /// Maps our `QueueMessage::sequence` to the stream entry id it was
+    /// delivered as, so `ack` can `XACK` the right entry.
+    pending: Mutex<HashMap<u64, String>>,
+}
+
+impl RedisStreamsProducer {
+    pub fn new(url: String) -> Self {
+        Self {
+            url,
+            connection: Mutex::new(None),
+            next_sequence: AtomicU64::new(1),
+            pending: Mutex::new(HashMap::new()),
+        }
+    }
+
+    async fn connection(&self) -> Result<redis::aio::ConnectionManager, QueueError> {
+        let mut guard = self.connection.lock().await;
+        if let Some(conn) = guard.as_ref() {
+            return Ok(conn.clone());
+        }
+
+        let client = redis::Client::open(self.url.as_str())
+            .map_err(|e| QueueError::Unavailable(e.to_string()))?;
+        let conn = client
+            .get_connection_manager()
+            .await
+            .map_err(|e| QueueError::Unavailable(e.to_string()))?;
+        *guard = Some(conn.clone());
+        Ok(conn)
+    }
+}
+
+#[async_trait::async_trait]
+impl QueueProducer for RedisStreamsProducer {
+    async fn init_from_env(&self) -> Result<(), QueueError> {
+        let mut conn = self.connection().await?;
+
+        let result: redis::RedisResult<()> = redis::cmd("XGROUP")
+            .arg("CREATE")
+            .arg(STREAM_KEY)
+            .arg(CONSUMER_GROUP)
+            .arg("$")
+            .arg("MKSTREAM")
+            .query_async(&mut conn)
+            .await;
+
+        match result {
+            Ok(()) => Ok(()),
+            // The group already existing is not an error - it means a
+            // previous instance already set up the stream.
+            Err(e) if e.to_string().contains("BUSYGROUP") => Ok(()),
+            Err(e) => Err(QueueError::Unavailable(e.to_string())),
+        }
+    }
+
+    async fn enqueue(&self, mut message: QueueMessage) -> Result<u64, QueueError> {
+        let mut conn = self.connection().await?;
+        let sequence = self.next_sequence.fetch_add(1, Ordering::SeqCst);
+        message.sequence = sequence;
+        let payload = message.encode()?;
+
+        let _: String = conn
+            .xadd(STREAM_KEY, "*", &[("payload", payload)])
+            .await
+            .map_err(|e| QueueError::Unavailable(e.to_string()))?;
+
+        Ok(sequence)
+    }
+
+    async fn poll(&self) -> Result<Option<QueueMessage>, QueueError> {
+        let mut conn = self.connection().await?;
+
+        let reply: redis::streams::StreamReadReply = conn
+            .xread_options(
+                &[STREAM_KEY],
+                &[">"],
+                &redis::streams::StreamReadOptions::default()
+                    .group(CONSUMER_GROUP, CONSUMER_NAME)
+                    .count(1),
+            )
+            .await
+            .map_err(|e| QueueError::Unavailable(e.to_string()))?;
+
+        for stream_key in reply.keys {
+            for entry in stream_key.ids {
+                let Some(redis::Value::Data(bytes)) = entry.map.get("payload") else {
+                    continue;
+                };
+                let message = QueueMessage::decode(bytes)?;
+                self.pending
+                    .lock()
+                    .await
+                    .insert(message.sequence, entry.id.clone());
+                return Ok(Some(message));
+            }
+        }
+
+        Ok(None)
+    }
+
+    async fn ack(&self, sequence: u64) -> Result<(), QueueError> {
+        let Some(entry_id) = self.pending.lock().await.remove(&sequence) else {
+            return Ok(());
+        };
+
+        let mut conn = self.connection().await?;
+        let _: i64 = conn
+            .xack(STREAM_KEY, CONSUMER_GROUP, &[entry_id])
+            .await
+            .map_err(|e| QueueError::Unavailable(e.to_string()))?;
+
+        Ok(())
+    }
+
+    fn endpoint(&self) -> &str {
+        &self.url
+    }
+}
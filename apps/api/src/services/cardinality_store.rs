@@ -0,0 +1,220 @@
+//! Persistence for `CardinalityTracker`'s per-project tag-pair state.
+//!
+//! Without this, every restart resets a project's tracked tag pairs back
+//! to zero and instantly re-admits cardinality the guardrail had already
+//! rejected - defeating `max_tags_per_project` across deploys. Mirrors the
+//! `RunStore`/`MetricsRepo` backend split elsewhere in the server: an
+//! in-memory no-op default plus a durable embedded-SQLite adapter,
+//! selected via `MLRUN_CARDINALITY_STORE` (see `LimitsConfig::from_env`).
+//!
+//! `CardinalityTracker` hydrates a project lazily, on first touch, via
+//! [`CardinalityStore::load_project`] rather than eagerly loading every
+//! known project at startup - there's no bound on how many projects might
+//! be sitting in the store, and only the ones actually ingesting right now
+//! need to be in memory.
+
+use tracing::warn;
+
+/// A project's persisted tag-pair cardinality state - mirrors `TagPairs`,
+/// but serializable.
+#[derive(Debug, Clone)]
+pub enum PersistedTagPairs {
+    Exact(Vec<(String, String)>),
+    /// Raw `HyperLogLog` registers, see `HyperLogLog::registers`.
+    Sketch(Vec<u8>),
+}
+
+/// A run's persisted tag-key/metric-name state.
+#[derive(Debug, Clone, Default)]
+pub struct PersistedRunCardinality {
+    pub tag_keys: Vec<String>,
+    pub metric_names: Vec<String>,
+}
+
+/// Storage backend for `CardinalityTracker`'s project/run state.
+///
+/// `load_run`/`remove_run` exist mainly for symmetry with
+/// `CardinalityTracker::clear_run`'s cleanup; the guardrail problem this
+/// store actually fixes is project-level (see the module doc), so only
+/// project state is ever written back here.
+#[async_trait::async_trait]
+pub trait CardinalityStore: std::fmt::Debug + Send + Sync {
+    /// Load a project's previously-persisted tag-pair state, if any.
+    async fn load_project(&self, project_id: &str) -> Option<PersistedTagPairs>;
+
+    /// Persist a project's current tag-pair state. Called whenever
+    /// `validate_batch` accepts a genuinely new pair, not on every
+    /// already-known repeat.
+    async fn persist_project(&self, project_id: &str, state: &PersistedTagPairs);
+
+    /// Load a run's previously-persisted state, if any.
+    async fn load_run(&self, run_id: &str) -> Option<PersistedRunCardinality>;
+
+    /// Remove a run's persisted state.
+    async fn remove_run(&self, run_id: &str);
+}
+
+/// No-op store: nothing survives a restart. Default backend
+/// (`MLRUN_CARDINALITY_STORE=memory`, or unset) - matches the tracker's
+/// historical, pre-persistence behavior.
+#[derive(Debug, Default)]
+pub struct MemoryCardinalityStore;
+
+#[async_trait::async_trait]
+impl CardinalityStore for MemoryCardinalityStore {
+    async fn load_project(&self, _project_id: &str) -> Option<PersistedTagPairs> {
+        None
+    }
+
+    async fn persist_project(&self, _project_id: &str, _state: &PersistedTagPairs) {}
+
+    async fn load_run(&self, _run_id: &str) -> Option<PersistedRunCardinality> {
+        None
+    }
+
+    async fn remove_run(&self, _run_id: &str) {}
+}
+
+/// Embedded-SQLite-backed store: durable across restarts without a
+/// ClickHouse/Postgres round-trip on the ingest hot path. Selected via
+/// `MLRUN_CARDINALITY_STORE=sqlite` with `MLRUN_CARDINALITY_STORE_PATH`.
+#[derive(Debug)]
+pub struct SqliteCardinalityStore {
+    pool: sqlx::SqlitePool,
+}
+
+impl SqliteCardinalityStore {
+    /// Connect to (creating if missing) the SQLite database at `path`,
+    /// creating its schema if needed.
+    pub async fn connect(path: &str) -> Result<Self, sqlx::Error> {
+        let options = sqlx::sqlite::SqliteConnectOptions::new()
+            .filename(path)
+            .create_if_missing(true);
+        let pool = sqlx::sqlite::SqlitePoolOptions::new()
+            .max_connections(4)
+            .connect_with(options)
+            .await?;
+
+        sqlx::query(
+            "CREATE TABLE IF NOT EXISTS project_cardinality (
+                project_id TEXT PRIMARY KEY,
+                exact_pairs TEXT,
+                hll_registers BLOB
+            )",
+        )
+        .execute(&pool)
+        .await?;
+
+        sqlx::query(
+            "CREATE TABLE IF NOT EXISTS run_cardinality (
+                run_id TEXT PRIMARY KEY,
+                tag_keys TEXT NOT NULL,
+                metric_names TEXT NOT NULL
+            )",
+        )
+        .execute(&pool)
+        .await?;
+
+        Ok(Self { pool })
+    }
+}
+
+#[async_trait::async_trait]
+impl CardinalityStore for SqliteCardinalityStore {
+    async fn load_project(&self, project_id: &str) -> Option<PersistedTagPairs> {
+        let row = sqlx::query_as::<_, (Option<String>, Option<Vec<u8>>)>(
+            "SELECT exact_pairs, hll_registers FROM project_cardinality WHERE project_id = ?",
+        )
+        .bind(project_id)
+        .fetch_optional(&self.pool)
+        .await;
+
+        match row {
+            Ok(Some((Some(json), _))) => serde_json::from_str::<Vec<(String, String)>>(&json)
+                .ok()
+                .map(PersistedTagPairs::Exact),
+            Ok(Some((_, Some(registers)))) => Some(PersistedTagPairs::Sketch(registers)),
+            Ok(_) => None,
+            Err(e) => {
+                warn!(project_id = %project_id, error = %e, "Failed to load persisted cardinality state");
+                None
+            }
+        }
+    }
+
+    async fn persist_project(&self, project_id: &str, state: &PersistedTagPairs) {
+        let (exact_json, registers) = match state {
+            PersistedTagPairs::Exact(pairs) => (serde_json::to_string(pairs).ok(), None),
+            PersistedTagPairs::Sketch(registers) => (None, Some(registers.clone())),
+        };
+
+        let result = sqlx::query(
+            "INSERT INTO project_cardinality (project_id, exact_pairs, hll_registers)
+             VALUES (?, ?, ?)
+             ON CONFLICT(project_id) DO UPDATE SET
+                exact_pairs = excluded.exact_pairs,
+                hll_registers = excluded.hll_registers",
+        )
+        .bind(project_id)
+        .bind(exact_json)
+        .bind(registers)
+        .execute(&self.pool)
+        .await;
+
+        if let Err(e) = result {
+            warn!(project_id = %project_id, error = %e, "Failed to persist cardinality state");
+        }
+    }
+
+    async fn load_run(&self, run_id: &str) -> Option<PersistedRunCardinality> {
+        let row = sqlx::query_as::<_, (String, String)>(
+            "SELECT tag_keys, metric_names FROM run_cardinality WHERE run_id = ?",
+        )
+        .bind(run_id)
+        .fetch_optional(&self.pool)
+        .await;
+
+        match row {
+            Ok(Some((tag_keys, metric_names))) => Some(PersistedRunCardinality {
+                tag_keys: serde_json::from_str(&tag_keys).unwrap_or_default(),
+                metric_names: serde_json::from_str(&metric_names).unwrap_or_default(),
+            }),
+            Ok(None) => None,
+            Err(e) => {
+                warn!(run_id = %run_id, error = %e, "Failed to load persisted run cardinality state");
+                None
+            }
+        }
+    }
+
+    async fn remove_run(&self, run_id: &str) {
+        let result = sqlx::query("DELETE FROM run_cardinality WHERE run_id = ?")
+            .bind(run_id)
+            .execute(&self.pool)
+            .await;
+
+        if let Err(e) = result {
+            warn!(run_id = %run_id, error = %e, "Failed to remove persisted run cardinality state");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_memory_store_never_persists_anything() {
+        let store = MemoryCardinalityStore;
+        store
+            .persist_project(
+                "proj",
+                &PersistedTagPairs::Exact(vec![("a".to_string(), "1".to_string())]),
+            )
+            .await;
+
+        assert!(store.load_project("proj").await.is_none());
+        assert!(store.load_run("run").await.is_none());
+        store.remove_run("run").await; // must not panic
+    }
+}
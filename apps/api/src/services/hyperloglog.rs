@@ -0,0 +1,156 @@
+//! Fixed-memory cardinality estimation via HyperLogLog.
+//!
+//! Used by [`super::limits::CardinalityTracker`] to bound the memory cost
+//! of the project-level tag guardrail: once a project's exact `tag_pairs`
+//! set would otherwise grow without limit, it can be replaced with one of
+//! these sketches, trading exact counts for a ~0.8% std error estimate
+//! (`p = 14` => 16384 single-byte registers, ~16 KB regardless of how many
+//! distinct pairs are observed).
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+/// Register precision: `m = 2^PRECISION` registers.
+const PRECISION: u8 = 14;
+
+/// A HyperLogLog sketch estimating the number of distinct values inserted.
+#[derive(Debug, Clone)]
+pub(crate) struct HyperLogLog {
+    registers: Vec<u8>,
+}
+
+impl HyperLogLog {
+    /// Create a new sketch with `2^PRECISION` registers.
+    pub(crate) fn new() -> Self {
+        Self {
+            registers: vec![0u8; 1usize << PRECISION],
+        }
+    }
+
+    /// Record an observation of `value`.
+    ///
+    /// Idempotent up to register precision: inserting the same value twice
+    /// does not change the estimate.
+    pub(crate) fn insert<T: Hash>(&mut self, value: &T) {
+        let mut hasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+        let hash = hasher.finish();
+
+        let index = (hash >> (64 - PRECISION)) as usize;
+        let remaining_bits = (64 - PRECISION) as u32;
+        let rest = hash << PRECISION;
+        let rank = rest.leading_zeros().min(remaining_bits) as u8 + 1;
+
+        let register = &mut self.registers[index];
+        *register = (*register).max(rank);
+    }
+
+    /// Rebuild a sketch from previously-saved `registers` (e.g. read back
+    /// from a [`super::cardinality_store::CardinalityStore`]). `registers` must have
+    /// come from [`Self::registers`] on a sketch with the same
+    /// [`PRECISION`] - a length mismatch is treated as corrupt state and
+    /// produces an empty sketch rather than panicking.
+    pub(crate) fn from_registers(registers: Vec<u8>) -> Self {
+        if registers.len() != 1usize << PRECISION {
+            return Self::new();
+        }
+        Self { registers }
+    }
+
+    /// Raw register bytes, for persisting this sketch (see
+    /// [`Self::from_registers`]).
+    pub(crate) fn registers(&self) -> &[u8] {
+        &self.registers
+    }
+
+    /// Estimate the number of distinct values inserted so far.
+    ///
+    /// Uses the standard HyperLogLog estimator, falling back to linear
+    /// counting when the raw estimate is small relative to `m` and some
+    /// registers are still empty (the usual correction for small
+    /// cardinalities, where the harmonic-mean estimator is biased).
+    pub(crate) fn estimate(&self) -> f64 {
+        let m = self.registers.len() as f64;
+        let alpha_m = 0.7213 / (1.0 + 1.079 / m);
+
+        let sum_inverses: f64 = self
+            .registers
+            .iter()
+            .map(|&rank| 2f64.powi(-(rank as i32)))
+            .sum();
+        let raw_estimate = alpha_m * m * m / sum_inverses;
+
+        if raw_estimate <= 2.5 * m {
+            let zero_registers = self.registers.iter().filter(|&&rank| rank == 0).count();
+            if zero_registers > 0 {
+                return m * (m / zero_registers as f64).ln();
+            }
+        }
+
+        raw_estimate
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_sketch_estimates_zero() {
+        let hll = HyperLogLog::new();
+        assert_eq!(hll.estimate(), 0.0);
+    }
+
+    #[test]
+    fn test_estimate_is_within_error_bound_for_small_cardinality() {
+        let mut hll = HyperLogLog::new();
+        for i in 0..1000 {
+            hll.insert(&format!("item-{i}"));
+        }
+
+        let estimate = hll.estimate();
+        assert!(
+            (800.0..1200.0).contains(&estimate),
+            "estimate {estimate} too far from true cardinality 1000"
+        );
+    }
+
+    #[test]
+    fn test_estimate_is_within_error_bound_for_large_cardinality() {
+        let mut hll = HyperLogLog::new();
+        for i in 0..200_000 {
+            hll.insert(&format!("item-{i}"));
+        }
+
+        let estimate = hll.estimate();
+        let error = (estimate - 200_000.0).abs() / 200_000.0;
+        assert!(error < 0.05, "relative error {error} exceeded 5%");
+    }
+
+    #[test]
+    fn test_registers_round_trip() {
+        let mut hll = HyperLogLog::new();
+        for i in 0..500 {
+            hll.insert(&format!("item-{i}"));
+        }
+
+        let restored = HyperLogLog::from_registers(hll.registers().to_vec());
+        assert_eq!(restored.estimate(), hll.estimate());
+    }
+
+    #[test]
+    fn test_from_registers_with_wrong_length_is_empty() {
+        let restored = HyperLogLog::from_registers(vec![1, 2, 3]);
+        assert_eq!(restored.estimate(), 0.0);
+    }
+
+    #[test]
+    fn test_duplicate_inserts_do_not_change_estimate() {
+        let mut hll = HyperLogLog::new();
+        for _ in 0..1000 {
+            hll.insert(&"same-value");
+        }
+
+        assert!(hll.estimate() < 2.0);
+    }
+}
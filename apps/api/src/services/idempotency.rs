@@ -63,8 +63,9 @@ pub struct BatchRecord {
     pub created_at: std::time::SystemTime,
 }
 
-/// In-memory idempotency store for alpha development.
-/// In production, this would be backed by PostgreSQL.
+/// In-memory idempotency store for alpha development. For a variant that
+/// survives a process restart, see
+/// [`super::idempotency_wal::WalIdempotencyStore`].
 #[derive(Debug, Default)]
 pub struct IdempotencyStore {
     /// Map from (run_id, batch_id) to BatchRecord
@@ -0,0 +1,641 @@
+//! WAL-backed idempotency store: a durable alternative to [`IdempotencyStore`].
+//!
+//! `IdempotencyStore` is purely in-memory, so a process restart loses all
+//! batch_id/sequence state and SDK retries after a crash would be
+//! re-ingested as duplicates. [`WalIdempotencyStore`] borrows the
+//! write-ahead-log + finalization design used for durable event logs:
+//!
+//! - Every `check_and_record` that returns `New` or `OutOfOrder` appends a
+//!   serialized [`BatchRecord`] to an append-only segment file, fsync'd in
+//!   batches of [`WalConfig::fsync_batch_size`].
+//! - [`WalIdempotencyStore::open`] rebuilds the in-memory read cache (a
+//!   plain [`IdempotencyStore`]) by replaying every segment through the same
+//!   `check_and_record` path a live call would take, so replay is
+//!   idempotent by construction and conflicting payloads are still
+//!   detected as `Conflict` after a restart.
+//! - [`WalIdempotencyStore::finalize`] lets the downstream sink (once a
+//!   batch is durably committed to permanent storage) mark records up to a
+//!   sequence number as safe to drop. The watermark is persisted to a small
+//!   metadata file (temp-file-then-rename) so it survives a crash even
+//!   before the next compaction runs.
+//! - [`WalIdempotencyStore::compact`] scans every segment, drops finalized
+//!   records, and rewrites survivors into one fresh segment - fsync'd and
+//!   renamed into place before any old segment is deleted, so a crash
+//!   mid-compaction leaves either the old or the new segment fully intact.
+
+use std::collections::HashMap;
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::UNIX_EPOCH;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tokio::sync::{Mutex, RwLock};
+use tracing::{info, warn};
+
+use super::idempotency::{BatchRecord, IdempotencyResult, IdempotencyStore};
+
+/// Errors from WAL segment or watermark I/O.
+#[derive(Error, Debug)]
+pub enum WalError {
+    #[error("WAL I/O error: {0}")]
+    Io(#[from] io::Error),
+    #[error("WAL serialization error: {0}")]
+    Serde(#[from] serde_json::Error),
+}
+
+/// Configuration for [`WalIdempotencyStore::open`].
+#[derive(Debug, Clone)]
+pub struct WalConfig {
+    /// Directory holding segment files (`segment-<n>.wal`) and the
+    /// watermark metadata file. Created if it doesn't exist.
+    pub dir: PathBuf,
+    /// Number of appended records between fsyncs. `1` fsyncs every record
+    /// (safest, slowest); higher values trade durability latency for fewer
+    /// fsync syscalls.
+    pub fsync_batch_size: usize,
+}
+
+impl WalConfig {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            fsync_batch_size: 1,
+        }
+    }
+}
+
+/// On-disk shape of a [`BatchRecord`]. `created_at` is stored as epoch
+/// millis rather than `SystemTime` directly, since `SystemTime` has no
+/// stable serde representation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct WalRecord {
+    project_id: String,
+    run_id: String,
+    batch_id: String,
+    seq: i64,
+    payload_hash: String,
+    metric_count: i32,
+    param_count: i32,
+    tag_count: i32,
+    created_at_unix_ms: u64,
+}
+
+impl From<&BatchRecord> for WalRecord {
+    fn from(record: &BatchRecord) -> Self {
+        let created_at_unix_ms = record
+            .created_at
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as u64;
+        Self {
+            project_id: record.project_id.clone(),
+            run_id: record.run_id.clone(),
+            batch_id: record.batch_id.clone(),
+            seq: record.seq,
+            payload_hash: record.payload_hash.clone(),
+            metric_count: record.metric_count,
+            param_count: record.param_count,
+            tag_count: record.tag_count,
+            created_at_unix_ms,
+        }
+    }
+}
+
+/// The currently-open segment file records are appended to.
+#[derive(Debug)]
+struct WalWriter {
+    file: std::fs::File,
+    unsynced: usize,
+}
+
+/// Durable, WAL-backed variant of [`IdempotencyStore`]. See the module docs
+/// for the durability design.
+pub struct WalIdempotencyStore {
+    inner: IdempotencyStore,
+    dir: PathBuf,
+    fsync_batch_size: usize,
+    next_segment_index: AtomicU64,
+    writer: Mutex<WalWriter>,
+    /// Per-run "finalized up to and including this seq" watermark.
+    watermark: RwLock<HashMap<String, i64>>,
+}
+
+impl WalIdempotencyStore {
+    /// Open (creating if necessary) the WAL directory in `config.dir`,
+    /// replaying every existing segment to rebuild the in-memory read
+    /// cache before accepting new writes.
+    pub async fn open(config: WalConfig) -> Result<Self, WalError> {
+        std::fs::create_dir_all(&config.dir)?;
+
+        let watermark = load_watermark(&config.dir)?;
+        let inner = IdempotencyStore::new();
+
+        let segments = list_segments(&config.dir)?;
+        for segment in &segments {
+            replay_segment(&inner, segment).await?;
+        }
+
+        let next_index = segments
+            .iter()
+            .filter_map(|p| segment_index(p))
+            .max()
+            .map(|n| n + 1)
+            .unwrap_or(0);
+        let active_path = segment_path(&config.dir, next_index);
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&active_path)?;
+
+        info!(
+            dir = %config.dir.display(),
+            segments = segments.len(),
+            "Recovered idempotency WAL"
+        );
+
+        Ok(Self {
+            inner,
+            dir: config.dir,
+            fsync_batch_size: config.fsync_batch_size.max(1),
+            next_segment_index: AtomicU64::new(next_index + 1),
+            writer: Mutex::new(WalWriter { file, unsynced: 0 }),
+            watermark: RwLock::new(watermark),
+        })
+    }
+
+    /// Same contract as [`IdempotencyStore::check_and_record`], durably
+    /// appending to the WAL whenever the batch is newly accepted (`New` or
+    /// `OutOfOrder`); `Duplicate`/`Conflict` batches were already durable
+    /// from their first arrival and aren't re-appended.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn check_and_record(
+        &self,
+        project_id: &str,
+        run_id: &str,
+        batch_id: &str,
+        seq: i64,
+        payload_hash: &str,
+        metric_count: i32,
+        param_count: i32,
+        tag_count: i32,
+    ) -> Result<IdempotencyResult, WalError> {
+        let result = self
+            .inner
+            .check_and_record(
+                project_id,
+                run_id,
+                batch_id,
+                seq,
+                payload_hash,
+                metric_count,
+                param_count,
+                tag_count,
+            )
+            .await;
+
+        if result.should_process() {
+            if let Some(record) = self.inner.get_batch(run_id, batch_id).await {
+                self.append(&record).await?;
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Get batch record if it exists (delegates to the in-memory cache).
+    pub async fn get_batch(&self, run_id: &str, batch_id: &str) -> Option<BatchRecord> {
+        self.inner.get_batch(run_id, batch_id).await
+    }
+
+    /// Get the highest sequence number seen for a run.
+    pub async fn get_sequence(&self, run_id: &str) -> i64 {
+        self.inner.get_sequence(run_id).await
+    }
+
+    /// Mark every record for `run_id` with `seq <= up_to_seq` as finalized
+    /// (durably committed downstream), so a later [`Self::compact`] call
+    /// may drop them from the WAL. Persists the watermark immediately
+    /// (temp-file-then-rename) so it survives a crash before compaction.
+    pub async fn finalize(&self, run_id: &str, up_to_seq: i64) -> Result<(), WalError> {
+        {
+            let mut watermark = self.watermark.write().await;
+            let entry = watermark.entry(run_id.to_string()).or_insert(i64::MIN);
+            if up_to_seq > *entry {
+                *entry = up_to_seq;
+            }
+        }
+        self.persist_watermark().await
+    }
+
+    /// Current finalized-up-to watermark for `run_id` (`None` if nothing
+    /// has been finalized yet).
+    pub async fn watermark_for(&self, run_id: &str) -> Option<i64> {
+        self.watermark.read().await.get(run_id).copied()
+    }
+
+    /// Scan every segment, drop records finalized by [`Self::finalize`],
+    /// and rewrite the survivors into one fresh segment. The new segment
+    /// is fully written and fsync'd, then renamed into place, before any
+    /// old segment is deleted - so a crash at any point during compaction
+    /// leaves either the old segments or the new one fully intact, never a
+    /// mix.
+    pub async fn compact(&self) -> Result<(), WalError> {
+        let mut writer = self.writer.lock().await;
+        // Flush the active segment so its on-disk content reflects every
+        // append acknowledged before this call.
+        writer.file.sync_all()?;
+
+        let watermark = self.watermark.read().await.clone();
+        let segments = list_segments(&self.dir)?;
+
+        let mut surviving = Vec::new();
+        for segment in &segments {
+            for record in read_segment(segment)? {
+                let finalized_through = watermark.get(&record.run_id).copied().unwrap_or(i64::MIN);
+                if record.seq > finalized_through {
+                    surviving.push(record);
+                }
+            }
+        }
+
+        let next_index = self.next_segment_index.fetch_add(1, Ordering::SeqCst);
+        let final_path = segment_path(&self.dir, next_index);
+        let tmp_path = final_path.with_extension("wal.tmp");
+        {
+            let mut tmp_file = std::fs::File::create(&tmp_path)?;
+            for record in &surviving {
+                write_record_line(&mut tmp_file, record)?;
+            }
+            tmp_file.sync_all()?;
+        }
+        std::fs::rename(&tmp_path, &final_path)?;
+
+        let new_file = std::fs::OpenOptions::new().append(true).open(&final_path)?;
+        *writer = WalWriter {
+            file: new_file,
+            unsynced: 0,
+        };
+        drop(writer);
+
+        for segment in segments {
+            if segment != final_path {
+                if let Err(e) = std::fs::remove_file(&segment) {
+                    warn!(segment = %segment.display(), error = %e, "Failed to remove compacted WAL segment");
+                }
+            }
+        }
+
+        info!(
+            dir = %self.dir.display(),
+            surviving = surviving.len(),
+            "Compacted idempotency WAL"
+        );
+        Ok(())
+    }
+
+    async fn append(&self, record: &BatchRecord) -> Result<(), WalError> {
+        let wal_record = WalRecord::from(record);
+        let mut writer = self.writer.lock().await;
+        write_record_line(&mut writer.file, &wal_record)?;
+        writer.unsynced += 1;
+        if writer.unsynced >= self.fsync_batch_size {
+            writer.file.sync_all()?;
+            writer.unsynced = 0;
+        }
+        Ok(())
+    }
+
+    async fn persist_watermark(&self) -> Result<(), WalError> {
+        let snapshot = self.watermark.read().await.clone();
+        let final_path = self.dir.join("watermark.json");
+        let tmp_path = self.dir.join("watermark.json.tmp");
+        {
+            let mut file = std::fs::File::create(&tmp_path)?;
+            serde_json::to_writer(&mut file, &snapshot)?;
+            file.sync_all()?;
+        }
+        std::fs::rename(&tmp_path, &final_path)?;
+        Ok(())
+    }
+}
+
+/// Selects which idempotency store `AppState` uses, per
+/// `config::IdempotencyStoreBackend`: in-memory (default, state vanishes on
+/// restart) or this module's WAL-backed store (durable, recovered on
+/// startup).
+pub enum IdempotencyBackend {
+    Memory(IdempotencyStore),
+    Wal(WalIdempotencyStore),
+}
+
+impl IdempotencyBackend {
+    /// Build the backend selected by `backend`/`wal_dir`. Like
+    /// `CardinalityTracker::connect`'s Sqlite fallback, a `Wal` backend
+    /// that fails to open (e.g. an unwritable directory) falls back to
+    /// in-memory rather than failing the whole server to start over an
+    /// optional durability upgrade.
+    pub async fn connect(
+        backend: crate::config::IdempotencyStoreBackend,
+        wal_dir: impl Into<PathBuf>,
+    ) -> Self {
+        match backend {
+            crate::config::IdempotencyStoreBackend::Memory => {
+                Self::Memory(IdempotencyStore::new())
+            }
+            crate::config::IdempotencyStoreBackend::Wal => {
+                let config = WalConfig::new(wal_dir);
+                match WalIdempotencyStore::open(config).await {
+                    Ok(store) => Self::Wal(store),
+                    Err(e) => {
+                        warn!(
+                            error = %e,
+                            "Failed to open idempotency WAL, falling back to in-memory store"
+                        );
+                        Self::Memory(IdempotencyStore::new())
+                    }
+                }
+            }
+        }
+    }
+
+    /// Same contract as [`IdempotencyStore::check_and_record`]. A `Wal`
+    /// backend that fails to durably append (e.g. disk full) treats the
+    /// batch as new rather than blocking ingestion on a durability write -
+    /// durability is an upgrade over the in-memory store, not a hard
+    /// requirement it can regress ingest availability to provide. This can
+    /// only happen for a batch that was `New` or `OutOfOrder` (the cases
+    /// `WalIdempotencyStore` appends for); `Duplicate`/`Conflict` batches
+    /// never reach the append step and so can't fail this way.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn check_and_record(
+        &self,
+        project_id: &str,
+        run_id: &str,
+        batch_id: &str,
+        seq: i64,
+        payload_hash: &str,
+        metric_count: i32,
+        param_count: i32,
+        tag_count: i32,
+    ) -> IdempotencyResult {
+        match self {
+            Self::Memory(store) => {
+                store
+                    .check_and_record(
+                        project_id,
+                        run_id,
+                        batch_id,
+                        seq,
+                        payload_hash,
+                        metric_count,
+                        param_count,
+                        tag_count,
+                    )
+                    .await
+            }
+            Self::Wal(store) => {
+                match store
+                    .check_and_record(
+                        project_id,
+                        run_id,
+                        batch_id,
+                        seq,
+                        payload_hash,
+                        metric_count,
+                        param_count,
+                        tag_count,
+                    )
+                    .await
+                {
+                    Ok(result) => result,
+                    Err(e) => {
+                        warn!(
+                            error = %e,
+                            run_id = %run_id,
+                            batch_id = %batch_id,
+                            "Idempotency WAL append failed; treating batch as new rather than \
+                             blocking ingestion"
+                        );
+                        IdempotencyResult::New
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn write_record_line(file: &mut std::fs::File, record: &WalRecord) -> Result<(), WalError> {
+    let mut line = serde_json::to_string(record)?;
+    line.push('\n');
+    file.write_all(line.as_bytes())?;
+    Ok(())
+}
+
+fn segment_path(dir: &Path, index: u64) -> PathBuf {
+    dir.join(format!("segment-{index:020}.wal"))
+}
+
+fn segment_index(path: &Path) -> Option<u64> {
+    path.file_stem()?
+        .to_str()?
+        .strip_prefix("segment-")?
+        .parse()
+        .ok()
+}
+
+fn list_segments(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut paths: Vec<PathBuf> = std::fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|p| p.extension().is_some_and(|ext| ext == "wal"))
+        .collect();
+    paths.sort();
+    Ok(paths)
+}
+
+/// Read every parseable record from `path`. A line that fails to parse
+/// stops replay of the rest of the segment rather than erroring the whole
+/// load - segments are append-only, so the only way a line is malformed is
+/// a torn write at the tail from a crash mid-append.
+fn read_segment(path: &Path) -> Result<Vec<WalRecord>, WalError> {
+    let file = std::fs::File::open(path)?;
+    let reader = BufReader::new(file);
+
+    let mut records = Vec::new();
+    for line in reader.lines() {
+        let line = line?;
+        if line.is_empty() {
+            continue;
+        }
+        match serde_json::from_str::<WalRecord>(&line) {
+            Ok(record) => records.push(record),
+            Err(e) => {
+                warn!(
+                    segment = %path.display(),
+                    error = %e,
+                    "Stopping WAL replay at unparsable line (likely a torn write from a crash)"
+                );
+                break;
+            }
+        }
+    }
+    Ok(records)
+}
+
+/// Replay `path` through [`IdempotencyStore::check_and_record`] - the same
+/// path a live call takes - so reconstructing the in-memory cache can never
+/// drift from live behavior, and replaying the same WAL twice yields
+/// identical maps.
+async fn replay_segment(inner: &IdempotencyStore, path: &Path) -> Result<(), WalError> {
+    for record in read_segment(path)? {
+        inner
+            .check_and_record(
+                &record.project_id,
+                &record.run_id,
+                &record.batch_id,
+                record.seq,
+                &record.payload_hash,
+                record.metric_count,
+                record.param_count,
+                record.tag_count,
+            )
+            .await;
+    }
+    Ok(())
+}
+
+fn load_watermark(dir: &Path) -> io::Result<HashMap<String, i64>> {
+    let path = dir.join("watermark.json");
+    match std::fs::read(&path) {
+        Ok(bytes) => Ok(serde_json::from_slice(&bytes).unwrap_or_default()),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(HashMap::new()),
+        Err(e) => Err(e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let unique = std::time::SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        std::env::temp_dir().join(format!("mlrun-idempotency-wal-{name}-{unique}"))
+    }
+
+    #[tokio::test]
+    async fn test_new_batch_persists_across_restart() {
+        let dir = temp_dir("restart");
+
+        {
+            let store = WalIdempotencyStore::open(WalConfig::new(&dir))
+                .await
+                .unwrap();
+            let result = store
+                .check_and_record("p", "r", "b1", 1, "hash1", 1, 0, 0)
+                .await
+                .unwrap();
+            assert_eq!(result, IdempotencyResult::New);
+        }
+
+        // Reopen: replay should reconstruct the same batch.
+        let store = WalIdempotencyStore::open(WalConfig::new(&dir))
+            .await
+            .unwrap();
+        let batch = store.get_batch("r", "b1").await.unwrap();
+        assert_eq!(batch.payload_hash, "hash1");
+        assert_eq!(store.get_sequence("r").await, 1);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[tokio::test]
+    async fn test_conflict_detected_after_restart() {
+        let dir = temp_dir("conflict");
+
+        {
+            let store = WalIdempotencyStore::open(WalConfig::new(&dir))
+                .await
+                .unwrap();
+            store
+                .check_and_record("p", "r", "b1", 1, "hash1", 1, 0, 0)
+                .await
+                .unwrap();
+        }
+
+        let store = WalIdempotencyStore::open(WalConfig::new(&dir))
+            .await
+            .unwrap();
+        let result = store
+            .check_and_record("p", "r", "b1", 1, "hash2", 1, 0, 0)
+            .await
+            .unwrap();
+        assert!(matches!(result, IdempotencyResult::Conflict { .. }));
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[tokio::test]
+    async fn test_compact_drops_finalized_records_but_keeps_rest() {
+        let dir = temp_dir("compact");
+        let store = WalIdempotencyStore::open(WalConfig::new(&dir))
+            .await
+            .unwrap();
+
+        store
+            .check_and_record("p", "r", "b1", 1, "h1", 1, 0, 0)
+            .await
+            .unwrap();
+        store
+            .check_and_record("p", "r", "b2", 2, "h2", 1, 0, 0)
+            .await
+            .unwrap();
+
+        store.finalize("r", 1).await.unwrap();
+        store.compact().await.unwrap();
+
+        assert_eq!(store.watermark_for("r").await, Some(1));
+
+        drop(store);
+        let reopened = WalIdempotencyStore::open(WalConfig::new(&dir))
+            .await
+            .unwrap();
+        // b1 (seq 1) was finalized and compacted out of the WAL, so it
+        // doesn't survive a restart; b2 (seq 2) wasn't finalized and does.
+        assert!(reopened.get_batch("r", "b1").await.is_none());
+        assert!(reopened.get_batch("r", "b2").await.is_some());
+        assert_eq!(reopened.watermark_for("r").await, Some(1));
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[tokio::test]
+    async fn test_fsync_batching_does_not_lose_records() {
+        let dir = temp_dir("batching");
+        let mut config = WalConfig::new(&dir);
+        config.fsync_batch_size = 4;
+
+        {
+            let store = WalIdempotencyStore::open(config).await.unwrap();
+            for i in 0..10 {
+                store
+                    .check_and_record("p", "r", &format!("b{i}"), i, &format!("h{i}"), 1, 0, 0)
+                    .await
+                    .unwrap();
+            }
+        }
+
+        let store = WalIdempotencyStore::open(WalConfig::new(&dir))
+            .await
+            .unwrap();
+        for i in 0..10 {
+            assert!(store.get_batch("r", &format!("b{i}")).await.is_some());
+        }
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
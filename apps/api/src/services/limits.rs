@@ -8,11 +8,192 @@
 //! Prevents high-cardinality data from overwhelming ClickHouse.
 
 use std::collections::{HashMap, HashSet};
+use std::fmt::Write as _;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 
 use tokio::sync::RwLock;
 use tracing::{debug, warn};
 
+use super::cardinality_store::{
+    CardinalityStore, MemoryCardinalityStore, PersistedTagPairs, SqliteCardinalityStore,
+};
+use super::hyperloglog::HyperLogLog;
+
+/// Why an item was dropped by [`CardinalityTracker::validate_batch`]:
+/// selects which of [`GuardrailMetrics`]'s drop counters is incremented,
+/// and is the `reason` label those counters are rendered with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DropReason {
+    /// The key/value/name exceeded its configured max length.
+    Length,
+    /// The run has reached `max_tag_keys_per_run` / `max_metric_names_per_run`.
+    RunKeyLimit,
+    /// The project has reached `max_tags_per_project`. Tag pairs only -
+    /// there is no project-level limit on metric names.
+    ProjectLimit,
+}
+
+impl DropReason {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Length => "length",
+            Self::RunKeyLimit => "run_key_limit",
+            Self::ProjectLimit => "project_limit",
+        }
+    }
+}
+
+/// Accept/drop counters for the cardinality guardrail, rendered in
+/// Prometheus text exposition format by
+/// [`CardinalityTracker::render_metrics`]. Mirrors the style of
+/// [`crate::metrics::Metrics`], scoped to this one subsystem so it stays
+/// self-contained and easy to unit test in isolation.
+#[derive(Debug, Default)]
+struct GuardrailMetrics {
+    tags_accepted_total: AtomicU64,
+    tags_dropped_length_total: AtomicU64,
+    tags_dropped_run_key_limit_total: AtomicU64,
+    tags_dropped_project_limit_total: AtomicU64,
+    metric_names_accepted_total: AtomicU64,
+    metric_names_dropped_length_total: AtomicU64,
+    metric_names_dropped_run_key_limit_total: AtomicU64,
+}
+
+impl GuardrailMetrics {
+    fn record_tag_accepted(&self) {
+        self.tags_accepted_total.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn record_tag_dropped(&self, reason: DropReason) {
+        let counter = match reason {
+            DropReason::Length => &self.tags_dropped_length_total,
+            DropReason::RunKeyLimit => &self.tags_dropped_run_key_limit_total,
+            DropReason::ProjectLimit => &self.tags_dropped_project_limit_total,
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn record_metric_name_accepted(&self) {
+        self.metric_names_accepted_total
+            .fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn record_metric_name_dropped(&self, reason: DropReason) {
+        let counter = match reason {
+            DropReason::Length => &self.metric_names_dropped_length_total,
+            DropReason::RunKeyLimit => &self.metric_names_dropped_run_key_limit_total,
+            DropReason::ProjectLimit => {
+                unreachable!("metric names have no project-level limit")
+            }
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn snapshot(&self) -> GuardrailMetricsSnapshot {
+        GuardrailMetricsSnapshot {
+            tags_accepted_total: self.tags_accepted_total.load(Ordering::Relaxed),
+            tags_dropped_length_total: self.tags_dropped_length_total.load(Ordering::Relaxed),
+            tags_dropped_run_key_limit_total: self
+                .tags_dropped_run_key_limit_total
+                .load(Ordering::Relaxed),
+            tags_dropped_project_limit_total: self
+                .tags_dropped_project_limit_total
+                .load(Ordering::Relaxed),
+            metric_names_accepted_total: self.metric_names_accepted_total.load(Ordering::Relaxed),
+            metric_names_dropped_length_total: self
+                .metric_names_dropped_length_total
+                .load(Ordering::Relaxed),
+            metric_names_dropped_run_key_limit_total: self
+                .metric_names_dropped_run_key_limit_total
+                .load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// A point-in-time read of [`GuardrailMetrics`]'s counters, for callers
+/// (e.g. `xtask bench`) that want the numbers rather than a Prometheus
+/// text blob.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GuardrailMetricsSnapshot {
+    pub tags_accepted_total: u64,
+    pub tags_dropped_length_total: u64,
+    pub tags_dropped_run_key_limit_total: u64,
+    pub tags_dropped_project_limit_total: u64,
+    pub metric_names_accepted_total: u64,
+    pub metric_names_dropped_length_total: u64,
+    pub metric_names_dropped_run_key_limit_total: u64,
+}
+
+/// Strategy for tracking a project's distinct `(tag_key, tag_value)` pairs.
+///
+/// `Exact` keeps every pair in a `HashSet` forever, which is precise but
+/// unbounded: a project with millions of distinct pairs makes the
+/// guardrail itself the memory problem it's meant to prevent. `HyperLogLog`
+/// swaps the exact set for a fixed-size sketch once it crosses
+/// `max_tags_per_project`, trading exact counts for a ~0.8% error bound at
+/// constant memory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TagCardinalityEstimator {
+    Exact,
+    HyperLogLog,
+}
+
+impl TagCardinalityEstimator {
+    fn from_str(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "exact" => Some(Self::Exact),
+            "hyperloglog" | "hll" => Some(Self::HyperLogLog),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Exact => "exact",
+            Self::HyperLogLog => "hyperloglog",
+        }
+    }
+}
+
+/// Which backend persists `CardinalityTracker`'s per-project tag-pair state
+/// (see `cardinality_store::CardinalityStore`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CardinalityStoreBackend {
+    /// In-memory: state vanishes on restart (default, matches the
+    /// tracker's historical behavior).
+    Memory,
+    /// Durable embedded-SQLite store at `MLRUN_CARDINALITY_STORE_PATH`.
+    Sqlite,
+}
+
+impl Default for CardinalityStoreBackend {
+    fn default() -> Self {
+        Self::Memory
+    }
+}
+
+impl CardinalityStoreBackend {
+    /// Parse from the `MLRUN_CARDINALITY_STORE` environment variable.
+    pub fn from_env() -> Self {
+        std::env::var("MLRUN_CARDINALITY_STORE")
+            .ok()
+            .and_then(|s| match s.to_lowercase().as_str() {
+                "memory" => Some(Self::Memory),
+                "sqlite" => Some(Self::Sqlite),
+                _ => None,
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Memory => "memory",
+            Self::Sqlite => "sqlite",
+        }
+    }
+}
+
 /// Configuration for cardinality limits.
 #[derive(Debug, Clone)]
 pub struct LimitsConfig {
@@ -28,6 +209,14 @@ pub struct LimitsConfig {
     pub max_tag_value_length: usize,
     /// Maximum metric name length
     pub max_metric_name_length: usize,
+    /// How the project-level tag guardrail tracks distinct pairs once it
+    /// grows past `max_tags_per_project`. Defaults to `Exact`, matching the
+    /// historical (unbounded) behavior.
+    pub project_tag_estimator: TagCardinalityEstimator,
+    /// Which backend persists tracker state across restarts.
+    pub store_backend: CardinalityStoreBackend,
+    /// SQLite database path, used when `store_backend` is `Sqlite`.
+    pub store_path: String,
 }
 
 impl Default for LimitsConfig {
@@ -39,6 +228,9 @@ impl Default for LimitsConfig {
             max_tag_key_length: 256,
             max_tag_value_length: 1024,
             max_metric_name_length: 256,
+            project_tag_estimator: TagCardinalityEstimator::Exact,
+            store_backend: CardinalityStoreBackend::Memory,
+            store_path: "./data/cardinality.sqlite".to_string(),
         }
     }
 }
@@ -66,6 +258,18 @@ impl LimitsConfig {
             }
         }
 
+        if let Ok(val) = std::env::var("MLRUN_PROJECT_TAG_ESTIMATOR") {
+            if let Some(estimator) = TagCardinalityEstimator::from_str(&val) {
+                config.project_tag_estimator = estimator;
+            }
+        }
+
+        config.store_backend = CardinalityStoreBackend::from_env();
+
+        if let Ok(val) = std::env::var("MLRUN_CARDINALITY_STORE_PATH") {
+            config.store_path = val;
+        }
+
         config
     }
 }
@@ -120,21 +324,133 @@ struct RunCardinality {
     metric_names: HashSet<String>,
 }
 
+/// A project's tracked `(tag_key, tag_value)` pairs: an exact set, or — once
+/// it has grown past `max_tags_per_project` under the `HyperLogLog`
+/// estimator - a fixed-size sketch.
+#[derive(Debug)]
+enum TagPairs {
+    Exact(HashSet<(String, String)>),
+    Sketch(HyperLogLog),
+}
+
+impl Default for TagPairs {
+    fn default() -> Self {
+        Self::Exact(HashSet::new())
+    }
+}
+
+impl TagPairs {
+    /// Current (or estimated) number of distinct pairs tracked.
+    fn len(&self) -> usize {
+        match self {
+            Self::Exact(set) => set.len(),
+            Self::Sketch(hll) => hll.estimate().round() as usize,
+        }
+    }
+
+    /// Whether `pair` has not been recorded yet. A sketch cannot answer
+    /// exact membership queries, so once switched over every pair is
+    /// treated as potentially new.
+    fn is_new(&self, pair: &(String, String)) -> bool {
+        match self {
+            Self::Exact(set) => !set.contains(pair),
+            Self::Sketch(_) => true,
+        }
+    }
+
+    /// Whether `max_tags_per_project` should block a new pair. The exact
+    /// set enforces this precisely; once switched over to the sketch,
+    /// membership is no longer known exactly, so every pair is treated as
+    /// potentially new (see [`Self::is_new`]) and the check instead
+    /// compares the sketch's estimate against the limit. That estimate
+    /// carries HyperLogLog's ~0.8% standard error, so the guardrail stays
+    /// approximate rather than exact past switchover - but it keeps
+    /// enforcing, rather than switching off right when a project is big
+    /// enough to need it.
+    fn over_limit(&self, pair_is_new: bool, max_tags_per_project: usize) -> bool {
+        match self {
+            Self::Exact(set) => pair_is_new && set.len() >= max_tags_per_project,
+            Self::Sketch(hll) => pair_is_new && hll.estimate().round() as usize >= max_tags_per_project,
+        }
+    }
+
+    /// Switch from an exact set to a HyperLogLog sketch once the set has
+    /// grown past `switchover` (only when `estimator` allows it — `Exact`
+    /// mode never switches over).
+    ///
+    /// Must run *before* [`Self::len`] is consulted to decide whether a new
+    /// pair should be accepted: the accept/reject check in
+    /// `validate_batch` rejects a new pair as soon as `len() >=
+    /// max_tags_per_project`, which is the same threshold that would
+    /// trigger this conversion — so if the switchover only happened inside
+    /// [`Self::insert`] (the accept path), it would never run: every pair
+    /// that reaches the threshold is rejected first.
+    fn maybe_switchover(&mut self, estimator: TagCardinalityEstimator, switchover: usize) {
+        if let Self::Exact(set) = self {
+            if estimator == TagCardinalityEstimator::HyperLogLog && set.len() >= switchover {
+                let mut sketch = HyperLogLog::new();
+                for existing in set.iter() {
+                    sketch.insert(existing);
+                }
+                *self = Self::Sketch(sketch);
+            }
+        }
+    }
+
+    /// Record `pair`. Call [`Self::maybe_switchover`] first so the set has
+    /// already converted to a sketch if it's past the threshold.
+    fn insert(&mut self, pair: (String, String)) {
+        match self {
+            Self::Exact(set) => {
+                set.insert(pair);
+            }
+            Self::Sketch(sketch) => sketch.insert(&pair),
+        }
+    }
+
+    /// Convert to the serializable form persisted by a [`CardinalityStore`].
+    fn to_persisted(&self) -> PersistedTagPairs {
+        match self {
+            Self::Exact(set) => PersistedTagPairs::Exact(set.iter().cloned().collect()),
+            Self::Sketch(hll) => PersistedTagPairs::Sketch(hll.registers().to_vec()),
+        }
+    }
+
+    /// Rebuild from a store's previously-persisted state.
+    fn from_persisted(persisted: PersistedTagPairs) -> Self {
+        match persisted {
+            PersistedTagPairs::Exact(pairs) => Self::Exact(pairs.into_iter().collect()),
+            PersistedTagPairs::Sketch(registers) => {
+                Self::Sketch(HyperLogLog::from_registers(registers))
+            }
+        }
+    }
+}
+
 /// Per-project cardinality tracking.
 #[derive(Debug, Default)]
 struct ProjectCardinality {
-    /// Total unique (tag_key, tag_value) pairs for this project
-    tag_pairs: HashSet<(String, String)>,
+    /// Total unique (tag_key, tag_value) pairs for this project, exact or
+    /// estimated — see [`TagPairs`].
+    tag_pairs: TagPairs,
 }
 
 /// In-memory cardinality tracker for enforcing limits.
 #[derive(Debug)]
 pub struct CardinalityTracker {
-    config: LimitsConfig,
+    /// Behind a lock rather than a plain field so [`Self::update_config`]
+    /// can hot-swap limits at runtime (see the admin `PUT /admin/limits`
+    /// route in `main.rs`) without restarting the tracker.
+    config: RwLock<LimitsConfig>,
     /// Per-run tracking
     runs: RwLock<HashMap<String, RunCardinality>>,
     /// Per-project tracking
     projects: RwLock<HashMap<String, ProjectCardinality>>,
+    /// Accept/drop counters, rendered by [`Self::render_metrics`].
+    metrics: GuardrailMetrics,
+    /// Persists project tag-pair state across restarts (see
+    /// `cardinality_store`). Defaults to an in-memory no-op.
+    store: Arc<dyn CardinalityStore>,
 }
 
 impl Default for CardinalityTracker {
@@ -144,23 +460,67 @@ impl Default for CardinalityTracker {
 }
 
 impl CardinalityTracker {
-    /// Create a new cardinality tracker with the given config.
+    /// Create a new cardinality tracker with the given config and an
+    /// in-memory (non-durable) store. Use [`Self::connect`] to honor
+    /// `config.store_backend` instead.
     pub fn new(config: LimitsConfig) -> Self {
+        Self::with_store(config, Arc::new(MemoryCardinalityStore))
+    }
+
+    /// Create a tracker backed by an explicit store, e.g. in tests that
+    /// want to assert on persisted state.
+    pub fn with_store(config: LimitsConfig, store: Arc<dyn CardinalityStore>) -> Self {
         Self {
-            config,
+            config: RwLock::new(config),
             runs: RwLock::new(HashMap::new()),
             projects: RwLock::new(HashMap::new()),
+            metrics: GuardrailMetrics::default(),
+            store,
         }
     }
 
-    /// Create a tracker from environment configuration.
+    /// Create a tracker from environment configuration, with an in-memory
+    /// (non-durable) store regardless of `MLRUN_CARDINALITY_STORE`. Use
+    /// [`Self::connect`] at startup to actually honor that setting.
     pub fn from_env() -> Self {
         Self::new(LimitsConfig::from_env())
     }
 
-    /// Get the limits configuration.
-    pub fn config(&self) -> &LimitsConfig {
-        &self.config
+    /// Create a tracker honoring `config.store_backend`. If `Sqlite` is
+    /// requested but the database can't be opened, falls back to an
+    /// in-memory store rather than failing to start (mirrors the
+    /// Postgres run-store/metrics-repo fallback in `main.rs`).
+    pub async fn connect(config: LimitsConfig) -> Self {
+        let store: Arc<dyn CardinalityStore> = match config.store_backend {
+            CardinalityStoreBackend::Sqlite => {
+                match SqliteCardinalityStore::connect(&config.store_path).await {
+                    Ok(store) => Arc::new(store),
+                    Err(e) => {
+                        warn!(
+                            "Failed to open SQLite cardinality store at {}, falling back to \
+                             in-memory: {}",
+                            config.store_path, e
+                        );
+                        Arc::new(MemoryCardinalityStore)
+                    }
+                }
+            }
+            CardinalityStoreBackend::Memory => Arc::new(MemoryCardinalityStore),
+        };
+        Self::with_store(config, store)
+    }
+
+    /// Get a snapshot of the current limits configuration.
+    pub async fn config(&self) -> LimitsConfig {
+        self.config.read().await.clone()
+    }
+
+    /// Hot-swap the limits configuration at runtime (e.g. from the admin
+    /// `PUT /admin/limits` route). Takes effect on the next
+    /// [`Self::validate_batch`] call; in-flight calls finish against
+    /// whatever config they already read.
+    pub async fn update_config(&self, config: LimitsConfig) {
+        *self.config.write().await = config;
     }
 
     /// Validate and filter a batch of tags and metrics.
@@ -175,95 +535,134 @@ impl CardinalityTracker {
     ) -> ValidationResult {
         let mut result = ValidationResult::default();
 
+        // Snapshot the config once so the whole batch is validated against
+        // one consistent set of limits, even if `update_config` runs
+        // concurrently.
+        let config = self.config.read().await.clone();
+
         // Get or create run tracking
         let mut runs = self.runs.write().await;
         let run = runs.entry(run_id.to_string()).or_default();
 
-        // Get or create project tracking
+        // Get or create project tracking, hydrating from the persistent
+        // store on first touch (see `cardinality_store`).
         let mut projects = self.projects.write().await;
-        let project = projects.entry(project_id.to_string()).or_default();
+        if !projects.contains_key(project_id) {
+            let hydrated = match self.store.load_project(project_id).await {
+                Some(persisted) => ProjectCardinality {
+                    tag_pairs: TagPairs::from_persisted(persisted),
+                },
+                None => ProjectCardinality::default(),
+            };
+            projects.insert(project_id.to_string(), hydrated);
+        }
+        let project = projects.get_mut(project_id).unwrap();
 
         // Validate tags
         for (key, value) in tags {
             // Check tag key length
-            if key.len() > self.config.max_tag_key_length {
+            if key.len() > config.max_tag_key_length {
                 result.warnings.push(format!(
                     "Tag key '{}...' exceeds max length {}",
-                    &key[..32.min(key.len())],
-                    self.config.max_tag_key_length
+                    truncate_for_display(key, 32),
+                    config.max_tag_key_length
                 ));
                 result.dropped_tags.push((key.clone(), value.clone()));
+                self.metrics.record_tag_dropped(DropReason::Length);
                 continue;
             }
 
             // Check tag value length
-            if value.len() > self.config.max_tag_value_length {
+            if value.len() > config.max_tag_value_length {
                 result.warnings.push(format!(
                     "Tag value for '{}' exceeds max length {}",
-                    key, self.config.max_tag_value_length
+                    key, config.max_tag_value_length
                 ));
                 result.dropped_tags.push((key.clone(), value.clone()));
+                self.metrics.record_tag_dropped(DropReason::Length);
                 continue;
             }
 
             // Check run tag key limit (only for new keys)
             if !run.tag_keys.contains(key) {
-                if run.tag_keys.len() >= self.config.max_tag_keys_per_run {
+                if run.tag_keys.len() >= config.max_tag_keys_per_run {
                     if result.dropped_tags.is_empty() {
                         result.warnings.push(format!(
                             "Run {} has reached max tag keys ({})",
-                            run_id, self.config.max_tag_keys_per_run
+                            run_id, config.max_tag_keys_per_run
                         ));
                     }
                     result.dropped_tags.push((key.clone(), value.clone()));
+                    self.metrics.record_tag_dropped(DropReason::RunKeyLimit);
                     continue;
                 }
             }
 
-            // Check project tag limit (only for new pairs)
+            // Check project tag limit (only for new pairs). Switch over to
+            // the sketch (if configured) before consulting `len()` below -
+            // otherwise a pair that reaches the threshold is always
+            // rejected before the conversion inside `insert()` could run.
+            project
+                .tag_pairs
+                .maybe_switchover(config.project_tag_estimator, config.max_tags_per_project);
             let pair = (key.clone(), value.clone());
-            if !project.tag_pairs.contains(&pair) {
-                if project.tag_pairs.len() >= self.config.max_tags_per_project {
-                    if result.dropped_tags.is_empty() {
-                        result.warnings.push(format!(
-                            "Project {} has reached max tags ({})",
-                            project_id, self.config.max_tags_per_project
-                        ));
-                    }
-                    result.dropped_tags.push((key.clone(), value.clone()));
-                    continue;
+            let pair_is_new = project.tag_pairs.is_new(&pair);
+            if project
+                .tag_pairs
+                .over_limit(pair_is_new, config.max_tags_per_project)
+            {
+                if result.dropped_tags.is_empty() {
+                    result.warnings.push(format!(
+                        "Project {} has reached max tags ({})",
+                        project_id, config.max_tags_per_project
+                    ));
                 }
+                result.dropped_tags.push((key.clone(), value.clone()));
+                self.metrics.record_tag_dropped(DropReason::ProjectLimit);
+                continue;
             }
 
             // Accept the tag
             run.tag_keys.insert(key.clone());
             project.tag_pairs.insert(pair.clone());
             result.accepted_tags.push((key.clone(), value.clone()));
+            self.metrics.record_tag_accepted();
+
+            // Persist only genuinely new pairs - re-persisting on every
+            // repeat tag would mean a DB write per ingested batch instead
+            // of per distinct pair.
+            if pair_is_new {
+                let persisted = project.tag_pairs.to_persisted();
+                self.store.persist_project(project_id, &persisted).await;
+            }
         }
 
         // Validate metrics
         for name in metric_names {
             // Check metric name length
-            if name.len() > self.config.max_metric_name_length {
+            if name.len() > config.max_metric_name_length {
                 result.warnings.push(format!(
                     "Metric name '{}...' exceeds max length {}",
-                    &name[..32.min(name.len())],
-                    self.config.max_metric_name_length
+                    truncate_for_display(name, 32),
+                    config.max_metric_name_length
                 ));
                 result.dropped_metrics.push(name.clone());
+                self.metrics.record_metric_name_dropped(DropReason::Length);
                 continue;
             }
 
             // Check run metric name limit (only for new names)
             if !run.metric_names.contains(name) {
-                if run.metric_names.len() >= self.config.max_metric_names_per_run {
+                if run.metric_names.len() >= config.max_metric_names_per_run {
                     if result.dropped_metrics.is_empty() {
                         result.warnings.push(format!(
                             "Run {} has reached max metric names ({})",
-                            run_id, self.config.max_metric_names_per_run
+                            run_id, config.max_metric_names_per_run
                         ));
                     }
                     result.dropped_metrics.push(name.clone());
+                    self.metrics
+                        .record_metric_name_dropped(DropReason::RunKeyLimit);
                     continue;
                 }
             }
@@ -271,6 +670,7 @@ impl CardinalityTracker {
             // Accept the metric
             run.metric_names.insert(name.clone());
             result.accepted_metrics.push(name.clone());
+            self.metrics.record_metric_name_accepted();
         }
 
         // Log if anything was dropped
@@ -303,6 +703,11 @@ impl CardinalityTracker {
     }
 
     /// Get current cardinality stats for a project.
+    ///
+    /// Exact while the project's tag pairs are still tracked in a `HashSet`;
+    /// once `project_tag_estimator` is `HyperLogLog` and the project has
+    /// grown past `max_tags_per_project`, this returns a HyperLogLog
+    /// estimate instead (see [`TagPairs`]).
     pub async fn get_project_stats(&self, project_id: &str) -> Option<usize> {
         let projects = self.projects.read().await;
         projects.get(project_id).map(|p| p.tag_pairs.len())
@@ -312,6 +717,7 @@ impl CardinalityTracker {
     pub async fn clear_run(&self, run_id: &str) {
         let mut runs = self.runs.write().await;
         runs.remove(run_id);
+        self.store.remove_run(run_id).await;
     }
 
     /// Clear all tracking (useful for testing).
@@ -322,6 +728,159 @@ impl CardinalityTracker {
         let mut projects = self.projects.write().await;
         projects.clear();
     }
+
+    /// Point-in-time accept/drop counters, for callers that want structured
+    /// numbers rather than the Prometheus text rendered by
+    /// [`Self::render_metrics`] (e.g. `xtask bench`'s JSON report).
+    pub fn metrics_snapshot(&self) -> GuardrailMetricsSnapshot {
+        self.metrics.snapshot()
+    }
+
+    /// Render accept/drop counters and current per-run/per-project gauges
+    /// in Prometheus text exposition format, so operators can alert on drop
+    /// rate or an approaching-limit project/run before data loss becomes
+    /// silent.
+    pub async fn render_metrics(&self) -> String {
+        let mut out = String::new();
+
+        let _ = writeln!(
+            out,
+            "# HELP mlrun_ingest_tags_accepted_total Total tag pairs accepted by the cardinality guardrail."
+        );
+        let _ = writeln!(out, "# TYPE mlrun_ingest_tags_accepted_total counter");
+        let _ = writeln!(
+            out,
+            "mlrun_ingest_tags_accepted_total {}",
+            self.metrics.tags_accepted_total.load(Ordering::Relaxed)
+        );
+
+        let _ = writeln!(
+            out,
+            "# HELP mlrun_ingest_tags_dropped_total Total tag pairs dropped by the cardinality guardrail, by reason."
+        );
+        let _ = writeln!(out, "# TYPE mlrun_ingest_tags_dropped_total counter");
+        let _ = writeln!(
+            out,
+            "mlrun_ingest_tags_dropped_total{{reason=\"{}\"}} {}",
+            DropReason::Length.as_str(),
+            self.metrics
+                .tags_dropped_length_total
+                .load(Ordering::Relaxed)
+        );
+        let _ = writeln!(
+            out,
+            "mlrun_ingest_tags_dropped_total{{reason=\"{}\"}} {}",
+            DropReason::RunKeyLimit.as_str(),
+            self.metrics
+                .tags_dropped_run_key_limit_total
+                .load(Ordering::Relaxed)
+        );
+        let _ = writeln!(
+            out,
+            "mlrun_ingest_tags_dropped_total{{reason=\"{}\"}} {}",
+            DropReason::ProjectLimit.as_str(),
+            self.metrics
+                .tags_dropped_project_limit_total
+                .load(Ordering::Relaxed)
+        );
+
+        let _ = writeln!(
+            out,
+            "# HELP mlrun_ingest_metric_names_accepted_total Total metric names accepted by the cardinality guardrail."
+        );
+        let _ = writeln!(
+            out,
+            "# TYPE mlrun_ingest_metric_names_accepted_total counter"
+        );
+        let _ = writeln!(
+            out,
+            "mlrun_ingest_metric_names_accepted_total {}",
+            self.metrics
+                .metric_names_accepted_total
+                .load(Ordering::Relaxed)
+        );
+
+        let _ = writeln!(
+            out,
+            "# HELP mlrun_ingest_metric_names_dropped_total Total metric names dropped by the cardinality guardrail, by reason."
+        );
+        let _ = writeln!(
+            out,
+            "# TYPE mlrun_ingest_metric_names_dropped_total counter"
+        );
+        let _ = writeln!(
+            out,
+            "mlrun_ingest_metric_names_dropped_total{{reason=\"{}\"}} {}",
+            DropReason::Length.as_str(),
+            self.metrics
+                .metric_names_dropped_length_total
+                .load(Ordering::Relaxed)
+        );
+        let _ = writeln!(
+            out,
+            "mlrun_ingest_metric_names_dropped_total{{reason=\"{}\"}} {}",
+            DropReason::RunKeyLimit.as_str(),
+            self.metrics
+                .metric_names_dropped_run_key_limit_total
+                .load(Ordering::Relaxed)
+        );
+
+        let _ = writeln!(
+            out,
+            "# HELP mlrun_ingest_project_tag_pairs Current (or HyperLogLog-estimated) distinct tag pairs tracked per project."
+        );
+        let _ = writeln!(out, "# TYPE mlrun_ingest_project_tag_pairs gauge");
+        for (project_id, project) in self.projects.read().await.iter() {
+            let _ = writeln!(
+                out,
+                "mlrun_ingest_project_tag_pairs{{project=\"{project_id}\"}} {}",
+                project.tag_pairs.len()
+            );
+        }
+
+        let _ = writeln!(
+            out,
+            "# HELP mlrun_ingest_run_tag_keys Current distinct tag keys tracked per run."
+        );
+        let _ = writeln!(out, "# TYPE mlrun_ingest_run_tag_keys gauge");
+        let _ = writeln!(
+            out,
+            "# HELP mlrun_ingest_run_metric_names Current distinct metric names tracked per run."
+        );
+        let _ = writeln!(out, "# TYPE mlrun_ingest_run_metric_names gauge");
+        for (run_id, run) in self.runs.read().await.iter() {
+            let _ = writeln!(
+                out,
+                "mlrun_ingest_run_tag_keys{{run=\"{run_id}\"}} {}",
+                run.tag_keys.len()
+            );
+            let _ = writeln!(
+                out,
+                "mlrun_ingest_run_metric_names{{run=\"{run_id}\"}} {}",
+                run.metric_names.len()
+            );
+        }
+
+        out
+    }
+}
+
+/// Truncate `s` to at most `max_bytes` bytes, rounding down to the nearest
+/// char boundary.
+///
+/// Tag keys/values and metric names are arbitrary user input and may
+/// contain multi-byte UTF-8 characters; a plain `&s[..max_bytes]` panics
+/// whenever `max_bytes` falls inside one (found by the `validate_batch`
+/// fuzz target - see `fuzz/fuzz_targets/cardinality_tracker.rs`).
+fn truncate_for_display(s: &str, max_bytes: usize) -> &str {
+    if s.len() <= max_bytes {
+        return s;
+    }
+    let mut end = max_bytes;
+    while !s.is_char_boundary(end) {
+        end -= 1;
+    }
+    &s[..end]
 }
 
 /// Shared cardinality tracker type.
@@ -337,6 +896,7 @@ mod tests {
         assert_eq!(config.max_tag_keys_per_run, 100);
         assert_eq!(config.max_metric_names_per_run, 1000);
         assert_eq!(config.max_tags_per_project, 10000);
+        assert_eq!(config.project_tag_estimator, TagCardinalityEstimator::Exact);
     }
 
     #[tokio::test]
@@ -399,12 +959,10 @@ mod tests {
         assert_eq!(result.accepted_metrics.len(), 2);
         assert_eq!(result.dropped_metrics.len(), 1);
         assert!(result.has_drops());
-        assert!(
-            result
-                .warnings
-                .iter()
-                .any(|w| w.contains("max metric names"))
-        );
+        assert!(result
+            .warnings
+            .iter()
+            .any(|w| w.contains("max metric names")));
     }
 
     #[tokio::test]
@@ -430,6 +988,86 @@ mod tests {
         assert!(result.warnings.iter().any(|w| w.contains("max tags")));
     }
 
+    #[tokio::test]
+    async fn test_hyperloglog_estimator_switches_over_past_threshold() {
+        let config = LimitsConfig {
+            max_tags_per_project: 50,
+            project_tag_estimator: TagCardinalityEstimator::HyperLogLog,
+            ..Default::default()
+        };
+        let tracker = CardinalityTracker::new(config);
+
+        let mut total_dropped = 0;
+        for i in 0..200 {
+            let tags = vec![(format!("key-{i}"), format!("value-{i}"))];
+            let result = tracker.validate_batch("proj", "run", &tags, &[]).await;
+            total_dropped += result.dropped_tags.len();
+        }
+
+        let estimate = tracker.get_project_stats("proj").await.unwrap();
+        // Once switched over, enforcement stays on but becomes approximate:
+        // the estimate should stay close to `max_tags_per_project` (allowing
+        // HyperLogLog's ~0.8% error plus the handful of pairs inserted
+        // before the switchover triggered), not grow unbounded to the true
+        // cardinality of 200 like it would if the limit stopped applying.
+        assert!(
+            (40..70).contains(&estimate),
+            "estimate {estimate} suggests the project limit stopped being enforced"
+        );
+        assert!(
+            total_dropped > 0,
+            "expected some pairs to be dropped past the project limit"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_exact_estimator_never_switches_over() {
+        let config = LimitsConfig {
+            max_tags_per_project: 10_000,
+            project_tag_estimator: TagCardinalityEstimator::Exact,
+            ..Default::default()
+        };
+        let tracker = CardinalityTracker::new(config);
+
+        for i in 0..50 {
+            let tags = vec![(format!("key-{i}"), format!("value-{i}"))];
+            tracker.validate_batch("proj", "run", &tags, &[]).await;
+        }
+
+        // Exact mode keeps an exact count no matter how the project grows.
+        assert_eq!(tracker.get_project_stats("proj").await.unwrap(), 50);
+    }
+
+    #[test]
+    fn test_project_tag_estimator_from_str() {
+        assert_eq!(
+            TagCardinalityEstimator::from_str("hyperloglog"),
+            Some(TagCardinalityEstimator::HyperLogLog)
+        );
+        assert_eq!(
+            TagCardinalityEstimator::from_str("HLL"),
+            Some(TagCardinalityEstimator::HyperLogLog)
+        );
+        assert_eq!(
+            TagCardinalityEstimator::from_str("exact"),
+            Some(TagCardinalityEstimator::Exact)
+        );
+        assert_eq!(TagCardinalityEstimator::from_str("bogus"), None);
+    }
+
+    #[test]
+    fn test_project_tag_estimator_as_str_roundtrip() {
+        for estimator in [
+            TagCardinalityEstimator::Exact,
+            TagCardinalityEstimator::HyperLogLog,
+        ] {
+            assert_eq!(
+                TagCardinalityEstimator::from_str(estimator.as_str()),
+                Some(estimator)
+            );
+        }
+    }
+
     #[tokio::test]
     async fn test_duplicate_tags_not_counted() {
         let config = LimitsConfig {
@@ -474,12 +1112,34 @@ mod tests {
 
         assert_eq!(result.accepted_tags.len(), 1);
         assert_eq!(result.dropped_tags.len(), 1);
-        assert!(
-            result
-                .warnings
-                .iter()
-                .any(|w| w.contains("exceeds max length"))
-        );
+        assert!(result
+            .warnings
+            .iter()
+            .any(|w| w.contains("exceeds max length")));
+    }
+
+    #[tokio::test]
+    async fn test_oversized_multibyte_key_does_not_panic() {
+        // A 33-byte key made of 3-byte UTF-8 characters: the naive
+        // `&key[..32]` used to panic here because byte index 32 falls in
+        // the middle of the 11th character.
+        let config = LimitsConfig {
+            max_tag_key_length: 5,
+            ..Default::default()
+        };
+        let tracker = CardinalityTracker::new(config);
+
+        let key = "あ".repeat(11);
+        assert_eq!(key.len(), 33);
+        let tags = vec![(key, "val".to_string())];
+
+        let result = tracker.validate_batch("proj", "run", &tags, &[]).await;
+
+        assert_eq!(result.dropped_tags.len(), 1);
+        assert!(result
+            .warnings
+            .iter()
+            .any(|w| w.contains("exceeds max length")));
     }
 
     #[tokio::test]
@@ -516,6 +1176,38 @@ mod tests {
         assert!(tracker.get_run_stats("run").await.is_none());
     }
 
+    #[tokio::test]
+    async fn test_render_metrics_reflects_accepts_and_drops_by_reason() {
+        let config = LimitsConfig {
+            max_tag_keys_per_run: 1,
+            max_metric_names_per_run: 1,
+            max_tag_key_length: 5,
+            ..Default::default()
+        };
+        let tracker = CardinalityTracker::new(config);
+
+        let tags = vec![
+            ("ok".to_string(), "1".to_string()),      // accepted
+            ("toolong".to_string(), "v".to_string()), // dropped: length
+            ("other".to_string(), "v".to_string()),   // dropped: run_key_limit
+        ];
+        let metrics = vec!["loss".to_string(), "acc".to_string()]; // 1 accepted, 1 dropped: run_key_limit
+
+        tracker.validate_batch("proj", "run", &tags, &metrics).await;
+
+        let rendered = tracker.render_metrics().await;
+        assert!(rendered.contains("mlrun_ingest_tags_accepted_total 1"));
+        assert!(rendered.contains("mlrun_ingest_tags_dropped_total{reason=\"length\"} 1"));
+        assert!(rendered.contains("mlrun_ingest_tags_dropped_total{reason=\"run_key_limit\"} 1"));
+        assert!(rendered.contains("mlrun_ingest_tags_dropped_total{reason=\"project_limit\"} 0"));
+        assert!(rendered.contains("mlrun_ingest_metric_names_accepted_total 1"));
+        assert!(rendered
+            .contains("mlrun_ingest_metric_names_dropped_total{reason=\"run_key_limit\"} 1"));
+        assert!(rendered.contains("mlrun_ingest_project_tag_pairs{project=\"proj\"} 1"));
+        assert!(rendered.contains("mlrun_ingest_run_tag_keys{run=\"run\"} 1"));
+        assert!(rendered.contains("mlrun_ingest_run_metric_names{run=\"run\"} 1"));
+    }
+
     #[test]
     fn test_validation_result_summary() {
         let mut result = ValidationResult::default();
@@ -528,4 +1220,82 @@ mod tests {
         assert!(warning.contains("1 tags dropped"));
         assert!(warning.contains("1 metrics dropped"));
     }
+
+    #[test]
+    fn test_cardinality_store_backend_from_env_roundtrip() {
+        for backend in [
+            CardinalityStoreBackend::Memory,
+            CardinalityStoreBackend::Sqlite,
+        ] {
+            std::env::set_var("MLRUN_CARDINALITY_STORE", backend.as_str());
+            assert_eq!(CardinalityStoreBackend::from_env(), backend);
+            std::env::remove_var("MLRUN_CARDINALITY_STORE");
+        }
+    }
+
+    /// A test-only [`CardinalityStore`] that actually persists, so we can
+    /// assert on hydration and on which writes `validate_batch` issues.
+    #[derive(Debug, Default)]
+    struct RecordingStore {
+        projects: tokio::sync::Mutex<HashMap<String, PersistedTagPairs>>,
+        persist_calls: AtomicU64,
+    }
+
+    #[async_trait::async_trait]
+    impl CardinalityStore for RecordingStore {
+        async fn load_project(&self, project_id: &str) -> Option<PersistedTagPairs> {
+            self.projects.lock().await.get(project_id).cloned()
+        }
+
+        async fn persist_project(&self, project_id: &str, state: &PersistedTagPairs) {
+            self.persist_calls.fetch_add(1, Ordering::Relaxed);
+            self.projects
+                .lock()
+                .await
+                .insert(project_id.to_string(), state.clone());
+        }
+
+        async fn load_run(
+            &self,
+            _run_id: &str,
+        ) -> Option<crate::services::cardinality_store::PersistedRunCardinality> {
+            None
+        }
+
+        async fn remove_run(&self, _run_id: &str) {}
+    }
+
+    #[tokio::test]
+    async fn test_validate_batch_persists_only_genuinely_new_pairs() {
+        let store = Arc::new(RecordingStore::default());
+        let tracker = CardinalityTracker::with_store(LimitsConfig::default(), store.clone());
+
+        let tags = vec![("env".to_string(), "prod".to_string())];
+        tracker.validate_batch("proj", "run", &tags, &[]).await;
+        tracker.validate_batch("proj", "run", &tags, &[]).await; // repeat, not a new pair
+
+        assert_eq!(store.persist_calls.load(Ordering::Relaxed), 1);
+    }
+
+    #[tokio::test]
+    async fn test_validate_batch_hydrates_project_from_store() {
+        let store = Arc::new(RecordingStore::default());
+        store.projects.lock().await.insert(
+            "proj".to_string(),
+            PersistedTagPairs::Exact(vec![("pre".to_string(), "existing".to_string())]),
+        );
+        let config = LimitsConfig {
+            max_tags_per_project: 1,
+            ..Default::default()
+        };
+        let tracker = CardinalityTracker::with_store(config, store);
+
+        // The project already has 1 (hydrated) pair and the limit is 1, so
+        // a genuinely new pair must be dropped.
+        let tags = vec![("new".to_string(), "value".to_string())];
+        let result = tracker.validate_batch("proj", "run", &tags, &[]).await;
+
+        assert_eq!(result.dropped_tags.len(), 1);
+        assert_eq!(tracker.get_project_stats("proj").await.unwrap(), 1);
+    }
 }
@@ -42,6 +42,19 @@ pub struct MetricSeries {
     pub downsampled: bool,
 }
 
+/// Which algorithm reduces a series to `max_points` points.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DownsampleMethod {
+    /// Bucket aggregation (mean/min/max per bucket). Cheap, but flattens
+    /// spikes - a sharp loss increase can average out against its neighbors.
+    #[default]
+    Aggregate,
+    /// Largest-Triangle-Three-Buckets: keeps the actual raw point per
+    /// bucket that best preserves the series' visual shape.
+    Lttb,
+}
+
 /// Request for querying metrics.
 #[derive(Debug, Deserialize)]
 pub struct MetricsQueryRequest {
@@ -55,6 +68,9 @@ pub struct MetricsQueryRequest {
     pub start_step: Option<i64>,
     /// End step (inclusive)
     pub end_step: Option<i64>,
+    /// Downsampling algorithm to use once `total_points > max_points`
+    #[serde(default)]
+    pub method: DownsampleMethod,
 }
 
 fn default_max_points() -> usize {
@@ -135,6 +151,127 @@ pub fn downsample_points(points: &[MetricPoint], max_points: usize) -> Vec<Aggre
     result
 }
 
+/// Downsample `points` to at most `max_points`, using whichever `method`
+/// the caller asked for.
+pub fn downsample(
+    points: &[MetricPoint],
+    max_points: usize,
+    method: DownsampleMethod,
+) -> Vec<AggregatedPoint> {
+    match method {
+        DownsampleMethod::Aggregate => downsample_points(points, max_points),
+        DownsampleMethod::Lttb => lttb_downsample(points, max_points),
+    }
+}
+
+fn to_aggregated(point: &MetricPoint) -> AggregatedPoint {
+    AggregatedPoint {
+        step: point.step,
+        mean: point.value,
+        min: point.value,
+        max: point.value,
+        count: 1,
+    }
+}
+
+/// Bucket boundary `idx` (0..=bucket_count) into `points[1..points.len()-1]`,
+/// measured in absolute indices into `points`. `idx == bucket_count` yields
+/// the virtual trailing bucket containing only the series' last point,
+/// used as the "next bucket" centroid source when scoring the final real
+/// bucket.
+fn lttb_bucket_bounds(
+    idx: usize,
+    bucket_count: usize,
+    bucket_size: f64,
+    len: usize,
+) -> (usize, usize) {
+    if idx >= bucket_count {
+        return (len - 1, len);
+    }
+    let start = 1 + (idx as f64 * bucket_size).floor() as usize;
+    let end = (1 + ((idx + 1) as f64 * bucket_size).floor() as usize).min(len - 1);
+    (start, end.max(start))
+}
+
+/// Mean (step, value) of `points`, falling back to `(0.0, 0.0)` for an
+/// empty slice (an empty centroid bucket can only happen at the series'
+/// tail, where the triangle it feeds into is about to be emitted anyway).
+fn centroid(points: &[MetricPoint]) -> (f64, f64) {
+    if points.is_empty() {
+        return (0.0, 0.0);
+    }
+    let n = points.len() as f64;
+    let step_sum: f64 = points.iter().map(|p| p.step as f64).sum();
+    let value_sum: f64 = points.iter().map(|p| p.value).sum();
+    (step_sum / n, value_sum / n)
+}
+
+/// Area of the triangle formed by points `a` and `b` and centroid `(cx, cy)`.
+fn triangle_area(a: &MetricPoint, b: &MetricPoint, cx: f64, cy: f64) -> f64 {
+    let (ax, ay) = (a.step as f64, a.value);
+    let (bx, by) = (b.step as f64, b.value);
+    (0.5 * ((ax - cx) * (by - ay) - (ax - bx) * (cy - ay))).abs()
+}
+
+/// Downsample a series to a target number of points using
+/// Largest-Triangle-Three-Buckets (LTTB), which keeps actual raw points
+/// rather than averaging them, preserving spikes that bucket aggregation
+/// would flatten.
+///
+/// Always keeps the first and last point. The remaining points are split
+/// into `max_points - 2` buckets; for each bucket (left to right) the point
+/// that maximizes the triangle area against the previously selected point
+/// and the next bucket's centroid is kept.
+pub fn lttb_downsample(points: &[MetricPoint], max_points: usize) -> Vec<AggregatedPoint> {
+    if points.is_empty() {
+        return vec![];
+    }
+
+    if max_points <= 2 || points.len() <= max_points {
+        return if max_points <= 2 && points.len() > 2 {
+            vec![
+                to_aggregated(&points[0]),
+                to_aggregated(&points[points.len() - 1]),
+            ]
+        } else {
+            points.iter().map(to_aggregated).collect()
+        };
+    }
+
+    let bucket_count = max_points - 2;
+    let bucket_size = (points.len() - 2) as f64 / bucket_count as f64;
+
+    let mut selected = Vec::with_capacity(max_points);
+    selected.push(&points[0]);
+
+    for i in 0..bucket_count {
+        let (start, end) = lttb_bucket_bounds(i, bucket_count, bucket_size, points.len());
+        let (next_start, next_end) =
+            lttb_bucket_bounds(i + 1, bucket_count, bucket_size, points.len());
+
+        if start >= end {
+            continue;
+        }
+
+        let a = *selected.last().unwrap();
+        let (cx, cy) = centroid(&points[next_start..next_end]);
+
+        let mut best_idx = start;
+        let mut best_area = -1.0;
+        for (j, candidate) in points[start..end].iter().enumerate() {
+            let area = triangle_area(a, candidate, cx, cy);
+            if area > best_area {
+                best_area = area;
+                best_idx = start + j;
+            }
+        }
+        selected.push(&points[best_idx]);
+    }
+
+    selected.push(&points[points.len() - 1]);
+    selected.into_iter().map(to_aggregated).collect()
+}
+
 /// In-memory metric storage for a run.
 #[derive(Debug, Default, Clone)]
 pub struct RunMetrics {
@@ -169,6 +306,7 @@ impl RunMetrics {
         max_points: usize,
         start_step: Option<i64>,
         end_step: Option<i64>,
+        method: DownsampleMethod,
     ) -> Vec<MetricSeries> {
         let query_names: Vec<&String> = if names.is_empty() {
             self.metrics.keys().collect()
@@ -195,7 +333,7 @@ impl RunMetrics {
 
                 let total_points = filtered.len();
                 let downsampled = total_points > max_points;
-                let aggregated = downsample_points(&filtered, max_points);
+                let aggregated = downsample(&filtered, max_points, method);
 
                 MetricSeries {
                     name: name.clone(),
@@ -260,6 +398,109 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_lttb_downsample_empty() {
+        let points: Vec<MetricPoint> = vec![];
+        assert!(lttb_downsample(&points, 10).is_empty());
+    }
+
+    #[test]
+    fn test_lttb_downsample_under_limit_passes_through() {
+        let points: Vec<MetricPoint> = (0..5)
+            .map(|i| MetricPoint {
+                name: "loss".to_string(),
+                step: i,
+                value: i as f64,
+                timestamp: None,
+            })
+            .collect();
+
+        let result = lttb_downsample(&points, 10);
+        assert_eq!(result.len(), 5);
+        assert_eq!(result[0].step, 0);
+        assert_eq!(result[0].count, 1);
+    }
+
+    #[test]
+    fn test_lttb_downsample_max_points_two_keeps_first_and_last() {
+        let points: Vec<MetricPoint> = (0..20)
+            .map(|i| MetricPoint {
+                name: "loss".to_string(),
+                step: i,
+                value: i as f64,
+                timestamp: None,
+            })
+            .collect();
+
+        let result = lttb_downsample(&points, 2);
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].step, 0);
+        assert_eq!(result[1].step, 19);
+    }
+
+    #[test]
+    fn test_lttb_downsample_keeps_endpoints_and_budget() {
+        let points: Vec<MetricPoint> = (0..200)
+            .map(|i| MetricPoint {
+                name: "loss".to_string(),
+                step: i,
+                value: (i as f64 * 0.1).sin(),
+                timestamp: None,
+            })
+            .collect();
+
+        let result = lttb_downsample(&points, 20);
+        assert_eq!(result.len(), 20);
+        assert_eq!(result[0].step, 0);
+        assert_eq!(result.last().unwrap().step, 199);
+        // Every selected point is a real raw point, not an averaged bucket.
+        for point in &result {
+            assert_eq!(point.count, 1);
+            assert_eq!(point.min, point.mean);
+            assert_eq!(point.max, point.mean);
+        }
+        // Steps are strictly increasing - no bucket ever gets skipped twice.
+        for pair in result.windows(2) {
+            assert!(pair[1].step > pair[0].step);
+        }
+    }
+
+    #[test]
+    fn test_lttb_downsample_preserves_a_spike_that_averaging_would_flatten() {
+        // A single large spike surrounded by flat noise: bucket averaging
+        // would dilute it into the bucket mean, LTTB should keep it.
+        let mut points: Vec<MetricPoint> = (0..100)
+            .map(|i| MetricPoint {
+                name: "loss".to_string(),
+                step: i,
+                value: 0.0,
+                timestamp: None,
+            })
+            .collect();
+        points[50].value = 1000.0;
+
+        let result = lttb_downsample(&points, 10);
+        assert!(result.iter().any(|p| p.mean == 1000.0));
+    }
+
+    #[test]
+    fn test_downsample_dispatches_by_method() {
+        let points: Vec<MetricPoint> = (0..100)
+            .map(|i| MetricPoint {
+                name: "loss".to_string(),
+                step: i,
+                value: i as f64,
+                timestamp: None,
+            })
+            .collect();
+
+        let aggregated = downsample(&points, 10, DownsampleMethod::Aggregate);
+        let lttb = downsample(&points, 10, DownsampleMethod::Lttb);
+        assert_eq!(aggregated.len(), 10);
+        assert_eq!(lttb.len(), 10);
+        assert!(lttb.iter().all(|p| p.count == 1));
+    }
+
     #[test]
     fn test_run_metrics_query() {
         let mut metrics = RunMetrics::new();
@@ -281,16 +522,28 @@ mod tests {
         }
 
         // Query all metrics
-        let series = metrics.query(&[], 100, None, None);
+        let series = metrics.query(&[], 100, None, None, DownsampleMethod::Aggregate);
         assert_eq!(series.len(), 2);
 
         // Query specific metric
-        let series = metrics.query(&["loss".to_string()], 100, None, None);
+        let series = metrics.query(
+            &["loss".to_string()],
+            100,
+            None,
+            None,
+            DownsampleMethod::Aggregate,
+        );
         assert_eq!(series.len(), 1);
         assert_eq!(series[0].name, "loss");
 
         // Query with step range
-        let series = metrics.query(&["loss".to_string()], 100, Some(10), Some(20));
+        let series = metrics.query(
+            &["loss".to_string()],
+            100,
+            Some(10),
+            Some(20),
+            DownsampleMethod::Aggregate,
+        );
         assert_eq!(series.len(), 1);
         assert_eq!(series[0].total_points, 11); // steps 10-20 inclusive
     }
@@ -2,17 +2,28 @@
 //!
 //! This module contains the gRPC and HTTP service implementations.
 
+pub mod cardinality_store;
+mod hyperloglog;
 pub mod idempotency;
+pub mod idempotency_wal;
 pub mod ingest;
 pub mod limits;
 pub mod metrics;
 
+pub use cardinality_store::{
+    CardinalityStore, MemoryCardinalityStore, PersistedRunCardinality, PersistedTagPairs,
+    SqliteCardinalityStore,
+};
 pub use idempotency::{
-    IdempotencyResult, IdempotencyStore, MetricPayload, ParamPayload, SharedIdempotencyStore,
-    TagPayload, compute_payload_hash,
+    compute_payload_hash, IdempotencyResult, IdempotencyStore, MetricPayload, ParamPayload,
+    SharedIdempotencyStore, TagPayload,
 };
+pub use idempotency_wal::{IdempotencyBackend, WalConfig, WalError, WalIdempotencyStore};
 pub use ingest::IngestServiceImpl;
-pub use limits::{CardinalityTracker, LimitsConfig, SharedCardinalityTracker, ValidationResult};
+pub use limits::{
+    CardinalityStoreBackend, CardinalityTracker, GuardrailMetricsSnapshot, LimitsConfig,
+    SharedCardinalityTracker, TagCardinalityEstimator, ValidationResult,
+};
 pub use metrics::{
     AggregatedPoint, MetricPoint, MetricSeries, MetricsQueryRequest, MetricsQueryResponse,
     RunMetrics,
@@ -0,0 +1,583 @@
+//! `ArtifactBackend` trait: storage-agnostic artifact persistence.
+//!
+//! Mirrors the `RunStore`/`ApiKeyStore` backend-trait split elsewhere in
+//! `storage`: [`ArtifactStore`] is generic over an `Arc<dyn ArtifactBackend>`
+//! rather than hard-wired to MinIO/S3, selected via `ARTIFACT_BACKEND` (see
+//! [`crate::config::ArtifactBackendKind`]). [`super::MinioClient`] is the
+//! production S3 implementation; [`LocalFsBackend`] writes under a root
+//! directory for dev/offline use where there's no MinIO server to talk to.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use tracing::instrument;
+
+use super::minio::{ArtifactLocation, ArtifactObjectMeta, LifecycleRule, MinioError, PresignedUrl};
+
+#[cfg(test)]
+use super::minio::{MinioClient, MinioConfig};
+
+/// Storage backend for artifacts, behind which [`ArtifactStore`] is
+/// generic. Implementations must be safe to share behind an `Arc` and
+/// called concurrently by every HTTP handler.
+///
+/// `put`/`get`/`head`/`delete`/`list` are direct, buffered operations -
+/// the right shape for a local-disk backend. A backend that can only hand
+/// back presigned URLs (S3) is free to return
+/// [`MinioError::Config`] from `put`/`get`, since real clients stream
+/// through the `presign`-minted URL instead of routing bytes through us.
+#[async_trait::async_trait]
+pub trait ArtifactBackend: Send + Sync {
+    /// Upload `data` as `artifact_name` under `run_id`.
+    async fn put(
+        &self,
+        run_id: &str,
+        artifact_name: &str,
+        data: Vec<u8>,
+    ) -> Result<ArtifactLocation, MinioError>;
+
+    /// Download an artifact's full contents.
+    async fn get(&self, run_id: &str, artifact_name: &str) -> Result<Vec<u8>, MinioError>;
+
+    /// Check whether an artifact exists, returning its size in bytes if so.
+    async fn head(&self, run_id: &str, artifact_name: &str) -> Result<Option<u64>, MinioError>;
+
+    /// Delete an artifact.
+    async fn delete(&self, run_id: &str, artifact_name: &str) -> Result<(), MinioError>;
+
+    /// List artifacts stored for a run.
+    async fn list(&self, run_id: &str) -> Result<Vec<ArtifactLocation>, MinioError>;
+
+    /// List artifacts stored for a run along with the size/last-modified
+    /// metadata [`ArtifactStore::apply_lifecycle`] needs to evaluate
+    /// [`LifecycleRule`]s against them.
+    async fn list_with_meta(&self, run_id: &str) -> Result<Vec<ArtifactObjectMeta>, MinioError>;
+
+    /// Mint a URL a client can use directly for `method` (`"PUT"` or
+    /// `"GET"`) against an artifact - a real presigned URL for S3, or a
+    /// direct `file://` path for backends with no signing concept.
+    fn presign(
+        &self,
+        run_id: &str,
+        artifact_name: &str,
+        method: &str,
+    ) -> Result<PresignedUrl, MinioError>;
+
+    /// Artifact location info, without touching the backend.
+    fn location(&self, run_id: &str, artifact_name: &str) -> ArtifactLocation;
+}
+
+/// Repository for artifact storage operations, generic over the
+/// underlying [`ArtifactBackend`] so handlers don't need to know whether
+/// they're talking to S3 or local disk.
+pub struct ArtifactStore {
+    backend: std::sync::Arc<dyn ArtifactBackend>,
+}
+
+impl ArtifactStore {
+    /// Create a new artifact store over `backend`.
+    pub fn new(backend: std::sync::Arc<dyn ArtifactBackend>) -> Self {
+        Self { backend }
+    }
+
+    /// Generate presigned upload URL for a new artifact.
+    #[instrument(skip(self))]
+    pub fn create_upload_url(
+        &self,
+        run_id: &str,
+        artifact_name: &str,
+        _content_type: Option<&str>,
+        _content_length: Option<u64>,
+    ) -> Result<(ArtifactLocation, PresignedUrl), MinioError> {
+        let location = self.backend.location(run_id, artifact_name);
+        let presigned = self.backend.presign(run_id, artifact_name, "PUT")?;
+        Ok((location, presigned))
+    }
+
+    /// Generate presigned download URL for an artifact.
+    #[instrument(skip(self))]
+    pub fn create_download_url(
+        &self,
+        run_id: &str,
+        artifact_name: &str,
+    ) -> Result<PresignedUrl, MinioError> {
+        self.backend.presign(run_id, artifact_name, "GET")
+    }
+
+    /// Get artifact location info.
+    pub fn get_location(&self, run_id: &str, artifact_name: &str) -> ArtifactLocation {
+        self.backend.location(run_id, artifact_name)
+    }
+
+    /// Upload `data` directly, bypassing the presigned-URL flow. Only
+    /// useful against a backend that actually buffers bytes through us
+    /// (e.g. [`LocalFsBackend`]); the S3 backend rejects this.
+    #[instrument(skip(self, data))]
+    pub async fn put(
+        &self,
+        run_id: &str,
+        artifact_name: &str,
+        data: Vec<u8>,
+    ) -> Result<ArtifactLocation, MinioError> {
+        self.backend.put(run_id, artifact_name, data).await
+    }
+
+    /// Download an artifact's full contents directly.
+    #[instrument(skip(self))]
+    pub async fn get(&self, run_id: &str, artifact_name: &str) -> Result<Vec<u8>, MinioError> {
+        self.backend.get(run_id, artifact_name).await
+    }
+
+    /// Check whether an artifact exists, returning its size if so.
+    #[instrument(skip(self))]
+    pub async fn head(&self, run_id: &str, artifact_name: &str) -> Result<Option<u64>, MinioError> {
+        self.backend.head(run_id, artifact_name).await
+    }
+
+    /// Delete an artifact.
+    #[instrument(skip(self))]
+    pub async fn delete(&self, run_id: &str, artifact_name: &str) -> Result<(), MinioError> {
+        self.backend.delete(run_id, artifact_name).await
+    }
+
+    /// List artifacts stored for a run.
+    #[instrument(skip(self))]
+    pub async fn list(&self, run_id: &str) -> Result<Vec<ArtifactLocation>, MinioError> {
+        self.backend.list(run_id).await
+    }
+
+    /// Enforce `rules` against every artifact stored for `run_id`: list
+    /// the run's artifacts with metadata, evaluate each rule against them,
+    /// and delete whatever the rules select. Rules are independent of one
+    /// another - an artifact matching more than one is still only deleted
+    /// once. Always enforced client-side; for the S3 backend, pair this
+    /// with [`super::MinioClient::put_bucket_lifecycle`] to also push the
+    /// `ExpireAfter` subset down as a native bucket lifecycle rule.
+    #[instrument(skip(self, rules))]
+    pub async fn apply_lifecycle(
+        &self,
+        run_id: &str,
+        rules: &[LifecycleRule],
+    ) -> Result<LifecycleReport, MinioError> {
+        let objects = self.backend.list_with_meta(run_id).await?;
+        let now = std::time::SystemTime::now();
+
+        let mut to_delete: HashMap<String, ArtifactLocation> = HashMap::new();
+        for rule in rules {
+            match rule {
+                LifecycleRule::ExpireAfter { prefix, max_age } => {
+                    for object in objects
+                        .iter()
+                        .filter(|object| object.location.key.starts_with(prefix.as_str()))
+                    {
+                        let age = now.duration_since(object.last_modified).unwrap_or_default();
+                        if age >= *max_age {
+                            to_delete.insert(object.location.key.clone(), object.location.clone());
+                        }
+                    }
+                }
+                LifecycleRule::KeepLast { prefix, keep } => {
+                    let mut matching: Vec<&ArtifactObjectMeta> = objects
+                        .iter()
+                        .filter(|object| object.location.key.starts_with(prefix.as_str()))
+                        .collect();
+                    matching.sort_by_key(|object| std::cmp::Reverse(object.last_modified));
+                    for object in matching.into_iter().skip(*keep) {
+                        to_delete.insert(object.location.key.clone(), object.location.clone());
+                    }
+                }
+            }
+        }
+
+        let mut deleted = Vec::new();
+        for (key, location) in to_delete {
+            let artifact_name = key.rsplit('/').next().unwrap_or(&key);
+            self.backend.delete(run_id, artifact_name).await?;
+            deleted.push(location);
+        }
+        Ok(LifecycleReport { deleted })
+    }
+}
+
+/// Outcome of one [`ArtifactStore::apply_lifecycle`] sweep.
+#[derive(Debug, Clone, Default)]
+pub struct LifecycleReport {
+    /// Every artifact the sweep deleted.
+    pub deleted: Vec<ArtifactLocation>,
+}
+
+/// Local-filesystem [`ArtifactBackend`] for dev/offline use: artifacts
+/// live under `root_dir`, so MLRun can run end-to-end without a MinIO
+/// server. There's no signing concept for a local path, so `presign` just
+/// returns a direct `file://` path for the caller to read/write in place.
+pub struct LocalFsBackend {
+    root_dir: PathBuf,
+}
+
+impl LocalFsBackend {
+    /// Create a backend rooted at `root_dir`, creating it if it doesn't
+    /// exist yet.
+    pub fn new(root_dir: impl Into<PathBuf>) -> std::io::Result<Self> {
+        let root_dir = root_dir.into();
+        std::fs::create_dir_all(&root_dir)?;
+        Ok(Self { root_dir })
+    }
+
+    fn artifact_path(&self, run_id: &str, artifact_name: &str) -> PathBuf {
+        self.root_dir.join("runs").join(run_id).join(artifact_name)
+    }
+
+    fn run_dir(&self, run_id: &str) -> PathBuf {
+        self.root_dir.join("runs").join(run_id)
+    }
+
+    fn io_err(path: &std::path::Path, e: std::io::Error) -> MinioError {
+        if e.kind() == std::io::ErrorKind::NotFound {
+            MinioError::NotFound(path.display().to_string())
+        } else {
+            MinioError::Client(e.to_string())
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl ArtifactBackend for LocalFsBackend {
+    async fn put(
+        &self,
+        run_id: &str,
+        artifact_name: &str,
+        data: Vec<u8>,
+    ) -> Result<ArtifactLocation, MinioError> {
+        let path = self.artifact_path(run_id, artifact_name);
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent)
+                .await
+                .map_err(|e| MinioError::Client(e.to_string()))?;
+        }
+        tokio::fs::write(&path, data)
+            .await
+            .map_err(|e| MinioError::Client(e.to_string()))?;
+        Ok(self.location(run_id, artifact_name))
+    }
+
+    async fn get(&self, run_id: &str, artifact_name: &str) -> Result<Vec<u8>, MinioError> {
+        let path = self.artifact_path(run_id, artifact_name);
+        tokio::fs::read(&path)
+            .await
+            .map_err(|e| Self::io_err(&path, e))
+    }
+
+    async fn head(&self, run_id: &str, artifact_name: &str) -> Result<Option<u64>, MinioError> {
+        match tokio::fs::metadata(self.artifact_path(run_id, artifact_name)).await {
+            Ok(meta) => Ok(Some(meta.len())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(MinioError::Client(e.to_string())),
+        }
+    }
+
+    async fn delete(&self, run_id: &str, artifact_name: &str) -> Result<(), MinioError> {
+        let path = self.artifact_path(run_id, artifact_name);
+        tokio::fs::remove_file(&path)
+            .await
+            .map_err(|e| Self::io_err(&path, e))
+    }
+
+    async fn list(&self, run_id: &str) -> Result<Vec<ArtifactLocation>, MinioError> {
+        let dir = self.run_dir(run_id);
+        let mut read_dir = match tokio::fs::read_dir(&dir).await {
+            Ok(read_dir) => read_dir,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(vec![]),
+            Err(e) => return Err(MinioError::Client(e.to_string())),
+        };
+
+        let mut locations = Vec::new();
+        while let Some(entry) = read_dir
+            .next_entry()
+            .await
+            .map_err(|e| MinioError::Client(e.to_string()))?
+        {
+            if let Some(name) = entry.file_name().to_str() {
+                locations.push(self.location(run_id, name));
+            }
+        }
+        Ok(locations)
+    }
+
+    async fn list_with_meta(&self, run_id: &str) -> Result<Vec<ArtifactObjectMeta>, MinioError> {
+        let dir = self.run_dir(run_id);
+        let mut read_dir = match tokio::fs::read_dir(&dir).await {
+            Ok(read_dir) => read_dir,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(vec![]),
+            Err(e) => return Err(MinioError::Client(e.to_string())),
+        };
+
+        let mut objects = Vec::new();
+        while let Some(entry) = read_dir
+            .next_entry()
+            .await
+            .map_err(|e| MinioError::Client(e.to_string()))?
+        {
+            let Some(name) = entry.file_name().to_str().map(str::to_string) else {
+                continue;
+            };
+            let metadata = entry
+                .metadata()
+                .await
+                .map_err(|e| MinioError::Client(e.to_string()))?;
+            let last_modified = metadata
+                .modified()
+                .map_err(|e| MinioError::Client(e.to_string()))?;
+            objects.push(ArtifactObjectMeta {
+                location: self.location(run_id, &name),
+                size_bytes: metadata.len(),
+                last_modified,
+            });
+        }
+        Ok(objects)
+    }
+
+    fn presign(
+        &self,
+        run_id: &str,
+        artifact_name: &str,
+        method: &str,
+    ) -> Result<PresignedUrl, MinioError> {
+        let path = self.artifact_path(run_id, artifact_name);
+        Ok(PresignedUrl {
+            url: format!("file://{}", path.display()),
+            method: method.to_string(),
+            // Not a signed grant with an expiry - it's a direct path into
+            // a local directory we already control access to.
+            expires_in_secs: 0,
+            headers: HashMap::new(),
+        })
+    }
+
+    fn location(&self, run_id: &str, artifact_name: &str) -> ArtifactLocation {
+        let key = format!("runs/{}/{}", run_id, artifact_name);
+        let storage_url = format!(
+            "file://{}",
+            self.artifact_path(run_id, artifact_name).display()
+        );
+        ArtifactLocation {
+            bucket: "local".to_string(),
+            key,
+            storage_url,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_backend() -> LocalFsBackend {
+        let dir =
+            std::env::temp_dir().join(format!("mlrun-artifact-test-{}", uuid::Uuid::new_v4()));
+        LocalFsBackend::new(dir).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_local_fs_put_then_get_roundtrips() {
+        let backend = temp_backend();
+        backend
+            .put("run-123", "model.pt", b"weights".to_vec())
+            .await
+            .unwrap();
+
+        let data = backend.get("run-123", "model.pt").await.unwrap();
+        assert_eq!(data, b"weights");
+    }
+
+    #[tokio::test]
+    async fn test_local_fs_get_missing_artifact_returns_not_found() {
+        let backend = temp_backend();
+        let err = backend.get("run-123", "missing.pt").await.unwrap_err();
+        assert!(matches!(err, MinioError::NotFound(_)));
+    }
+
+    #[tokio::test]
+    async fn test_local_fs_head_reports_size() {
+        let backend = temp_backend();
+        backend
+            .put("run-123", "model.pt", b"12345".to_vec())
+            .await
+            .unwrap();
+
+        assert_eq!(backend.head("run-123", "model.pt").await.unwrap(), Some(5));
+        assert_eq!(backend.head("run-123", "missing.pt").await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_local_fs_delete_then_get_not_found() {
+        let backend = temp_backend();
+        backend
+            .put("run-123", "model.pt", b"weights".to_vec())
+            .await
+            .unwrap();
+        backend.delete("run-123", "model.pt").await.unwrap();
+
+        assert!(backend.get("run-123", "model.pt").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_local_fs_list_empty_run_returns_empty_vec() {
+        let backend = temp_backend();
+        assert_eq!(backend.list("does-not-exist").await.unwrap(), vec![]);
+    }
+
+    #[tokio::test]
+    async fn test_local_fs_list_returns_uploaded_artifacts() {
+        let backend = temp_backend();
+        backend
+            .put("run-123", "model.pt", b"a".to_vec())
+            .await
+            .unwrap();
+        backend
+            .put("run-123", "metrics.json", b"b".to_vec())
+            .await
+            .unwrap();
+
+        let mut names: Vec<String> = backend
+            .list("run-123")
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|loc| loc.key)
+            .collect();
+        names.sort();
+        assert_eq!(
+            names,
+            vec![
+                "runs/run-123/metrics.json".to_string(),
+                "runs/run-123/model.pt".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_local_fs_presign_returns_file_url() {
+        let backend = temp_backend();
+        let presigned = backend.presign("run-123", "model.pt", "PUT").unwrap();
+        assert_eq!(presigned.method, "PUT");
+        assert!(presigned.url.starts_with("file://"));
+        assert!(presigned.url.ends_with("runs/run-123/model.pt"));
+    }
+
+    #[test]
+    fn test_artifact_store_over_s3_backend() {
+        let backend = std::sync::Arc::new(MinioClient::new(MinioConfig::default()));
+        let store = ArtifactStore::new(backend);
+
+        let result = store.create_upload_url("run-123", "checkpoint.pt", None, None);
+        assert!(result.is_ok());
+
+        let (location, presigned) = result.unwrap();
+        assert_eq!(location.bucket, "mlrun-artifacts");
+        assert_eq!(presigned.method, "PUT");
+    }
+
+    #[tokio::test]
+    async fn test_artifact_store_over_local_fs_backend() {
+        let backend = std::sync::Arc::new(temp_backend());
+        let store = ArtifactStore::new(backend);
+
+        store
+            .put("run-123", "checkpoint.pt", b"weights".to_vec())
+            .await
+            .unwrap();
+        assert_eq!(
+            store.get("run-123", "checkpoint.pt").await.unwrap(),
+            b"weights"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_apply_lifecycle_expire_after_deletes_old_artifacts() {
+        let backend = std::sync::Arc::new(temp_backend());
+        let store = ArtifactStore::new(backend);
+
+        store
+            .put("run-123", "old.log", b"stale".to_vec())
+            .await
+            .unwrap();
+
+        let report = store
+            .apply_lifecycle(
+                "run-123",
+                &[LifecycleRule::ExpireAfter {
+                    prefix: "runs/run-123/".to_string(),
+                    max_age: std::time::Duration::from_secs(0),
+                }],
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(report.deleted.len(), 1);
+        assert!(store.get("run-123", "old.log").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_apply_lifecycle_expire_after_keeps_fresh_artifacts() {
+        let backend = std::sync::Arc::new(temp_backend());
+        let store = ArtifactStore::new(backend);
+
+        store
+            .put("run-123", "fresh.log", b"new".to_vec())
+            .await
+            .unwrap();
+
+        let report = store
+            .apply_lifecycle(
+                "run-123",
+                &[LifecycleRule::ExpireAfter {
+                    prefix: "runs/run-123/".to_string(),
+                    max_age: std::time::Duration::from_secs(86_400 * 30),
+                }],
+            )
+            .await
+            .unwrap();
+
+        assert!(report.deleted.is_empty());
+        assert_eq!(store.get("run-123", "fresh.log").await.unwrap(), b"new");
+    }
+
+    #[tokio::test]
+    async fn test_apply_lifecycle_keep_last_deletes_everything_but_the_newest() {
+        let backend = std::sync::Arc::new(temp_backend());
+        let store = ArtifactStore::new(backend);
+
+        for i in 0..5 {
+            store
+                .put("run-123", &format!("checkpoint-{i}.pt"), vec![i as u8])
+                .await
+                .unwrap();
+            // Ensure each checkpoint gets a distinct, increasing mtime so
+            // "newest" is well-defined.
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+
+        let report = store
+            .apply_lifecycle(
+                "run-123",
+                &[LifecycleRule::KeepLast {
+                    prefix: "runs/run-123/checkpoint-".to_string(),
+                    keep: 2,
+                }],
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(report.deleted.len(), 3);
+        let remaining: Vec<String> = store
+            .list("run-123")
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|loc| loc.key)
+            .collect();
+        assert_eq!(remaining.len(), 2);
+        assert!(remaining.contains(&"runs/run-123/checkpoint-3.pt".to_string()));
+        assert!(remaining.contains(&"runs/run-123/checkpoint-4.pt".to_string()));
+    }
+}
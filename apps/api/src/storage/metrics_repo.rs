@@ -0,0 +1,186 @@
+//! `MetricsRepo` trait: storage-agnostic metric point persistence.
+//!
+//! Mirrors the [`super::RunStore`] split: an in-memory backend for
+//! alpha/dev (the default, wrapping the existing [`RunMetrics`] map) and a
+//! PostgreSQL-backed one for production, selected via `METRICS_STORE_BACKEND`
+//! (see [`crate::config::MetricsRepoBackend`]). Both ingest paths (direct
+//! gRPC and the queue consumer) write through this trait instead of
+//! touching [`InMemoryStore::metrics`](crate::services::ingest::InMemoryStore)
+//! directly, so logged points survive a server restart once Postgres is
+//! configured.
+
+use std::collections::HashMap;
+
+use thiserror::Error;
+use tokio::sync::RwLock;
+
+use crate::services::metrics::{DownsampleMethod, MetricPoint, MetricSeries, RunMetrics};
+
+/// Errors returned by a `MetricsRepo` implementation.
+#[derive(Error, Debug)]
+pub enum MetricsRepoError {
+    #[error("run not found: {0}")]
+    NotFound(String),
+    #[error("query `{query}` failed: {source}")]
+    Query {
+        query: &'static str,
+        #[source]
+        source: sqlx::Error,
+    },
+    #[error("configuration error: {0}")]
+    Config(String),
+}
+
+/// Storage backend for logged metric points.
+///
+/// Implementations must be safe to share behind an `Arc` and called
+/// concurrently by every ingest path (direct gRPC and the queue consumer).
+#[async_trait::async_trait]
+pub trait MetricsRepo: Send + Sync {
+    /// Append `points` to `run_id`'s series, creating the run's entry if
+    /// this is its first point.
+    async fn add_points(
+        &self,
+        run_id: &str,
+        points: Vec<MetricPoint>,
+    ) -> Result<(), MetricsRepoError>;
+
+    /// Query `run_id`'s metrics with optional name/step-range filtering and
+    /// downsampling, identical in shape to [`RunMetrics::query`].
+    async fn query(
+        &self,
+        run_id: &str,
+        names: &[String],
+        max_points: usize,
+        start_step: Option<i64>,
+        end_step: Option<i64>,
+        method: DownsampleMethod,
+    ) -> Result<Vec<MetricSeries>, MetricsRepoError>;
+
+    /// List the distinct metric names logged for `run_id`.
+    async fn metric_names(&self, run_id: &str) -> Result<Vec<String>, MetricsRepoError>;
+
+    /// List the run IDs that have at least one logged metric point.
+    async fn list_runs(&self) -> Result<Vec<String>, MetricsRepoError>;
+}
+
+/// In-memory `MetricsRepo` implementation for alpha development and tests.
+/// In production, use [`super::postgres::PostgresMetricsRepo`] instead.
+#[derive(Debug, Default)]
+pub struct InMemoryMetricsRepo {
+    runs: RwLock<HashMap<String, RunMetrics>>,
+}
+
+impl InMemoryMetricsRepo {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait::async_trait]
+impl MetricsRepo for InMemoryMetricsRepo {
+    async fn add_points(
+        &self,
+        run_id: &str,
+        points: Vec<MetricPoint>,
+    ) -> Result<(), MetricsRepoError> {
+        let mut runs = self.runs.write().await;
+        let run_metrics = runs
+            .entry(run_id.to_string())
+            .or_insert_with(RunMetrics::new);
+        for point in points {
+            run_metrics.add_point(point);
+        }
+        Ok(())
+    }
+
+    async fn query(
+        &self,
+        run_id: &str,
+        names: &[String],
+        max_points: usize,
+        start_step: Option<i64>,
+        end_step: Option<i64>,
+        method: DownsampleMethod,
+    ) -> Result<Vec<MetricSeries>, MetricsRepoError> {
+        let runs = self.runs.read().await;
+        Ok(runs
+            .get(run_id)
+            .map(|m| m.query(names, max_points, start_step, end_step, method))
+            .unwrap_or_default())
+    }
+
+    async fn metric_names(&self, run_id: &str) -> Result<Vec<String>, MetricsRepoError> {
+        let runs = self.runs.read().await;
+        Ok(runs
+            .get(run_id)
+            .map(|m| m.metric_names())
+            .unwrap_or_default())
+    }
+
+    async fn list_runs(&self) -> Result<Vec<String>, MetricsRepoError> {
+        let runs = self.runs.read().await;
+        let mut ids: Vec<String> = runs.keys().cloned().collect();
+        ids.sort();
+        Ok(ids)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(name: &str, step: i64, value: f64) -> MetricPoint {
+        MetricPoint {
+            name: name.to_string(),
+            step,
+            value,
+            timestamp: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_add_points_then_query_roundtrips() {
+        let repo = InMemoryMetricsRepo::new();
+        repo.add_points("run-1", vec![point("loss", 0, 1.0), point("loss", 1, 0.5)])
+            .await
+            .unwrap();
+
+        let series = repo
+            .query("run-1", &[], 100, None, None, DownsampleMethod::Aggregate)
+            .await
+            .unwrap();
+        assert_eq!(series.len(), 1);
+        assert_eq!(series[0].name, "loss");
+        assert_eq!(series[0].total_points, 2);
+    }
+
+    #[tokio::test]
+    async fn test_query_unknown_run_returns_empty() {
+        let repo = InMemoryMetricsRepo::new();
+        let series = repo
+            .query("missing", &[], 100, None, None, DownsampleMethod::Aggregate)
+            .await
+            .unwrap();
+        assert!(series.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_list_runs_is_sorted() {
+        let repo = InMemoryMetricsRepo::new();
+        repo.add_points("run-b", vec![point("loss", 0, 1.0)])
+            .await
+            .unwrap();
+        repo.add_points("run-a", vec![point("loss", 0, 1.0)])
+            .await
+            .unwrap();
+
+        assert_eq!(repo.list_runs().await.unwrap(), vec!["run-a", "run-b"]);
+    }
+
+    #[tokio::test]
+    async fn test_metric_names_for_unknown_run_is_empty() {
+        let repo = InMemoryMetricsRepo::new();
+        assert!(repo.metric_names("missing").await.unwrap().is_empty());
+    }
+}
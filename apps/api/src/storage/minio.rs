@@ -2,11 +2,24 @@
 //!
 //! Provides presigned URL generation and artifact management.
 //! Compatible with MinIO, AWS S3, and other S3-compatible storage.
+//!
+//! Presigning uses real AWS Signature Version 4 query-string signing (the
+//! same custom-signer approach the `object_store` crate took when it
+//! dropped `rusoto`), so the returned [`PresignedUrl`] authenticates
+//! directly against MinIO or S3 without going through an SDK.
 
+use chrono::Utc;
+use futures::{Stream, StreamExt};
+use hmac::{Hmac, Mac};
+use reqwest::StatusCode;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::pin::Pin;
 use thiserror::Error;
 use tracing::instrument;
 
+type HmacSha256 = Hmac<Sha256>;
+
 /// Errors that can occur in MinIO operations.
 #[derive(Error, Debug)]
 pub enum MinioError {
@@ -23,6 +36,14 @@ pub enum MinioError {
     InvalidPresign(String),
 }
 
+/// S3 requires every part but the last to be at least 5 MiB.
+pub const MIN_MULTIPART_CHUNK_SIZE_BYTES: u64 = 5 * 1024 * 1024;
+
+const DEFAULT_MULTIPART_CHUNK_SIZE_BYTES: u64 = 8 * 1024 * 1024;
+
+/// S3's hard cap on keys returned per `ListObjectsV2` page.
+const LIST_OBJECTS_MAX_KEYS: u32 = 1000;
+
 /// Configuration for MinIO/S3 connection.
 #[derive(Debug, Clone)]
 pub struct MinioConfig {
@@ -40,6 +61,10 @@ pub struct MinioConfig {
     pub region: String,
     /// Presigned URL expiry in seconds
     pub presign_expiry_secs: u64,
+    /// Chunk size for multipart uploads, in bytes. Clamped up to
+    /// [`MIN_MULTIPART_CHUNK_SIZE_BYTES`] wherever it's read, since S3
+    /// rejects smaller non-final parts.
+    pub multipart_chunk_size_bytes: u64,
 }
 
 impl Default for MinioConfig {
@@ -52,6 +77,7 @@ impl Default for MinioConfig {
             path_style: true,
             region: "us-east-1".to_string(),
             presign_expiry_secs: 3600, // 1 hour
+            multipart_chunk_size_bytes: DEFAULT_MULTIPART_CHUNK_SIZE_BYTES,
         }
     }
 }
@@ -79,6 +105,11 @@ impl MinioConfig {
                 .ok()
                 .and_then(|s| s.parse().ok())
                 .unwrap_or(3600),
+            multipart_chunk_size_bytes: std::env::var("MINIO_MULTIPART_CHUNK_SIZE_BYTES")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .map(|n: u64| n.max(MIN_MULTIPART_CHUNK_SIZE_BYTES))
+                .unwrap_or(DEFAULT_MULTIPART_CHUNK_SIZE_BYTES),
         }
     }
 }
@@ -121,16 +152,99 @@ pub struct PresignedUrl {
     pub headers: std::collections::HashMap<String, String>,
 }
 
+/// A byte range for a partial object read, in HTTP `Range` header terms:
+/// `bytes=start-` when `end` is `None` (open-ended, "from `start` to the
+/// end of the object"), `bytes=start-end` otherwise.
+#[derive(Debug, Clone, Copy)]
+pub struct ByteRange {
+    /// First byte to fetch, inclusive.
+    pub start: u64,
+    /// Last byte to fetch, inclusive; `None` means "through the end of
+    /// the object".
+    pub end: Option<u64>,
+}
+
+impl ByteRange {
+    /// Value for the `Range` request header.
+    fn header_value(&self) -> String {
+        match self.end {
+            Some(end) => format!("bytes={}-{}", self.start, end),
+            None => format!("bytes={}-", self.start),
+        }
+    }
+}
+
+/// A streamed slice of an object, returned by
+/// [`MinioClient::get_object_range`].
+pub struct ObjectRangeResponse {
+    /// Size of the returned slice, in bytes.
+    pub content_length: u64,
+    /// The `Content-Range` response header (e.g. `bytes 0-1023/10240`),
+    /// present whenever the server actually served a partial range rather
+    /// than the whole object.
+    pub content_range: Option<String>,
+    /// The object body, streamed rather than buffered so large artifacts
+    /// don't have to fit in memory.
+    pub body: Pin<Box<dyn Stream<Item = Result<bytes::Bytes, MinioError>> + Send>>,
+}
+
+/// An in-progress multipart upload.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MultipartUpload {
+    /// The `UploadId` S3 assigned; required on every subsequent part
+    /// upload and on completion/abort.
+    pub upload_id: String,
+    /// Bucket the upload targets.
+    pub bucket: String,
+    /// Object key the completed upload will produce.
+    pub key: String,
+}
+
+/// An [`ArtifactLocation`] plus the metadata needed to evaluate
+/// [`LifecycleRule`]s against it.
+#[derive(Debug, Clone)]
+pub struct ArtifactObjectMeta {
+    /// Where the object lives.
+    pub location: ArtifactLocation,
+    /// Object size in bytes.
+    pub size_bytes: u64,
+    /// When the object was last written.
+    pub last_modified: std::time::SystemTime,
+}
+
+/// A rule an [`super::artifact_backend::ArtifactStore`] lifecycle sweep
+/// enforces against the artifacts stored for a run. Mirrors the two
+/// expiration shapes garage's `s3/lifecycle.rs` supports: age-based and
+/// keep-last-N.
+#[derive(Debug, Clone)]
+pub enum LifecycleRule {
+    /// Delete artifacts whose key starts with `prefix` once they're older
+    /// than `max_age`. Expressible as a native S3 lifecycle rule - see
+    /// [`lifecycle_rules_to_s3_xml`].
+    ExpireAfter {
+        prefix: String,
+        max_age: std::time::Duration,
+    },
+    /// Keep only the `keep` most-recently-modified artifacts whose key
+    /// starts with `prefix`, deleting the rest. No S3 lifecycle
+    /// equivalent - always enforced client-side.
+    KeepLast { prefix: String, keep: usize },
+}
+
 /// MinIO/S3 client wrapper.
 #[derive(Clone)]
 pub struct MinioClient {
     config: MinioConfig,
+    http: reqwest::Client,
 }
 
 impl MinioClient {
     /// Create a new MinIO client.
     pub fn new(config: MinioConfig) -> Self {
-        Self { config }
+        Self {
+            config,
+            http: reqwest::Client::new(),
+        }
     }
 
     /// Get the storage URL for an artifact.
@@ -139,9 +253,6 @@ impl MinioClient {
     }
 
     /// Generate a presigned URL for uploading an artifact.
-    ///
-    /// Note: This is a placeholder implementation. In production, use the
-    /// aws-sdk-s3 crate or similar to generate proper presigned URLs.
     #[instrument(skip(self))]
     pub fn presign_upload(
         &self,
@@ -151,19 +262,13 @@ impl MinioClient {
         _content_length: Option<u64>,
     ) -> Result<PresignedUrl, MinioError> {
         let location = self.get_artifact_location(run_id, artifact_name);
-
-        // Placeholder: In production, use proper S3 signing
-        // This generates a URL that would need the actual presigning logic
-        let url = format!("{}/{}/{}", self.config.endpoint, location.bucket, location.key);
-
-        let mut headers = std::collections::HashMap::new();
-        headers.insert("x-amz-acl".to_string(), "private".to_string());
+        let url = self.presign(&location.key, "PUT", &[])?;
 
         Ok(PresignedUrl {
             url,
             method: "PUT".to_string(),
             expires_in_secs: self.config.presign_expiry_secs,
-            headers,
+            headers: std::collections::HashMap::new(),
         })
     }
 
@@ -175,18 +280,468 @@ impl MinioClient {
         artifact_name: &str,
     ) -> Result<PresignedUrl, MinioError> {
         let location = self.get_artifact_location(run_id, artifact_name);
+        let url = self.presign(&location.key, "GET", &[])?;
 
-        // Placeholder: In production, use proper S3 signing
-        let url = format!("{}/{}/{}", self.config.endpoint, location.bucket, location.key);
+        Ok(PresignedUrl {
+            url,
+            method: "GET".to_string(),
+            expires_in_secs: self.config.presign_expiry_secs,
+            headers: std::collections::HashMap::new(),
+        })
+    }
+
+    /// Generate a presigned GET URL for downloading one byte range of an
+    /// artifact, so a client can resume an interrupted download or stream
+    /// a large checkpoint without buffering it whole. The `Range` header
+    /// rides along in [`PresignedUrl::headers`] - presigning only covers
+    /// the URL's signature, so the caller must still send it with the
+    /// request. `Range` isn't part of `X-Amz-SignedHeaders`, so adding it
+    /// doesn't invalidate the signature.
+    #[instrument(skip(self))]
+    pub fn presign_download_range(
+        &self,
+        run_id: &str,
+        artifact_name: &str,
+        range: ByteRange,
+    ) -> Result<PresignedUrl, MinioError> {
+        let location = self.get_artifact_location(run_id, artifact_name);
+        let url = self.presign(&location.key, "GET", &[])?;
+
+        let mut headers = std::collections::HashMap::new();
+        headers.insert("Range".to_string(), range.header_value());
 
         Ok(PresignedUrl {
             url,
             method: "GET".to_string(),
             expires_in_secs: self.config.presign_expiry_secs,
+            headers,
+        })
+    }
+
+    /// Fetch an object (optionally a byte range of it) directly, streaming
+    /// the body rather than buffering it. Used server-side, e.g. to proxy
+    /// a download without an intermediate presigned URL.
+    ///
+    /// A `range` past the end of the object surfaces as
+    /// [`MinioError::InvalidPresign`] (S3 returns `416 Range Not
+    /// Satisfiable`); an open-ended range (`range.end` is `None`) returns
+    /// everything from `range.start` to the end of the object; a
+    /// zero-length object fetched without a range comes back as an empty
+    /// body with `content_length: 0`.
+    #[instrument(skip(self))]
+    pub async fn get_object_range(
+        &self,
+        run_id: &str,
+        artifact_name: &str,
+        range: Option<ByteRange>,
+    ) -> Result<ObjectRangeResponse, MinioError> {
+        let location = self.get_artifact_location(run_id, artifact_name);
+        let payload_hash = hex::encode(Sha256::digest(b""));
+        let (url, mut headers) = self.sign_request("GET", &location.key, &[], &payload_hash)?;
+        if let Some(range) = range {
+            headers.insert(
+                reqwest::header::RANGE,
+                reqwest::header::HeaderValue::from_str(&range.header_value())
+                    .map_err(|e| MinioError::Client(e.to_string()))?,
+            );
+        }
+
+        let response = self
+            .http
+            .get(&url)
+            .headers(headers)
+            .send()
+            .await
+            .map_err(|e| MinioError::Client(e.to_string()))?;
+
+        match response.status() {
+            StatusCode::OK | StatusCode::PARTIAL_CONTENT => {}
+            StatusCode::RANGE_NOT_SATISFIABLE => {
+                return Err(MinioError::InvalidPresign(format!(
+                    "range not satisfiable for {}",
+                    location.key
+                )));
+            }
+            status => {
+                return Err(MinioError::Client(format!("get object failed: {}", status)));
+            }
+        }
+
+        let content_length = response.content_length().unwrap_or(0);
+        let content_range = response
+            .headers()
+            .get(reqwest::header::CONTENT_RANGE)
+            .and_then(|v| v.to_str().ok())
+            .map(|s| s.to_string());
+        let body = response
+            .bytes_stream()
+            .map(|chunk| chunk.map_err(|e| MinioError::Client(e.to_string())));
+
+        Ok(ObjectRangeResponse {
+            content_length,
+            content_range,
+            body: Box::pin(body),
+        })
+    }
+
+    /// Begin a multipart upload: `POST ?uploads` against the object key,
+    /// parsing the `UploadId` S3 assigns out of the response.
+    ///
+    /// Unlike presigning, this is a real request we make ourselves (S3
+    /// hands back state we need before the caller can start uploading
+    /// parts), so it's signed with header-based SigV4 rather than the
+    /// query-string form `presign` uses.
+    #[instrument(skip(self))]
+    pub async fn initiate_multipart(
+        &self,
+        run_id: &str,
+        artifact_name: &str,
+    ) -> Result<MultipartUpload, MinioError> {
+        let location = self.get_artifact_location(run_id, artifact_name);
+        let payload_hash = hex::encode(Sha256::digest(b""));
+        let (url, headers) = self.sign_request(
+            "POST",
+            &location.key,
+            &[("uploads".to_string(), String::new())],
+            &payload_hash,
+        )?;
+
+        let response = self
+            .http
+            .post(&url)
+            .headers(headers)
+            .send()
+            .await
+            .map_err(|e| MinioError::Client(e.to_string()))?;
+        if !response.status().is_success() {
+            return Err(MinioError::Client(format!(
+                "initiate multipart upload failed: {}",
+                response.status()
+            )));
+        }
+        let body = response
+            .text()
+            .await
+            .map_err(|e| MinioError::Client(e.to_string()))?;
+        let upload_id = extract_xml_tag(&body, "UploadId")
+            .ok_or_else(|| MinioError::Client("response missing UploadId".to_string()))?;
+
+        Ok(MultipartUpload {
+            upload_id,
+            bucket: location.bucket,
+            key: location.key,
+        })
+    }
+
+    /// Generate a presigned PUT URL for uploading one part of `upload`.
+    #[instrument(skip(self))]
+    pub fn presign_part(
+        &self,
+        upload: &MultipartUpload,
+        part_number: u32,
+    ) -> Result<PresignedUrl, MinioError> {
+        let url = self.presign(
+            &upload.key,
+            "PUT",
+            &[
+                ("partNumber".to_string(), part_number.to_string()),
+                ("uploadId".to_string(), upload.upload_id.clone()),
+            ],
+        )?;
+
+        Ok(PresignedUrl {
+            url,
+            method: "PUT".to_string(),
+            expires_in_secs: self.config.presign_expiry_secs,
             headers: std::collections::HashMap::new(),
         })
     }
 
+    /// Finish a multipart upload: `POST ?uploadId=...` with a
+    /// `CompleteMultipartUpload` XML body listing every part's number and
+    /// ETag. `parts` must cover every part uploaded, in any order - S3
+    /// sorts by part number itself.
+    #[instrument(skip(self, parts))]
+    pub async fn complete_multipart(
+        &self,
+        upload: &MultipartUpload,
+        parts: Vec<(u32, String)>,
+    ) -> Result<(), MinioError> {
+        let body = complete_multipart_xml(&parts);
+        let payload_hash = hex::encode(Sha256::digest(body.as_bytes()));
+        let (url, headers) = self.sign_request(
+            "POST",
+            &upload.key,
+            &[("uploadId".to_string(), upload.upload_id.clone())],
+            &payload_hash,
+        )?;
+
+        let response = self
+            .http
+            .post(&url)
+            .headers(headers)
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| MinioError::Client(e.to_string()))?;
+        if !response.status().is_success() {
+            return Err(MinioError::Client(format!(
+                "complete multipart upload failed: {}",
+                response.status()
+            )));
+        }
+        Ok(())
+    }
+
+    /// Abort an in-progress multipart upload: `DELETE ?uploadId=...`,
+    /// releasing any parts S3 has already stored.
+    #[instrument(skip(self))]
+    pub async fn abort_multipart(&self, upload: &MultipartUpload) -> Result<(), MinioError> {
+        let payload_hash = hex::encode(Sha256::digest(b""));
+        let (url, headers) = self.sign_request(
+            "DELETE",
+            &upload.key,
+            &[("uploadId".to_string(), upload.upload_id.clone())],
+            &payload_hash,
+        )?;
+
+        let response = self
+            .http
+            .delete(&url)
+            .headers(headers)
+            .send()
+            .await
+            .map_err(|e| MinioError::Client(e.to_string()))?;
+        if !response.status().is_success() {
+            return Err(MinioError::Client(format!(
+                "abort multipart upload failed: {}",
+                response.status()
+            )));
+        }
+        Ok(())
+    }
+
+    /// Configured multipart chunk size, clamped up to
+    /// [`MIN_MULTIPART_CHUNK_SIZE_BYTES`].
+    pub fn multipart_chunk_size_bytes(&self) -> u64 {
+        self.config
+            .multipart_chunk_size_bytes
+            .max(MIN_MULTIPART_CHUNK_SIZE_BYTES)
+    }
+
+    /// Build a SigV4 query-string-presigned URL for `method` against
+    /// `key`, signed with `config.access_key`/`config.secret_key` and
+    /// valid for `config.presign_expiry_secs`. `extra_query` holds
+    /// request-specific params (e.g. `partNumber`/`uploadId` for a
+    /// multipart part) that get folded into the signed query string
+    /// alongside the `X-Amz-*` ones.
+    ///
+    /// Only `host` is in `X-Amz-SignedHeaders`: the payload hash is the
+    /// literal `UNSIGNED-PAYLOAD` (the standard choice for presigned PUT/GET
+    /// URLs, since the caller - not us - streams the body).
+    fn presign(
+        &self,
+        key: &str,
+        method: &str,
+        extra_query: &[(String, String)],
+    ) -> Result<String, MinioError> {
+        let (scheme, host) = self.split_endpoint()?;
+        let host = if self.config.path_style {
+            host.to_string()
+        } else {
+            format!("{}.{}", self.config.bucket, host)
+        };
+
+        let now = Utc::now();
+        let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let date_stamp = now.format("%Y%m%d").to_string();
+
+        let credential_scope = format!("{}/{}/s3/aws4_request", date_stamp, self.config.region);
+        let credential = format!("{}/{}", self.config.access_key, credential_scope);
+
+        let mut query_params = vec![
+            (
+                "X-Amz-Algorithm".to_string(),
+                "AWS4-HMAC-SHA256".to_string(),
+            ),
+            ("X-Amz-Credential".to_string(), credential),
+            ("X-Amz-Date".to_string(), amz_date.clone()),
+            (
+                "X-Amz-Expires".to_string(),
+                self.config.presign_expiry_secs.to_string(),
+            ),
+            ("X-Amz-SignedHeaders".to_string(), "host".to_string()),
+        ];
+        query_params.extend(extra_query.iter().cloned());
+        query_params.sort();
+
+        let canonical_query_string = query_params
+            .iter()
+            .map(|(k, v)| format!("{}={}", uri_encode(k, true), uri_encode(v, true)))
+            .collect::<Vec<_>>()
+            .join("&");
+
+        let canonical_uri = self.canonical_uri(key);
+        let canonical_headers = format!("host:{}\n", host);
+        let canonical_request = format!(
+            "{}\n{}\n{}\n{}\n{}\n{}",
+            method,
+            canonical_uri,
+            canonical_query_string,
+            canonical_headers,
+            "host",
+            "UNSIGNED-PAYLOAD"
+        );
+        let hashed_canonical_request = hex::encode(Sha256::digest(canonical_request.as_bytes()));
+
+        let string_to_sign = format!(
+            "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+            amz_date, credential_scope, hashed_canonical_request
+        );
+
+        let signing_key = self.signing_key(&date_stamp);
+        let signature = hex::encode(hmac_sha256(&signing_key, string_to_sign.as_bytes()));
+
+        Ok(format!(
+            "{}://{}{}?{}&X-Amz-Signature={}",
+            scheme, host, canonical_uri, canonical_query_string, signature
+        ))
+    }
+
+    /// Canonical URI for `key`: bucket-in-path when `path_style`, bucket as
+    /// a subdomain otherwise. Path segments are percent-encoded but `/` is
+    /// left unescaped, since it's the path separator, not part of a value.
+    fn canonical_uri(&self, key: &str) -> String {
+        let encoded_key = uri_encode(key, false);
+        if self.config.path_style {
+            format!(
+                "/{}/{}",
+                uri_encode(&self.config.bucket, false),
+                encoded_key
+            )
+        } else {
+            format!("/{}", encoded_key)
+        }
+    }
+
+    /// Split `config.endpoint` into `(scheme, host[:port])`.
+    fn split_endpoint(&self) -> Result<(&str, &str), MinioError> {
+        if let Some(host) = self.config.endpoint.strip_prefix("https://") {
+            Ok(("https", host))
+        } else if let Some(host) = self.config.endpoint.strip_prefix("http://") {
+            Ok(("http", host))
+        } else {
+            Err(MinioError::Config(format!(
+                "endpoint '{}' must start with http:// or https://",
+                self.config.endpoint
+            )))
+        }
+    }
+
+    /// Derive the SigV4 signing key via the standard
+    /// `kDate -> kRegion -> kService -> kSigning` HMAC-SHA256 chain.
+    fn signing_key(&self, date_stamp: &str) -> Vec<u8> {
+        let k_date = hmac_sha256(
+            format!("AWS4{}", self.config.secret_key).as_bytes(),
+            date_stamp.as_bytes(),
+        );
+        let k_region = hmac_sha256(&k_date, self.config.region.as_bytes());
+        let k_service = hmac_sha256(&k_region, b"s3");
+        hmac_sha256(&k_service, b"aws4_request")
+    }
+
+    /// Sign a direct (non-presigned) S3 request with header-based SigV4
+    /// auth: `initiate_multipart`/`complete_multipart`/`abort_multipart`
+    /// call S3 themselves rather than handing the caller a URL, so they
+    /// need an `Authorization` header over the actual request rather than
+    /// a signed query string. Returns the request URL and the headers to
+    /// send with it.
+    fn sign_request(
+        &self,
+        method: &str,
+        key: &str,
+        query: &[(String, String)],
+        payload_hash: &str,
+    ) -> Result<(String, reqwest::header::HeaderMap), MinioError> {
+        let (scheme, host) = self.split_endpoint()?;
+        let host = if self.config.path_style {
+            host.to_string()
+        } else {
+            format!("{}.{}", self.config.bucket, host)
+        };
+
+        let now = Utc::now();
+        let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let date_stamp = now.format("%Y%m%d").to_string();
+        let credential_scope = format!("{}/{}/s3/aws4_request", date_stamp, self.config.region);
+
+        let mut query_params = query.to_vec();
+        query_params.sort();
+        let canonical_query_string = query_params
+            .iter()
+            .map(|(k, v)| format!("{}={}", uri_encode(k, true), uri_encode(v, true)))
+            .collect::<Vec<_>>()
+            .join("&");
+
+        let canonical_uri = self.canonical_uri(key);
+        let signed_headers = "host;x-amz-content-sha256;x-amz-date";
+        let canonical_headers = format!(
+            "host:{}\nx-amz-content-sha256:{}\nx-amz-date:{}\n",
+            host, payload_hash, amz_date
+        );
+        let canonical_request = format!(
+            "{}\n{}\n{}\n{}\n{}\n{}",
+            method,
+            canonical_uri,
+            canonical_query_string,
+            canonical_headers,
+            signed_headers,
+            payload_hash
+        );
+        let hashed_canonical_request = hex::encode(Sha256::digest(canonical_request.as_bytes()));
+
+        let string_to_sign = format!(
+            "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+            amz_date, credential_scope, hashed_canonical_request
+        );
+
+        let signing_key = self.signing_key(&date_stamp);
+        let signature = hex::encode(hmac_sha256(&signing_key, string_to_sign.as_bytes()));
+
+        let authorization = format!(
+            "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+            self.config.access_key, credential_scope, signed_headers, signature
+        );
+
+        let url = if canonical_query_string.is_empty() {
+            format!("{}://{}{}", scheme, host, canonical_uri)
+        } else {
+            format!(
+                "{}://{}{}?{}",
+                scheme, host, canonical_uri, canonical_query_string
+            )
+        };
+
+        let mut headers = reqwest::header::HeaderMap::new();
+        headers.insert(
+            "x-amz-date",
+            reqwest::header::HeaderValue::from_str(&amz_date)
+                .map_err(|e| MinioError::Client(e.to_string()))?,
+        );
+        headers.insert(
+            "x-amz-content-sha256",
+            reqwest::header::HeaderValue::from_str(payload_hash)
+                .map_err(|e| MinioError::Client(e.to_string()))?,
+        );
+        headers.insert(
+            reqwest::header::AUTHORIZATION,
+            reqwest::header::HeaderValue::from_str(&authorization)
+                .map_err(|e| MinioError::Client(e.to_string()))?,
+        );
+
+        Ok((url, headers))
+    }
+
     /// Check if an artifact exists.
     #[instrument(skip(self))]
     pub async fn artifact_exists(
@@ -198,27 +753,122 @@ impl MinioClient {
         Ok(false)
     }
 
-    /// Delete an artifact.
+    /// Delete an artifact via `DELETE /{key}`. S3 delete is idempotent - a
+    /// 404 for an object that's already gone isn't treated as a failure.
     #[instrument(skip(self))]
     pub async fn delete_artifact(
         &self,
-        _run_id: &str,
-        _artifact_name: &str,
+        run_id: &str,
+        artifact_name: &str,
     ) -> Result<(), MinioError> {
-        // Placeholder: In production, use DELETE request
-        Err(MinioError::Config(
-            "Delete not implemented".to_string(),
-        ))
+        let location = self.get_artifact_location(run_id, artifact_name);
+        let payload_hash = hex::encode(Sha256::digest(b""));
+        let (url, headers) = self.sign_request("DELETE", &location.key, &[], &payload_hash)?;
+
+        let response = self
+            .http
+            .delete(&url)
+            .headers(headers)
+            .send()
+            .await
+            .map_err(|e| MinioError::Client(e.to_string()))?;
+        if response.status().is_success() || response.status() == StatusCode::NOT_FOUND {
+            Ok(())
+        } else {
+            Err(MinioError::Client(format!(
+                "delete object failed: {}",
+                response.status()
+            )))
+        }
     }
 
     /// List artifacts for a run.
     #[instrument(skip(self))]
-    pub async fn list_artifacts(
+    pub async fn list_artifacts(&self, run_id: &str) -> Result<Vec<ArtifactLocation>, MinioError> {
+        Ok(self
+            .list_artifacts_with_meta(run_id)
+            .await?
+            .into_iter()
+            .map(|meta| meta.location)
+            .collect())
+    }
+
+    /// List artifacts for a run along with the size/last-modified metadata
+    /// a lifecycle sweep needs: `GET ?list-type=2&prefix=runs/{run_id}/`,
+    /// paginated via `NextContinuationToken` until `IsTruncated` comes back
+    /// `false`.
+    #[instrument(skip(self))]
+    pub async fn list_artifacts_with_meta(
         &self,
-        _run_id: &str,
-    ) -> Result<Vec<ArtifactLocation>, MinioError> {
-        // Placeholder: In production, use LIST request with prefix
-        Ok(vec![])
+        run_id: &str,
+    ) -> Result<Vec<ArtifactObjectMeta>, MinioError> {
+        let prefix = format!("runs/{}/", run_id);
+        let mut objects = Vec::new();
+        let mut continuation_token: Option<String> = None;
+
+        loop {
+            let mut query = vec![
+                ("list-type".to_string(), "2".to_string()),
+                ("prefix".to_string(), prefix.clone()),
+                ("max-keys".to_string(), LIST_OBJECTS_MAX_KEYS.to_string()),
+            ];
+            if let Some(token) = &continuation_token {
+                query.push(("continuation-token".to_string(), token.clone()));
+            }
+
+            let payload_hash = hex::encode(Sha256::digest(b""));
+            let (url, headers) = self.sign_request("GET", "", &query, &payload_hash)?;
+
+            let response = self
+                .http
+                .get(&url)
+                .headers(headers)
+                .send()
+                .await
+                .map_err(|e| MinioError::Client(e.to_string()))?;
+            if !response.status().is_success() {
+                return Err(MinioError::Client(format!(
+                    "list objects failed: {}",
+                    response.status()
+                )));
+            }
+            let body = response
+                .text()
+                .await
+                .map_err(|e| MinioError::Client(e.to_string()))?;
+
+            for block in extract_xml_blocks(&body, "Contents") {
+                let Some(key) = extract_xml_tag(block, "Key") else {
+                    continue;
+                };
+                let size_bytes = extract_xml_tag(block, "Size")
+                    .and_then(|s| s.parse::<u64>().ok())
+                    .unwrap_or(0);
+                let last_modified = extract_xml_tag(block, "LastModified")
+                    .and_then(|s| parse_xml_datetime(&s))
+                    .unwrap_or(std::time::SystemTime::UNIX_EPOCH);
+
+                objects.push(ArtifactObjectMeta {
+                    location: ArtifactLocation {
+                        bucket: self.config.bucket.clone(),
+                        storage_url: format!("minio://{}/{}", self.config.bucket, key),
+                        key,
+                    },
+                    size_bytes,
+                    last_modified,
+                });
+            }
+
+            if extract_xml_tag(&body, "IsTruncated").as_deref() != Some("true") {
+                break;
+            }
+            continuation_token = extract_xml_tag(&body, "NextContinuationToken");
+            if continuation_token.is_none() {
+                break;
+            }
+        }
+
+        Ok(objects)
     }
 
     /// Ensure the bucket exists, creating it if necessary.
@@ -228,6 +878,44 @@ impl MinioClient {
         Ok(())
     }
 
+    /// Push `rules` down to the bucket's native S3 lifecycle
+    /// configuration via `PUT ?lifecycle`, for the subset of `rules` that
+    /// can be expressed that way (see [`lifecycle_rules_to_s3_xml`]).
+    /// Returns `Ok(())` without making a request if none can be.
+    ///
+    /// This only covers [`LifecycleRule::ExpireAfter`] rules; callers
+    /// still need [`super::artifact_backend::ArtifactStore::apply_lifecycle`]
+    /// for `KeepLast` rules, which S3 has no native concept of.
+    #[instrument(skip(self, rules))]
+    pub async fn put_bucket_lifecycle(&self, rules: &[LifecycleRule]) -> Result<(), MinioError> {
+        let Some(body) = lifecycle_rules_to_s3_xml(rules) else {
+            return Ok(());
+        };
+        let payload_hash = hex::encode(Sha256::digest(body.as_bytes()));
+        let (url, headers) = self.sign_request(
+            "PUT",
+            "",
+            &[("lifecycle".to_string(), String::new())],
+            &payload_hash,
+        )?;
+
+        let response = self
+            .http
+            .put(&url)
+            .headers(headers)
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| MinioError::Client(e.to_string()))?;
+        if !response.status().is_success() {
+            return Err(MinioError::Client(format!(
+                "put bucket lifecycle failed: {}",
+                response.status()
+            )));
+        }
+        Ok(())
+    }
+
     /// Get bucket name.
     pub fn bucket(&self) -> &str {
         &self.config.bucket
@@ -239,44 +927,184 @@ impl MinioClient {
     }
 }
 
-/// Repository for artifact storage operations.
-pub struct ArtifactStore {
-    client: MinioClient,
+/// `HMAC-SHA256(key, data)`, used both for the SigV4 signing-key chain and
+/// the final request signature.
+fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = HmacSha256::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
 }
 
-impl ArtifactStore {
-    /// Create a new artifact store.
-    pub fn new(client: MinioClient) -> Self {
-        Self { client }
+/// AWS's URI-encoding rules: percent-encode every byte except the
+/// unreserved set (`A-Z a-z 0-9 - _ . ~`). `encode_slash` controls whether
+/// `/` is escaped too - required for query string keys/values, but not for
+/// path segments, where `/` is the separator rather than part of a value.
+fn uri_encode(input: &str, encode_slash: bool) -> String {
+    let mut out = String::with_capacity(input.len());
+    for byte in input.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                out.push(byte as char)
+            }
+            b'/' if !encode_slash => out.push('/'),
+            _ => out.push_str(&format!("%{byte:02X}")),
+        }
     }
+    out
+}
 
-    /// Generate presigned upload URL for a new artifact.
-    #[instrument(skip(self))]
-    pub fn create_upload_url(
+/// Translate the [`LifecycleRule::ExpireAfter`] rules in `rules` into a
+/// `PutBucketLifecycleConfiguration` XML document, one `<Rule>` per
+/// prefix, rounding `max_age` down to whole days since that's the
+/// granularity S3 lifecycle expiration supports. Returns `None` if
+/// `rules` has no `ExpireAfter` entries - `KeepLast` has no S3 lifecycle
+/// equivalent, so a rule set made up entirely of those has nothing to
+/// push down.
+fn lifecycle_rules_to_s3_xml(rules: &[LifecycleRule]) -> Option<String> {
+    let expirations: Vec<(&str, u64)> = rules
+        .iter()
+        .filter_map(|rule| match rule {
+            LifecycleRule::ExpireAfter { prefix, max_age } => {
+                Some((prefix.as_str(), max_age.as_secs() / 86_400))
+            }
+            LifecycleRule::KeepLast { .. } => None,
+        })
+        .collect();
+    if expirations.is_empty() {
+        return None;
+    }
+
+    let mut body =
+        String::from(r#"<?xml version="1.0" encoding="UTF-8"?><LifecycleConfiguration>"#);
+    for (i, (prefix, days)) in expirations.iter().enumerate() {
+        body.push_str(&format!(
+            "<Rule><ID>mlrun-expire-{i}</ID><Status>Enabled</Status>\
+             <Filter><Prefix>{prefix}</Prefix></Filter>\
+             <Expiration><Days>{days}</Days></Expiration></Rule>"
+        ));
+    }
+    body.push_str("</LifecycleConfiguration>");
+    Some(body)
+}
+
+/// Build the `CompleteMultipartUpload` request body S3 expects: every
+/// part's number and ETag, in any order (S3 sorts by part number itself).
+fn complete_multipart_xml(parts: &[(u32, String)]) -> String {
+    let mut body =
+        String::from(r#"<?xml version="1.0" encoding="UTF-8"?><CompleteMultipartUpload>"#);
+    for (part_number, etag) in parts {
+        body.push_str(&format!(
+            "<Part><PartNumber>{}</PartNumber><ETag>{}</ETag></Part>",
+            part_number, etag
+        ));
+    }
+    body.push_str("</CompleteMultipartUpload>");
+    body
+}
+
+/// Pull the text content of `<tag>...</tag>` out of an S3 XML response.
+/// A full XML parser is overkill for extracting a single known element
+/// like `UploadId`.
+fn extract_xml_tag(xml: &str, tag: &str) -> Option<String> {
+    let open = format!("<{}>", tag);
+    let close = format!("</{}>", tag);
+    let start = xml.find(&open)? + open.len();
+    let end = start + xml[start..].find(&close)?;
+    Some(xml[start..end].to_string())
+}
+
+/// Split a `ListObjectsV2` response into each `<tag>...</tag>` block (e.g.
+/// one per `<Contents>` entry), so [`extract_xml_tag`] can be applied
+/// per-object rather than matching only the first occurrence in the whole
+/// document.
+fn extract_xml_blocks<'a>(xml: &'a str, tag: &str) -> Vec<&'a str> {
+    let open = format!("<{}>", tag);
+    let close = format!("</{}>", tag);
+    let mut blocks = Vec::new();
+    let mut rest = xml;
+    while let Some(start) = rest.find(&open) {
+        let after_open = &rest[start + open.len()..];
+        let Some(end) = after_open.find(&close) else {
+            break;
+        };
+        blocks.push(&after_open[..end]);
+        rest = &after_open[end + close.len()..];
+    }
+    blocks
+}
+
+/// Parse an S3 `LastModified` timestamp (ISO 8601 / RFC 3339, e.g.
+/// `2024-01-01T00:00:00.000Z`) into a [`std::time::SystemTime`].
+fn parse_xml_datetime(s: &str) -> Option<std::time::SystemTime> {
+    chrono::DateTime::parse_from_rfc3339(s)
+        .ok()
+        .map(|dt| dt.with_timezone(&Utc).into())
+}
+
+/// [`super::artifact_backend::ArtifactBackend`] impl backing
+/// [`super::artifact_backend::ArtifactStore`] in production: uploads/
+/// downloads go through presigned URLs rather than buffering through us,
+/// so `put`/`get` just point callers at `presign` instead.
+#[async_trait::async_trait]
+impl super::artifact_backend::ArtifactBackend for MinioClient {
+    async fn put(
         &self,
-        run_id: &str,
-        artifact_name: &str,
-        content_type: Option<&str>,
-        content_length: Option<u64>,
-    ) -> Result<(ArtifactLocation, PresignedUrl), MinioError> {
-        let location = self.client.get_artifact_location(run_id, artifact_name);
-        let presigned = self.client.presign_upload(run_id, artifact_name, content_type, content_length)?;
-        Ok((location, presigned))
+        _run_id: &str,
+        _artifact_name: &str,
+        _data: Vec<u8>,
+    ) -> Result<ArtifactLocation, MinioError> {
+        Err(MinioError::Config(
+            "S3 backend does not buffer uploads through the server; use presign(...) for a PUT URL instead"
+                .to_string(),
+        ))
     }
 
-    /// Generate presigned download URL for an artifact.
-    #[instrument(skip(self))]
-    pub fn create_download_url(
+    async fn get(&self, _run_id: &str, _artifact_name: &str) -> Result<Vec<u8>, MinioError> {
+        Err(MinioError::Config(
+            "S3 backend does not buffer downloads through the server; use presign(...) for a GET URL instead"
+                .to_string(),
+        ))
+    }
+
+    async fn head(&self, run_id: &str, artifact_name: &str) -> Result<Option<u64>, MinioError> {
+        if self.artifact_exists(run_id, artifact_name).await? {
+            // Placeholder existence check doesn't carry size; see
+            // `artifact_exists`.
+            Ok(Some(0))
+        } else {
+            Ok(None)
+        }
+    }
+
+    async fn delete(&self, run_id: &str, artifact_name: &str) -> Result<(), MinioError> {
+        self.delete_artifact(run_id, artifact_name).await
+    }
+
+    async fn list(&self, run_id: &str) -> Result<Vec<ArtifactLocation>, MinioError> {
+        self.list_artifacts(run_id).await
+    }
+
+    async fn list_with_meta(&self, run_id: &str) -> Result<Vec<ArtifactObjectMeta>, MinioError> {
+        self.list_artifacts_with_meta(run_id).await
+    }
+
+    fn presign(
         &self,
         run_id: &str,
         artifact_name: &str,
+        method: &str,
     ) -> Result<PresignedUrl, MinioError> {
-        self.client.presign_download(run_id, artifact_name)
+        match method {
+            "PUT" => self.presign_upload(run_id, artifact_name, None, None),
+            "GET" => self.presign_download(run_id, artifact_name),
+            other => Err(MinioError::InvalidPresign(format!(
+                "unsupported presign method: {other}"
+            ))),
+        }
     }
 
-    /// Get artifact location info.
-    pub fn get_location(&self, run_id: &str, artifact_name: &str) -> ArtifactLocation {
-        self.client.get_artifact_location(run_id, artifact_name)
+    fn location(&self, run_id: &str, artifact_name: &str) -> ArtifactLocation {
+        self.get_artifact_location(run_id, artifact_name)
     }
 }
 
@@ -297,7 +1125,10 @@ mod tests {
         let location = ArtifactLocation::new("mlrun-artifacts", "run-123", "model.pt");
         assert_eq!(location.bucket, "mlrun-artifacts");
         assert_eq!(location.key, "runs/run-123/model.pt");
-        assert_eq!(location.storage_url, "minio://mlrun-artifacts/runs/run-123/model.pt");
+        assert_eq!(
+            location.storage_url,
+            "minio://mlrun-artifacts/runs/run-123/model.pt"
+        );
     }
 
     #[test]
@@ -305,13 +1136,21 @@ mod tests {
         let config = MinioConfig::default();
         let client = MinioClient::new(config);
 
-        let result = client.presign_upload("run-123", "model.pt", Some("application/octet-stream"), Some(1024));
+        let result = client.presign_upload(
+            "run-123",
+            "model.pt",
+            Some("application/octet-stream"),
+            Some(1024),
+        );
         assert!(result.is_ok());
 
         let presigned = result.unwrap();
         assert_eq!(presigned.method, "PUT");
         assert!(presigned.url.contains("run-123"));
         assert!(presigned.url.contains("model.pt"));
+        assert!(presigned.url.contains("X-Amz-Algorithm=AWS4-HMAC-SHA256"));
+        assert!(presigned.url.contains("X-Amz-SignedHeaders=host"));
+        assert!(presigned.url.contains("X-Amz-Signature="));
     }
 
     #[test]
@@ -324,19 +1163,220 @@ mod tests {
 
         let presigned = result.unwrap();
         assert_eq!(presigned.method, "GET");
+        assert!(presigned.url.contains("X-Amz-Signature="));
     }
 
     #[test]
-    fn test_artifact_store() {
+    fn test_presign_path_style_puts_bucket_in_path() {
+        let config = MinioConfig {
+            path_style: true,
+            endpoint: "http://localhost:9000".to_string(),
+            ..Default::default()
+        };
+        let client = MinioClient::new(config);
+
+        let url = client
+            .presign_upload("run-123", "model.pt", None, None)
+            .unwrap()
+            .url;
+        assert!(url.starts_with("http://localhost:9000/mlrun-artifacts/runs/run-123/model.pt?"));
+    }
+
+    #[test]
+    fn test_presign_virtual_hosted_style_puts_bucket_in_host() {
+        let config = MinioConfig {
+            path_style: false,
+            endpoint: "https://s3.amazonaws.com".to_string(),
+            ..Default::default()
+        };
+        let client = MinioClient::new(config);
+
+        let url = client
+            .presign_upload("run-123", "model.pt", None, None)
+            .unwrap()
+            .url;
+        assert!(url.starts_with("https://mlrun-artifacts.s3.amazonaws.com/runs/run-123/model.pt?"));
+    }
+
+    #[test]
+    fn test_presign_url_path_is_stable_for_same_config() {
+        // The signing-key chain and canonical request only depend on
+        // access_key/secret/region/bucket and the (second-resolution)
+        // timestamp, so two presigns issued in the same second with
+        // identical config sign identically.
         let config = MinioConfig::default();
         let client = MinioClient::new(config);
-        let store = ArtifactStore::new(client);
 
-        let result = store.create_upload_url("run-123", "checkpoint.pt", None, None);
-        assert!(result.is_ok());
+        let first = client
+            .presign_upload("run-123", "model.pt", None, None)
+            .unwrap()
+            .url;
+        let second = client
+            .presign_upload("run-123", "model.pt", None, None)
+            .unwrap()
+            .url;
 
-        let (location, presigned) = result.unwrap();
-        assert_eq!(location.bucket, "mlrun-artifacts");
+        // Signatures may legitimately differ if a second boundary is
+        // crossed between calls; assert on the deterministic canonical
+        // request piece (host-style path + object key) instead.
+        assert_eq!(
+            first.split('?').next().unwrap(),
+            second.split('?').next().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_multipart_chunk_size_defaults_to_8mib() {
+        let client = MinioClient::new(MinioConfig::default());
+        assert_eq!(client.multipart_chunk_size_bytes(), 8 * 1024 * 1024);
+    }
+
+    #[test]
+    fn test_multipart_chunk_size_is_clamped_to_minimum() {
+        let config = MinioConfig {
+            multipart_chunk_size_bytes: 1024,
+            ..Default::default()
+        };
+        let client = MinioClient::new(config);
+        assert_eq!(
+            client.multipart_chunk_size_bytes(),
+            MIN_MULTIPART_CHUNK_SIZE_BYTES
+        );
+    }
+
+    #[test]
+    fn test_presign_part_includes_part_number_and_upload_id() {
+        let client = MinioClient::new(MinioConfig::default());
+        let upload = MultipartUpload {
+            upload_id: "upload-abc".to_string(),
+            bucket: "mlrun-artifacts".to_string(),
+            key: "runs/run-123/model.pt".to_string(),
+        };
+
+        let presigned = client.presign_part(&upload, 3).unwrap();
         assert_eq!(presigned.method, "PUT");
+        assert!(presigned.url.contains("partNumber=3"));
+        assert!(presigned.url.contains("uploadId=upload-abc"));
+        assert!(presigned.url.contains("X-Amz-Signature="));
+    }
+
+    #[test]
+    fn test_complete_multipart_xml_lists_every_part() {
+        let body =
+            complete_multipart_xml(&[(1, "\"etag1\"".to_string()), (2, "\"etag2\"".to_string())]);
+        assert!(body.contains("<PartNumber>1</PartNumber><ETag>\"etag1\"</ETag>"));
+        assert!(body.contains("<PartNumber>2</PartNumber><ETag>\"etag2\"</ETag>"));
+        assert!(body.starts_with("<?xml"));
+    }
+
+    #[test]
+    fn test_extract_xml_tag() {
+        let xml = "<InitiateMultipartUploadResult><UploadId>abc-123</UploadId></InitiateMultipartUploadResult>";
+        assert_eq!(
+            extract_xml_tag(xml, "UploadId"),
+            Some("abc-123".to_string())
+        );
+        assert_eq!(extract_xml_tag(xml, "Missing"), None);
+    }
+
+    #[test]
+    fn test_extract_xml_blocks_splits_each_contents_entry() {
+        let xml = "<ListBucketResult>\
+            <Contents><Key>runs/a/x.bin</Key><Size>10</Size></Contents>\
+            <Contents><Key>runs/a/y.bin</Key><Size>20</Size></Contents>\
+            </ListBucketResult>";
+
+        let blocks = extract_xml_blocks(xml, "Contents");
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(extract_xml_tag(blocks[0], "Key"), Some("runs/a/x.bin".to_string()));
+        assert_eq!(extract_xml_tag(blocks[1], "Key"), Some("runs/a/y.bin".to_string()));
+    }
+
+    #[test]
+    fn test_extract_xml_blocks_empty_when_tag_absent() {
+        assert!(extract_xml_blocks("<ListBucketResult></ListBucketResult>", "Contents").is_empty());
+    }
+
+    #[test]
+    fn test_parse_xml_datetime_roundtrips_rfc3339() {
+        let parsed = parse_xml_datetime("2024-01-01T00:00:00.000Z").unwrap();
+        let expected = std::time::SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(1_704_067_200);
+        assert_eq!(parsed, expected);
+    }
+
+    #[test]
+    fn test_parse_xml_datetime_rejects_garbage() {
+        assert!(parse_xml_datetime("not a date").is_none());
+    }
+
+    #[test]
+    fn test_byte_range_header_value() {
+        let bounded = ByteRange {
+            start: 0,
+            end: Some(1023),
+        };
+        assert_eq!(bounded.header_value(), "bytes=0-1023");
+
+        let open_ended = ByteRange {
+            start: 1024,
+            end: None,
+        };
+        assert_eq!(open_ended.header_value(), "bytes=1024-");
+    }
+
+    #[test]
+    fn test_presign_download_range_sets_range_header() {
+        let client = MinioClient::new(MinioConfig::default());
+        let range = ByteRange {
+            start: 0,
+            end: Some(1023),
+        };
+
+        let presigned = client
+            .presign_download_range("run-123", "model.pt", range)
+            .unwrap();
+        assert_eq!(presigned.method, "GET");
+        assert_eq!(
+            presigned.headers.get("Range"),
+            Some(&"bytes=0-1023".to_string())
+        );
+    }
+
+    #[test]
+    fn test_lifecycle_rules_to_s3_xml_only_covers_expire_after() {
+        let xml = lifecycle_rules_to_s3_xml(&[
+            LifecycleRule::ExpireAfter {
+                prefix: "runs/".to_string(),
+                max_age: std::time::Duration::from_secs(86_400 * 7),
+            },
+            LifecycleRule::KeepLast {
+                prefix: "runs/checkpoint-".to_string(),
+                keep: 3,
+            },
+        ])
+        .unwrap();
+
+        assert!(xml.contains("<Prefix>runs/</Prefix>"));
+        assert!(xml.contains("<Days>7</Days>"));
+        assert!(!xml.contains("checkpoint"));
+    }
+
+    #[test]
+    fn test_lifecycle_rules_to_s3_xml_none_for_keep_last_only() {
+        assert!(lifecycle_rules_to_s3_xml(&[LifecycleRule::KeepLast {
+            prefix: "runs/".to_string(),
+            keep: 1,
+        }])
+        .is_none());
+    }
+
+    #[test]
+    fn test_lifecycle_rules_to_s3_xml_rounds_max_age_down_to_whole_days() {
+        let xml = lifecycle_rules_to_s3_xml(&[LifecycleRule::ExpireAfter {
+            prefix: "runs/".to_string(),
+            max_age: std::time::Duration::from_secs(86_400 * 3 + 3600),
+        }])
+        .unwrap();
+        assert!(xml.contains("<Days>3</Days>"));
     }
 }
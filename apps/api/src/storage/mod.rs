@@ -2,15 +2,27 @@
 //!
 //! This module provides storage backends for metrics, metadata, and artifacts.
 
+pub mod artifact_backend;
 pub mod clickhouse;
+pub mod metrics_repo;
 pub mod minio;
 pub mod postgres;
+pub mod run_store;
 
+pub use artifact_backend::{ArtifactBackend, ArtifactStore, LifecycleReport, LocalFsBackend};
 pub use clickhouse::{ClickHouseClient, MetricsRepository};
-pub use minio::{ArtifactLocation, ArtifactStore, MinioClient, MinioConfig, MinioError, PresignedUrl};
+pub use metrics_repo::{InMemoryMetricsRepo, MetricsRepo, MetricsRepoError};
+pub use minio::{
+    ArtifactLocation, ArtifactObjectMeta, ByteRange, LifecycleRule, MinioClient, MinioConfig,
+    MinioError, MultipartUpload, ObjectRangeResponse, PresignedUrl, MIN_MULTIPART_CHUNK_SIZE_BYTES,
+};
 pub use postgres::{
     Artifact, ArtifactRepository, ArtifactType, CreateArtifactInput, CreateParameterInput,
-    CreateProjectInput, CreateRunInput, ListRunsFilter, Parameter, ParameterRepository,
-    ParameterValue, PostgresConfig, PostgresError, Project, ProjectRepository, Run, RunRepository,
-    RunStatus, RunSummary,
+    CreateProjectInput, CreateRunInput, Parameter, ParameterRepository, ParameterValue,
+    PostgresConfig, PostgresError, PostgresMetricsRepo, PostgresRunStore, Project,
+    ProjectRepository, Run, RunRepository, RunStatus, RunSummary,
+};
+pub use run_store::{
+    parse_run_status, run_status_as_str, BatchDelta, InMemoryRunStore, InitRunOutcome,
+    InitRunParams, ListRunsFilter, RunRecord, RunStore, RunStoreError, RunsPage,
 };
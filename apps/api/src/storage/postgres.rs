@@ -2,12 +2,31 @@
 //!
 //! Provides relational storage for projects, runs, parameters, and artifacts.
 //! See: /migrations/postgres/001_metadata_schema.sql for schema.
+//!
+//! [`PostgresRunStore`] is the durable [`super::RunStore`] backend: it owns
+//! a `deadpool`-style connection pool (via `sqlx`'s own pool) and runs the
+//! `projects`/`runs`/`metrics`/`params`/`tags` migrations in
+//! [`run_migrations`] on connect, so a fresh database self-initializes.
+//! `ProjectRepository`/`RunRepository`/etc. below remain unconnected stubs
+//! for the richer entity model a future request will wire up.
+
+use std::time::SystemTime;
 
 use serde::{Deserialize, Serialize};
+use sqlx::postgres::PgPoolOptions;
+use sqlx::PgPool;
 use thiserror::Error;
-use tracing::instrument;
+use tracing::{instrument, warn};
 use uuid::Uuid;
 
+use super::metrics_repo::{MetricsRepo, MetricsRepoError};
+use super::run_store::{
+    run_status_as_str, BatchDelta, InitRunOutcome, InitRunParams, RunRecord, RunStore,
+    RunStoreError, RunsPage,
+};
+use crate::services::metrics::{AggregatedPoint, DownsampleMethod, MetricPoint, MetricSeries};
+use mlrun_proto::mlrun::v1::RunStatus as ProtoRunStatus;
+
 /// Errors that can occur in PostgreSQL operations.
 #[derive(Error, Debug)]
 pub enum PostgresError {
@@ -61,6 +80,643 @@ impl PostgresConfig {
                 .unwrap_or(2),
         }
     }
+
+    /// Build a connection pool and run schema migrations against it, so a
+    /// fresh database self-initializes on boot.
+    pub async fn connect(&self) -> Result<PgPool, PostgresError> {
+        let pool = PgPoolOptions::new()
+            .max_connections(self.max_connections)
+            .min_connections(self.min_connections)
+            .connect(&self.url)
+            .await
+            .map_err(|e| PostgresError::Database(e.to_string()))?;
+
+        run_migrations(&pool).await?;
+
+        Ok(pool)
+    }
+}
+
+/// Create the `projects`/`runs`/`metrics`/`params`/`tags` tables if they
+/// don't already exist. Run once per [`PostgresConfig::connect`] call;
+/// `CREATE TABLE IF NOT EXISTS` makes it safe to run on every boot.
+async fn run_migrations(pool: &PgPool) -> Result<(), PostgresError> {
+    const STATEMENTS: &[&str] = &[
+        r#"CREATE TABLE IF NOT EXISTS projects (
+            id UUID PRIMARY KEY,
+            name TEXT UNIQUE NOT NULL,
+            created_at TIMESTAMPTZ NOT NULL DEFAULT now()
+        )"#,
+        r#"CREATE TABLE IF NOT EXISTS runs (
+            id TEXT PRIMARY KEY,
+            project_id UUID NOT NULL REFERENCES projects(id),
+            name TEXT,
+            status TEXT NOT NULL DEFAULT 'running',
+            metrics_count BIGINT NOT NULL DEFAULT 0,
+            params_count BIGINT NOT NULL DEFAULT 0,
+            tags JSONB NOT NULL DEFAULT '{}'::jsonb,
+            created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+            updated_at TIMESTAMPTZ NOT NULL DEFAULT now()
+        )"#,
+        r#"CREATE TABLE IF NOT EXISTS metrics (
+            id BIGSERIAL PRIMARY KEY,
+            run_id TEXT NOT NULL REFERENCES runs(id),
+            name TEXT NOT NULL,
+            step BIGINT NOT NULL,
+            value DOUBLE PRECISION NOT NULL,
+            logged_at TIMESTAMPTZ NOT NULL DEFAULT now()
+        )"#,
+        r#"CREATE TABLE IF NOT EXISTS params (
+            run_id TEXT NOT NULL REFERENCES runs(id),
+            name TEXT NOT NULL,
+            value TEXT NOT NULL,
+            PRIMARY KEY (run_id, name)
+        )"#,
+        r#"CREATE TABLE IF NOT EXISTS tags (
+            run_id TEXT NOT NULL REFERENCES runs(id),
+            key TEXT NOT NULL,
+            value TEXT NOT NULL,
+            PRIMARY KEY (run_id, key)
+        )"#,
+        "CREATE INDEX IF NOT EXISTS runs_project_id_idx ON runs (project_id)",
+        "CREATE INDEX IF NOT EXISTS metrics_run_id_idx ON metrics (run_id)",
+        "CREATE INDEX IF NOT EXISTS metrics_run_id_name_step_idx ON metrics (run_id, name, step)",
+    ];
+
+    for statement in STATEMENTS {
+        sqlx::query(statement)
+            .execute(pool)
+            .await
+            .map_err(|e| PostgresError::Database(e.to_string()))?;
+    }
+
+    Ok(())
+}
+
+/// Row shape matching a `runs` row joined against its `projects.name`.
+#[derive(sqlx::FromRow)]
+struct RunRow {
+    id: String,
+    project_name: String,
+    name: Option<String>,
+    status: String,
+    metrics_count: i64,
+    params_count: i64,
+    tags: serde_json::Value,
+    created_at: chrono::DateTime<chrono::Utc>,
+    updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl TryFrom<RunRow> for RunRecord {
+    type Error = PostgresError;
+
+    fn try_from(row: RunRow) -> Result<Self, Self::Error> {
+        Ok(RunRecord {
+            run_id: row.id,
+            project_id: row.project_name,
+            name: row.name,
+            status: parse_proto_run_status(&row.status)?,
+            metrics_count: row.metrics_count as u64,
+            params_count: row.params_count as u64,
+            tags: serde_json::from_value(row.tags).unwrap_or_default(),
+            created_at: row.created_at.into(),
+            updated_at: row.updated_at.into(),
+        })
+    }
+}
+
+fn parse_proto_run_status(s: &str) -> Result<ProtoRunStatus, PostgresError> {
+    match s {
+        "running" => Ok(ProtoRunStatus::Running),
+        "finished" => Ok(ProtoRunStatus::Finished),
+        "failed" => Ok(ProtoRunStatus::Failed),
+        "killed" => Ok(ProtoRunStatus::Killed),
+        other => Err(PostgresError::Database(format!(
+            "unknown run status in database: {other}"
+        ))),
+    }
+}
+
+const RUN_ROW_COLUMNS: &str = r#"
+    r.id, p.name AS project_name, r.name, r.status, r.metrics_count, r.params_count,
+    r.tags, r.created_at, r.updated_at
+    FROM runs r JOIN projects p ON p.id = r.project_id
+"#;
+
+/// Durable [`RunStore`] backed by PostgreSQL. Runs survive a server
+/// restart; `project_id` strings are resolved to a `projects` row via
+/// [`PostgresRunStore::get_or_create_project`] so runs carry a stable FK.
+pub struct PostgresRunStore {
+    pool: PgPool,
+}
+
+impl PostgresRunStore {
+    /// Connect to `config`'s database, running migrations before returning.
+    pub async fn connect(config: &PostgresConfig) -> Result<Self, PostgresError> {
+        let pool = config.connect().await?;
+        Ok(Self { pool })
+    }
+
+    async fn get_or_create_project(&self, name: &str) -> Result<Uuid, PostgresError> {
+        if let Some(row) = sqlx::query_scalar::<_, Uuid>("SELECT id FROM projects WHERE name = $1")
+            .bind(name)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| PostgresError::Database(e.to_string()))?
+        {
+            return Ok(row);
+        }
+
+        let id = Uuid::now_v7();
+        sqlx::query(
+            "INSERT INTO projects (id, name) VALUES ($1, $2) ON CONFLICT (name) DO NOTHING",
+        )
+        .bind(id)
+        .bind(name)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| PostgresError::Database(e.to_string()))?;
+
+        // Re-select in case a concurrent caller's insert won the race.
+        sqlx::query_scalar::<_, Uuid>("SELECT id FROM projects WHERE name = $1")
+            .bind(name)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| PostgresError::Database(e.to_string()))
+    }
+}
+
+#[async_trait::async_trait]
+impl RunStore for PostgresRunStore {
+    async fn init_run(&self, params: InitRunParams) -> InitRunOutcome {
+        let run_id = params.run_id.unwrap_or_else(|| Uuid::now_v7().to_string());
+
+        if let Some(existing) = self.get_run(&run_id).await {
+            return InitRunOutcome {
+                record: existing,
+                resumed: true,
+            };
+        }
+
+        let now = SystemTime::now();
+        let fallback_record = || RunRecord {
+            run_id: run_id.clone(),
+            project_id: params.project_id.clone(),
+            name: params.name.clone(),
+            status: ProtoRunStatus::Running,
+            metrics_count: 0,
+            params_count: 0,
+            tags: params.tags.clone(),
+            created_at: now,
+            updated_at: now,
+        };
+
+        let project_id = match self.get_or_create_project(&params.project_id).await {
+            Ok(id) => id,
+            Err(e) => {
+                warn!(error = %e, project = %params.project_id, run_id = %run_id, "Failed to resolve project; run not persisted");
+                return InitRunOutcome {
+                    record: fallback_record(),
+                    resumed: false,
+                };
+            }
+        };
+
+        let tags_json = serde_json::to_value(&params.tags).unwrap_or_default();
+
+        let insert = sqlx::query(
+            "INSERT INTO runs (id, project_id, name, status, tags) VALUES ($1, $2, $3, 'running', $4)",
+        )
+        .bind(&run_id)
+        .bind(project_id)
+        .bind(&params.name)
+        .bind(&tags_json)
+        .execute(&self.pool)
+        .await;
+
+        if let Err(e) = insert {
+            warn!(error = %e, run_id = %run_id, "Failed to persist new run");
+        }
+
+        InitRunOutcome {
+            record: fallback_record(),
+            resumed: false,
+        }
+    }
+
+    async fn ingest_batch(
+        &self,
+        run_id: &str,
+        delta: BatchDelta,
+    ) -> Result<RunRecord, RunStoreError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| RunStoreError::Backend(e.to_string()))?;
+
+        let row: RunRow = sqlx::query_as(&format!(
+            "SELECT {RUN_ROW_COLUMNS} WHERE r.id = $1 FOR UPDATE"
+        ))
+        .bind(run_id)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| RunStoreError::Backend(e.to_string()))?
+        .ok_or_else(|| RunStoreError::NotFound(run_id.to_string()))?;
+
+        let mut record: RunRecord = row
+            .try_into()
+            .map_err(|e: PostgresError| RunStoreError::Backend(e.to_string()))?;
+
+        record.metrics_count += delta.metrics;
+        record.params_count += delta.params;
+        for (key, value) in delta.upsert_tags {
+            record.tags.insert(key, value);
+        }
+        for key in delta.remove_tags {
+            record.tags.remove(&key);
+        }
+
+        let tags_json = serde_json::to_value(&record.tags).unwrap_or_default();
+
+        sqlx::query(
+            "UPDATE runs SET metrics_count = $2, params_count = $3, tags = $4, updated_at = now() WHERE id = $1",
+        )
+        .bind(run_id)
+        .bind(record.metrics_count as i64)
+        .bind(record.params_count as i64)
+        .bind(&tags_json)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| RunStoreError::Backend(e.to_string()))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| RunStoreError::Backend(e.to_string()))?;
+
+        record.updated_at = SystemTime::now();
+        Ok(record)
+    }
+
+    async fn finish_run(
+        &self,
+        run_id: &str,
+        status: ProtoRunStatus,
+    ) -> Result<RunRecord, RunStoreError> {
+        let result = sqlx::query("UPDATE runs SET status = $2, updated_at = now() WHERE id = $1")
+            .bind(run_id)
+            .bind(run_status_as_str(status))
+            .execute(&self.pool)
+            .await
+            .map_err(|e| RunStoreError::Backend(e.to_string()))?;
+
+        if result.rows_affected() == 0 {
+            return Err(RunStoreError::NotFound(run_id.to_string()));
+        }
+
+        self.get_run(run_id)
+            .await
+            .ok_or_else(|| RunStoreError::NotFound(run_id.to_string()))
+    }
+
+    async fn list_runs(&self, filter: super::run_store::ListRunsFilter) -> RunsPage {
+        let status_str = filter.status.map(run_status_as_str);
+
+        let rows: Result<Vec<RunRow>, sqlx::Error> = sqlx::query_as(&format!(
+            "SELECT {RUN_ROW_COLUMNS} WHERE ($1::text IS NULL OR p.name = $1) AND ($2::text IS NULL OR r.status = $2) ORDER BY r.created_at DESC",
+        ))
+        .bind(&filter.project)
+        .bind(status_str)
+        .fetch_all(&self.pool)
+        .await;
+
+        let rows = match rows {
+            Ok(rows) => rows,
+            Err(e) => {
+                warn!(error = %e, "Failed to list runs");
+                return RunsPage {
+                    runs: vec![],
+                    total: 0,
+                };
+            }
+        };
+
+        let total = rows.len();
+        let runs = rows
+            .into_iter()
+            .filter_map(|row| RunRecord::try_from(row).ok())
+            .skip(filter.offset)
+            .take(filter.limit)
+            .collect();
+
+        RunsPage { runs, total }
+    }
+
+    async fn get_run(&self, run_id: &str) -> Option<RunRecord> {
+        let row: RunRow = sqlx::query_as(&format!("SELECT {RUN_ROW_COLUMNS} WHERE r.id = $1"))
+            .bind(run_id)
+            .fetch_optional(&self.pool)
+            .await
+            .inspect_err(|e| warn!(error = %e, run_id = %run_id, "Failed to fetch run"))
+            .ok()
+            .flatten()?;
+
+        row.try_into().ok()
+    }
+}
+
+/// Durable [`MetricsRepo`] backed by PostgreSQL, storing points in the
+/// same `metrics` table [`run_migrations`] creates (`run_id, name, step,
+/// value, logged_at`, indexed on `(run_id, name, step)`). Step-range
+/// filtering and bucket aggregation are pushed down into SQL via `WHERE
+/// step BETWEEN` and `width_bucket(...)` rather than fetched and
+/// downsampled in Rust, so a query over a long run doesn't have to pull
+/// every point across the wire.
+pub struct PostgresMetricsRepo {
+    pool: PgPool,
+}
+
+impl PostgresMetricsRepo {
+    /// Connect to `config`'s database, running migrations before returning.
+    pub async fn connect(config: &PostgresConfig) -> Result<Self, PostgresError> {
+        let pool = config.connect().await?;
+        Ok(Self { pool })
+    }
+
+    /// Query a single metric's series, aggregating in SQL once the point
+    /// count exceeds `max_points` rather than pulling every row.
+    async fn query_series(
+        &self,
+        run_id: &str,
+        name: &str,
+        max_points: usize,
+        start_step: Option<i64>,
+        end_step: Option<i64>,
+        method: DownsampleMethod,
+    ) -> Result<MetricSeries, MetricsRepoError> {
+        #[derive(sqlx::FromRow)]
+        struct Bounds {
+            total: i64,
+            min_step: Option<i64>,
+            max_step: Option<i64>,
+        }
+
+        let bounds: Bounds = sqlx::query_as(
+            "SELECT count(*) AS total, min(step) AS min_step, max(step) AS max_step \
+             FROM metrics WHERE run_id = $1 AND name = $2 \
+             AND ($3::bigint IS NULL OR step >= $3) AND ($4::bigint IS NULL OR step <= $4)",
+        )
+        .bind(run_id)
+        .bind(name)
+        .bind(start_step)
+        .bind(end_step)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| MetricsRepoError::Query {
+            query: "select metrics bounds",
+            source: e,
+        })?;
+
+        let total_points = bounds.total as usize;
+        if total_points == 0 {
+            return Ok(MetricSeries {
+                name: name.to_string(),
+                points: vec![],
+                total_points: 0,
+                downsampled: false,
+            });
+        }
+
+        if total_points <= max_points {
+            #[derive(sqlx::FromRow)]
+            struct Row {
+                step: i64,
+                value: f64,
+            }
+
+            let rows: Vec<Row> = sqlx::query_as(
+                "SELECT step, value FROM metrics WHERE run_id = $1 AND name = $2 \
+                 AND ($3::bigint IS NULL OR step >= $3) AND ($4::bigint IS NULL OR step <= $4) \
+                 ORDER BY step",
+            )
+            .bind(run_id)
+            .bind(name)
+            .bind(start_step)
+            .bind(end_step)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| MetricsRepoError::Query {
+                query: "select metrics rows",
+                source: e,
+            })?;
+
+            let points = rows
+                .into_iter()
+                .map(|r| AggregatedPoint {
+                    step: r.step,
+                    mean: r.value,
+                    min: r.value,
+                    max: r.value,
+                    count: 1,
+                })
+                .collect();
+
+            return Ok(MetricSeries {
+                name: name.to_string(),
+                points,
+                total_points,
+                downsampled: false,
+            });
+        }
+
+        // LTTB needs the actual raw points to pick from (it can't be
+        // expressed as a SQL aggregate), so fetch everything in range and
+        // run the same algorithm `RunMetrics::query` uses in-process.
+        if method == DownsampleMethod::Lttb {
+            #[derive(sqlx::FromRow)]
+            struct Row {
+                step: i64,
+                value: f64,
+            }
+
+            let rows: Vec<Row> = sqlx::query_as(
+                "SELECT step, value FROM metrics WHERE run_id = $1 AND name = $2 \
+                 AND ($3::bigint IS NULL OR step >= $3) AND ($4::bigint IS NULL OR step <= $4) \
+                 ORDER BY step",
+            )
+            .bind(run_id)
+            .bind(name)
+            .bind(start_step)
+            .bind(end_step)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| MetricsRepoError::Query {
+                query: "select metrics rows for lttb",
+                source: e,
+            })?;
+
+            let raw_points: Vec<MetricPoint> = rows
+                .into_iter()
+                .map(|r| MetricPoint {
+                    name: name.to_string(),
+                    step: r.step,
+                    value: r.value,
+                    timestamp: None,
+                })
+                .collect();
+
+            return Ok(MetricSeries {
+                name: name.to_string(),
+                points: crate::services::metrics::lttb_downsample(&raw_points, max_points),
+                total_points,
+                downsampled: true,
+            });
+        }
+
+        #[derive(sqlx::FromRow)]
+        struct Bucket {
+            bucket_step: i64,
+            mean: f64,
+            min_value: f64,
+            max_value: f64,
+            cnt: i64,
+        }
+
+        let bucket_count = max_points.max(1) as i32;
+        let buckets: Vec<Bucket> = sqlx::query_as(
+            "SELECT min(step) AS bucket_step, avg(value) AS mean, min(value) AS min_value, \
+             max(value) AS max_value, count(*) AS cnt \
+             FROM metrics \
+             WHERE run_id = $1 AND name = $2 \
+             AND ($3::bigint IS NULL OR step >= $3) AND ($4::bigint IS NULL OR step <= $4) \
+             GROUP BY width_bucket(step, $5::bigint, $6::bigint + 1, $7::int) \
+             ORDER BY bucket_step",
+        )
+        .bind(run_id)
+        .bind(name)
+        .bind(start_step)
+        .bind(end_step)
+        .bind(bounds.min_step)
+        .bind(bounds.max_step)
+        .bind(bucket_count)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| MetricsRepoError::Query {
+            query: "select metrics buckets",
+            source: e,
+        })?;
+
+        let points = buckets
+            .into_iter()
+            .map(|b| AggregatedPoint {
+                step: b.bucket_step,
+                mean: b.mean,
+                min: b.min_value,
+                max: b.max_value,
+                count: b.cnt as usize,
+            })
+            .collect();
+
+        Ok(MetricSeries {
+            name: name.to_string(),
+            points,
+            total_points,
+            downsampled: true,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl MetricsRepo for PostgresMetricsRepo {
+    async fn add_points(
+        &self,
+        run_id: &str,
+        points: Vec<MetricPoint>,
+    ) -> Result<(), MetricsRepoError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| MetricsRepoError::Query {
+                query: "begin add_points transaction",
+                source: e,
+            })?;
+
+        for point in &points {
+            let logged_at = point
+                .timestamp
+                .and_then(|secs| chrono::DateTime::from_timestamp(secs as i64, 0));
+
+            sqlx::query(
+                "INSERT INTO metrics (run_id, name, step, value, logged_at) \
+                 VALUES ($1, $2, $3, $4, COALESCE($5, now()))",
+            )
+            .bind(run_id)
+            .bind(&point.name)
+            .bind(point.step)
+            .bind(point.value)
+            .bind(logged_at)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| MetricsRepoError::Query {
+                query: "insert into metrics",
+                source: e,
+            })?;
+        }
+
+        tx.commit().await.map_err(|e| MetricsRepoError::Query {
+            query: "commit add_points transaction",
+            source: e,
+        })
+    }
+
+    async fn query(
+        &self,
+        run_id: &str,
+        names: &[String],
+        max_points: usize,
+        start_step: Option<i64>,
+        end_step: Option<i64>,
+        method: DownsampleMethod,
+    ) -> Result<Vec<MetricSeries>, MetricsRepoError> {
+        let query_names = if names.is_empty() {
+            self.metric_names(run_id).await?
+        } else {
+            names.to_vec()
+        };
+
+        let mut series = Vec::with_capacity(query_names.len());
+        for name in query_names {
+            series.push(
+                self.query_series(run_id, &name, max_points, start_step, end_step, method)
+                    .await?,
+            );
+        }
+        Ok(series)
+    }
+
+    async fn metric_names(&self, run_id: &str) -> Result<Vec<String>, MetricsRepoError> {
+        sqlx::query_scalar::<_, String>(
+            "SELECT DISTINCT name FROM metrics WHERE run_id = $1 ORDER BY name",
+        )
+        .bind(run_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| MetricsRepoError::Query {
+            query: "select distinct metric names",
+            source: e,
+        })
+    }
+
+    async fn list_runs(&self) -> Result<Vec<String>, MetricsRepoError> {
+        sqlx::query_scalar::<_, String>("SELECT DISTINCT run_id FROM metrics ORDER BY run_id")
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| MetricsRepoError::Query {
+                query: "select distinct metric run ids",
+                source: e,
+            })
+    }
 }
 
 /// Run status enum matching PostgreSQL enum.
@@ -167,15 +823,9 @@ impl Parameter {
     pub fn value_as_string(&self) -> String {
         match self.value_type.as_str() {
             "string" => self.value_string.clone().unwrap_or_default(),
-            "float" => self
-                .value_float
-                .map(|v| v.to_string())
-                .unwrap_or_default(),
+            "float" => self.value_float.map(|v| v.to_string()).unwrap_or_default(),
             "int" => self.value_int.map(|v| v.to_string()).unwrap_or_default(),
-            "bool" => self
-                .value_bool
-                .map(|v| v.to_string())
-                .unwrap_or_default(),
+            "bool" => self.value_bool.map(|v| v.to_string()).unwrap_or_default(),
             "json" => self
                 .value_json
                 .as_ref()
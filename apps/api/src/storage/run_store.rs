@@ -0,0 +1,346 @@
+//! `RunStore` trait: storage-agnostic run lifecycle tracking.
+//!
+//! Mirrors the `ApiKeyStore` split in `auth`: an in-memory backend for
+//! alpha/dev (the default) and a PostgreSQL-backed one for production,
+//! selected via `RUN_STORE_BACKEND` (see [`crate::config::RunStoreBackend`]).
+//! Both the HTTP handlers and the gRPC `IngestServiceImpl` call this trait
+//! instead of touching a run hashmap directly, so runs survive a server
+//! restart once Postgres is configured.
+
+use std::collections::HashMap;
+use std::time::SystemTime;
+
+use thiserror::Error;
+use tokio::sync::RwLock;
+
+use mlrun_proto::mlrun::v1::RunStatus;
+
+/// Errors returned by a `RunStore` implementation.
+#[derive(Error, Debug)]
+pub enum RunStoreError {
+    #[error("run not found: {0}")]
+    NotFound(String),
+    #[error("backend error: {0}")]
+    Backend(String),
+}
+
+/// A run's durable metadata, independent of storage backend.
+#[derive(Debug, Clone)]
+pub struct RunRecord {
+    pub run_id: String,
+    pub project_id: String,
+    pub name: Option<String>,
+    pub status: RunStatus,
+    pub metrics_count: u64,
+    pub params_count: u64,
+    pub tags: HashMap<String, String>,
+    pub created_at: SystemTime,
+    pub updated_at: SystemTime,
+}
+
+/// Inputs to [`RunStore::init_run`].
+#[derive(Debug, Clone, Default)]
+pub struct InitRunParams {
+    pub run_id: Option<String>,
+    pub project_id: String,
+    pub name: Option<String>,
+    pub tags: HashMap<String, String>,
+}
+
+/// Result of [`RunStore::init_run`]: whether an existing run was returned
+/// rather than a new one created (SDK resume semantics).
+#[derive(Debug, Clone)]
+pub struct InitRunOutcome {
+    pub record: RunRecord,
+    pub resumed: bool,
+}
+
+/// Delta applied to a run by [`RunStore::ingest_batch`].
+#[derive(Debug, Clone, Default)]
+pub struct BatchDelta {
+    pub metrics: u64,
+    pub params: u64,
+    pub upsert_tags: Vec<(String, String)>,
+    pub remove_tags: Vec<String>,
+}
+
+/// Filters for [`RunStore::list_runs`].
+#[derive(Debug, Clone, Default)]
+pub struct ListRunsFilter {
+    pub project: Option<String>,
+    pub status: Option<RunStatus>,
+    pub limit: usize,
+    pub offset: usize,
+}
+
+/// A page of [`RunStore::list_runs`] results, with the total match count
+/// before pagination so callers can render "showing X of Y".
+#[derive(Debug, Clone)]
+pub struct RunsPage {
+    pub runs: Vec<RunRecord>,
+    pub total: usize,
+}
+
+/// Storage backend for run lifecycle metadata.
+///
+/// Implementations must be safe to share behind an `Arc` and called
+/// concurrently by every HTTP/gRPC handler.
+#[async_trait::async_trait]
+pub trait RunStore: Send + Sync {
+    /// Create a run, or return the existing one if `run_id` already exists
+    /// (idempotent, matching SDK resume semantics).
+    async fn init_run(&self, params: InitRunParams) -> InitRunOutcome;
+
+    /// Apply an ingestion delta to a run (metric/param counts, tag upserts
+    /// and removals), bumping `updated_at`.
+    async fn ingest_batch(
+        &self,
+        run_id: &str,
+        delta: BatchDelta,
+    ) -> Result<RunRecord, RunStoreError>;
+
+    /// Transition a run to a terminal status, bumping `updated_at`.
+    async fn finish_run(&self, run_id: &str, status: RunStatus)
+        -> Result<RunRecord, RunStoreError>;
+
+    /// List runs matching `filter`, newest first, paginated.
+    async fn list_runs(&self, filter: ListRunsFilter) -> RunsPage;
+
+    /// Fetch a single run by ID.
+    async fn get_run(&self, run_id: &str) -> Option<RunRecord>;
+}
+
+/// Render a [`RunStatus`] the way it's stored/displayed across the API
+/// (lowercase, with `_` falling back to `"pending"` for not-yet-started
+/// statuses the HTTP/gRPC surface doesn't otherwise distinguish).
+pub fn run_status_as_str(status: RunStatus) -> &'static str {
+    match status {
+        RunStatus::Running => "running",
+        RunStatus::Finished => "finished",
+        RunStatus::Failed => "failed",
+        RunStatus::Killed => "killed",
+        _ => "pending",
+    }
+}
+
+/// Inverse of [`run_status_as_str`]. Returns `None` for anything that isn't
+/// one of the four statuses the API surface accepts.
+pub fn parse_run_status(s: &str) -> Option<RunStatus> {
+    match s {
+        "running" => Some(RunStatus::Running),
+        "finished" => Some(RunStatus::Finished),
+        "failed" => Some(RunStatus::Failed),
+        "killed" => Some(RunStatus::Killed),
+        _ => None,
+    }
+}
+
+/// In-memory `RunStore` implementation for alpha development and tests.
+/// In production, use [`super::postgres::PostgresRunStore`] instead.
+#[derive(Debug, Default)]
+pub struct InMemoryRunStore {
+    runs: RwLock<HashMap<String, RunRecord>>,
+}
+
+impl InMemoryRunStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait::async_trait]
+impl RunStore for InMemoryRunStore {
+    async fn init_run(&self, params: InitRunParams) -> InitRunOutcome {
+        let run_id = params
+            .run_id
+            .unwrap_or_else(|| uuid::Uuid::now_v7().to_string());
+
+        let mut runs = self.runs.write().await;
+
+        if let Some(existing) = runs.get(&run_id) {
+            return InitRunOutcome {
+                record: existing.clone(),
+                resumed: true,
+            };
+        }
+
+        let now = SystemTime::now();
+        let record = RunRecord {
+            run_id: run_id.clone(),
+            project_id: params.project_id,
+            name: params.name,
+            status: RunStatus::Running,
+            metrics_count: 0,
+            params_count: 0,
+            tags: params.tags,
+            created_at: now,
+            updated_at: now,
+        };
+
+        runs.insert(run_id, record.clone());
+        InitRunOutcome {
+            record,
+            resumed: false,
+        }
+    }
+
+    async fn ingest_batch(
+        &self,
+        run_id: &str,
+        delta: BatchDelta,
+    ) -> Result<RunRecord, RunStoreError> {
+        let mut runs = self.runs.write().await;
+        let record = runs
+            .get_mut(run_id)
+            .ok_or_else(|| RunStoreError::NotFound(run_id.to_string()))?;
+
+        record.metrics_count += delta.metrics;
+        record.params_count += delta.params;
+        for (key, value) in delta.upsert_tags {
+            record.tags.insert(key, value);
+        }
+        for key in delta.remove_tags {
+            record.tags.remove(&key);
+        }
+        record.updated_at = SystemTime::now();
+
+        Ok(record.clone())
+    }
+
+    async fn finish_run(
+        &self,
+        run_id: &str,
+        status: RunStatus,
+    ) -> Result<RunRecord, RunStoreError> {
+        let mut runs = self.runs.write().await;
+        let record = runs
+            .get_mut(run_id)
+            .ok_or_else(|| RunStoreError::NotFound(run_id.to_string()))?;
+
+        record.status = status;
+        record.updated_at = SystemTime::now();
+
+        Ok(record.clone())
+    }
+
+    async fn list_runs(&self, filter: ListRunsFilter) -> RunsPage {
+        let runs = self.runs.read().await;
+
+        let mut filtered: Vec<RunRecord> = runs
+            .values()
+            .filter(|r| filter.project.as_ref().map_or(true, |p| &r.project_id == p))
+            .filter(|r| filter.status.map_or(true, |s| r.status == s))
+            .cloned()
+            .collect();
+
+        filtered.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        let total = filtered.len();
+
+        let runs = filtered
+            .into_iter()
+            .skip(filter.offset)
+            .take(filter.limit)
+            .collect();
+
+        RunsPage { runs, total }
+    }
+
+    async fn get_run(&self, run_id: &str) -> Option<RunRecord> {
+        self.runs.read().await.get(run_id).cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_init_run_is_idempotent() {
+        let store = InMemoryRunStore::new();
+
+        let first = store
+            .init_run(InitRunParams {
+                run_id: Some("run-1".to_string()),
+                project_id: "proj".to_string(),
+                name: None,
+                tags: HashMap::new(),
+            })
+            .await;
+        assert!(!first.resumed);
+
+        let second = store
+            .init_run(InitRunParams {
+                run_id: Some("run-1".to_string()),
+                project_id: "proj".to_string(),
+                name: None,
+                tags: HashMap::new(),
+            })
+            .await;
+        assert!(second.resumed);
+        assert_eq!(second.record.run_id, "run-1");
+    }
+
+    #[tokio::test]
+    async fn test_ingest_batch_unknown_run_errors() {
+        let store = InMemoryRunStore::new();
+        let result = store.ingest_batch("missing", BatchDelta::default()).await;
+        assert!(matches!(result, Err(RunStoreError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_ingest_batch_applies_delta() {
+        let store = InMemoryRunStore::new();
+        store
+            .init_run(InitRunParams {
+                run_id: Some("run-1".to_string()),
+                project_id: "proj".to_string(),
+                name: None,
+                tags: HashMap::new(),
+            })
+            .await;
+
+        let record = store
+            .ingest_batch(
+                "run-1",
+                BatchDelta {
+                    metrics: 3,
+                    params: 1,
+                    upsert_tags: vec![("k".to_string(), "v".to_string())],
+                    remove_tags: vec![],
+                },
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(record.metrics_count, 3);
+        assert_eq!(record.params_count, 1);
+        assert_eq!(record.tags.get("k"), Some(&"v".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_list_runs_filters_and_paginates() {
+        let store = InMemoryRunStore::new();
+        for i in 0..3 {
+            store
+                .init_run(InitRunParams {
+                    run_id: Some(format!("run-{i}")),
+                    project_id: "proj".to_string(),
+                    name: None,
+                    tags: HashMap::new(),
+                })
+                .await;
+        }
+
+        let page = store
+            .list_runs(ListRunsFilter {
+                project: Some("proj".to_string()),
+                status: None,
+                limit: 2,
+                offset: 0,
+            })
+            .await;
+
+        assert_eq!(page.total, 3);
+        assert_eq!(page.runs.len(), 2);
+    }
+}
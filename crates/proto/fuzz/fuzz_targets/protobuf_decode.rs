@@ -0,0 +1,19 @@
+//! Fuzzes `prost` decoding of arbitrary bytes as `mlrun.v1` request
+//! messages. Malformed or truncated wire-format input must only ever
+//! produce a `DecodeError`, never panic.
+//!
+//! Run via `cargo fuzz run protobuf_decode` from `crates/proto/fuzz`.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use prost::Message;
+
+use mlrun_proto::mlrun::v1::{InitRunRequest, LogMetricsRequest, LogParamsRequest, LogTagsRequest};
+
+fuzz_target!(|data: &[u8]| {
+    let _ = InitRunRequest::decode(data);
+    let _ = LogMetricsRequest::decode(data);
+    let _ = LogParamsRequest::decode(data);
+    let _ = LogTagsRequest::decode(data);
+});
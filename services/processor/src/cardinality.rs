@@ -0,0 +1,221 @@
+//! Per-run series cardinality guard.
+//!
+//! `apps-api`'s `CardinalityTracker` limits distinct tag keys/metric names
+//! *before* a batch is accepted into storage. This guard runs downstream,
+//! inside the processor, and tracks something finer-grained: distinct
+//! *series* - a (metric name, tag set) pair - per run, so a runaway training
+//! loop that logs a bounded number of metric names but an unbounded number
+//! of tag combinations is caught even when every individual batch passes
+//! the ingest-time limits. Rollup tiers themselves key their buckets by
+//! metric name and bucket start only; a point's tags are not part of a
+//! [`RollupTile`](crate::rollup::RollupTile).
+
+use std::collections::{HashMap, HashSet};
+
+use tokio::sync::RwLock;
+use tracing::warn;
+
+/// Outcome of [`CardinalityGuard::check_and_register`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum SeriesGuardResult {
+    /// The series was already known, or newly registered within budget.
+    Accepted { distinct_series: usize },
+    /// The run's series budget is exhausted; the series was not
+    /// registered.
+    Rejected {
+        distinct_series: usize,
+        limit: usize,
+    },
+}
+
+impl SeriesGuardResult {
+    /// Returns true if the series may be rolled up.
+    pub fn is_accepted(&self) -> bool {
+        matches!(self, Self::Accepted { .. })
+    }
+}
+
+/// Tracks the set of distinct (metric name, tag set) series seen per run,
+/// refusing to register new series once a run's budget is exhausted.
+#[derive(Debug)]
+pub struct CardinalityGuard {
+    limit: usize,
+    series: RwLock<HashMap<String, HashSet<String>>>,
+}
+
+impl CardinalityGuard {
+    /// Create a guard that allows at most `limit` distinct series per run.
+    pub fn new(limit: usize) -> Self {
+        Self {
+            limit,
+            series: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Register `(metric_name, tags)` for `run_id` if it's already known or
+    /// the run is still under budget; refuse it otherwise.
+    pub async fn check_and_register(
+        &self,
+        run_id: &str,
+        metric_name: &str,
+        tags: &[(String, String)],
+    ) -> SeriesGuardResult {
+        let key = series_key(metric_name, tags);
+        let mut series = self.series.write().await;
+        let run_series = series.entry(run_id.to_string()).or_default();
+
+        if run_series.contains(&key) {
+            return SeriesGuardResult::Accepted {
+                distinct_series: run_series.len(),
+            };
+        }
+
+        if run_series.len() >= self.limit {
+            warn!(
+                run_id = %run_id,
+                metric_name = %metric_name,
+                distinct_series = run_series.len(),
+                limit = self.limit,
+                "Series cardinality budget exceeded, refusing new series"
+            );
+            return SeriesGuardResult::Rejected {
+                distinct_series: run_series.len(),
+                limit: self.limit,
+            };
+        }
+
+        run_series.insert(key);
+        SeriesGuardResult::Accepted {
+            distinct_series: run_series.len(),
+        }
+    }
+
+    /// Current distinct-series count for `run_id` (`0` if unseen).
+    pub async fn distinct_series(&self, run_id: &str) -> usize {
+        self.series
+            .read()
+            .await
+            .get(run_id)
+            .map(|s| s.len())
+            .unwrap_or(0)
+    }
+
+    /// Drop tracking for a run (e.g. once it finishes).
+    pub async fn clear_run(&self, run_id: &str) {
+        self.series.write().await.remove(run_id);
+    }
+}
+
+/// Canonical key for a (metric name, tag set) series, order-independent in
+/// the tags.
+fn series_key(metric_name: &str, tags: &[(String, String)]) -> String {
+    let mut sorted: Vec<&(String, String)> = tags.iter().collect();
+    sorted.sort();
+
+    let mut key = String::from(metric_name);
+    for (k, v) in sorted {
+        key.push('\u{1}');
+        key.push_str(k);
+        key.push('=');
+        key.push_str(v);
+    }
+    key
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tags(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn same_series_reaccepted_without_counting_twice() {
+        let guard = CardinalityGuard::new(2);
+
+        let first = guard
+            .check_and_register("run-1", "loss", &tags(&[("env", "prod")]))
+            .await;
+        assert_eq!(first, SeriesGuardResult::Accepted { distinct_series: 1 });
+
+        let second = guard
+            .check_and_register("run-1", "loss", &tags(&[("env", "prod")]))
+            .await;
+        assert_eq!(second, SeriesGuardResult::Accepted { distinct_series: 1 });
+    }
+
+    #[tokio::test]
+    async fn tag_order_does_not_create_distinct_series() {
+        let guard = CardinalityGuard::new(5);
+
+        guard
+            .check_and_register("run-1", "loss", &tags(&[("a", "1"), ("b", "2")]))
+            .await;
+        let result = guard
+            .check_and_register("run-1", "loss", &tags(&[("b", "2"), ("a", "1")]))
+            .await;
+
+        assert_eq!(result, SeriesGuardResult::Accepted { distinct_series: 1 });
+    }
+
+    #[tokio::test]
+    async fn guard_trips_at_configured_threshold() {
+        let guard = CardinalityGuard::new(2);
+
+        let a = guard
+            .check_and_register("run-1", "loss", &tags(&[("shard", "a")]))
+            .await;
+        let b = guard
+            .check_and_register("run-1", "loss", &tags(&[("shard", "b")]))
+            .await;
+        assert!(a.is_accepted());
+        assert!(b.is_accepted());
+
+        let c = guard
+            .check_and_register("run-1", "loss", &tags(&[("shard", "c")]))
+            .await;
+        assert_eq!(
+            c,
+            SeriesGuardResult::Rejected {
+                distinct_series: 2,
+                limit: 2,
+            }
+        );
+        assert!(!c.is_accepted());
+
+        assert_eq!(guard.distinct_series("run-1").await, 2);
+    }
+
+    #[tokio::test]
+    async fn budgets_are_independent_per_run() {
+        let guard = CardinalityGuard::new(1);
+
+        guard
+            .check_and_register("run-1", "loss", &tags(&[("shard", "a")]))
+            .await;
+        let other_run = guard
+            .check_and_register("run-2", "loss", &tags(&[("shard", "a")]))
+            .await;
+
+        assert!(other_run.is_accepted());
+    }
+
+    #[tokio::test]
+    async fn clear_run_resets_budget() {
+        let guard = CardinalityGuard::new(1);
+
+        guard
+            .check_and_register("run-1", "loss", &tags(&[("shard", "a")]))
+            .await;
+        guard.clear_run("run-1").await;
+
+        let result = guard
+            .check_and_register("run-1", "loss", &tags(&[("shard", "b")]))
+            .await;
+        assert!(result.is_accepted());
+    }
+}
@@ -1,11 +1,15 @@
-<<<<<<< HEAD
-fn main() {
-    println!("track-processor");
-=======
-use std::time::Duration;
+mod cardinality;
+mod processor;
+mod rollup;
+
+use std::sync::Arc;
+
 use tracing::info;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
+use processor::{Processor, ProcessorConfig};
+use rollup::LoggingRollupSink;
+
 #[tokio::main]
 async fn main() {
     // Initialize tracing
@@ -17,10 +21,8 @@ async fn main() {
     info!("Starting MLRun Processor v0.1.0");
     info!("Background processor for rollups, downsampling, and cardinality guards");
 
-    // Main processing loop (placeholder)
-    loop {
-        info!("Processor heartbeat - no work yet");
-        tokio::time::sleep(Duration::from_secs(60)).await;
-    }
->>>>>>> de683b6 (feat(core-001): complete monorepo scaffold)
+    let config = ProcessorConfig::from_env();
+    let processor = Processor::new(config, Arc::new(LoggingRollupSink));
+
+    processor.run().await;
 }
@@ -0,0 +1,261 @@
+//! The background rollup/downsampling/cardinality-guard subsystem.
+//!
+//! Points enter only through [`Processor::observe_point`]. The `processor`
+//! binary starts [`Processor::run`] without any point source: nothing in
+//! this crate reads `apps-api`'s ingested-batch or idempotency metadata, so
+//! points are rolled up only when a caller hands them in. Each
+//! point first passes the [`CardinalityGuard`](crate::cardinality::CardinalityGuard),
+//! which refuses to register a series once a run's budget is exhausted;
+//! accepted points are buffered until the next [`Processor::tick`], which
+//! aggregates them into fixed-window [`RollupTile`](crate::rollup::RollupTile)s
+//! and hands them to the configured [`RollupSink`](crate::rollup::RollupSink).
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::RwLock;
+use tracing::debug;
+
+use crate::cardinality::{CardinalityGuard, SeriesGuardResult};
+use crate::rollup::{aggregate_tier, RawPoint, RollupSink, RollupTier};
+
+/// Configuration for [`Processor::new`].
+#[derive(Debug, Clone)]
+pub struct ProcessorConfig {
+    /// How often [`Processor::run`] calls [`Processor::tick`].
+    pub tick_interval: Duration,
+    /// Maximum distinct (metric name, tag set) series per run, enforced by
+    /// the [`CardinalityGuard`].
+    pub max_series_per_run: usize,
+}
+
+impl Default for ProcessorConfig {
+    fn default() -> Self {
+        Self {
+            tick_interval: Duration::from_secs(10),
+            max_series_per_run: 1000,
+        }
+    }
+}
+
+impl ProcessorConfig {
+    /// Build config from environment variables, falling back to defaults.
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+
+        if let Ok(val) = std::env::var("MLRUN_PROCESSOR_TICK_INTERVAL_SECS") {
+            if let Ok(secs) = val.parse() {
+                config.tick_interval = Duration::from_secs(secs);
+            }
+        }
+
+        if let Ok(val) = std::env::var("MLRUN_PROCESSOR_MAX_SERIES_PER_RUN") {
+            if let Ok(n) = val.parse() {
+                config.max_series_per_run = n;
+            }
+        }
+
+        config
+    }
+}
+
+/// Pending points buffered between ticks, and the per-run rollup
+/// watermark (the latest point timestamp that has been rolled up).
+#[derive(Debug, Default)]
+struct RollupEngine {
+    pending: RwLock<HashMap<String, Vec<RawPoint>>>,
+    watermark: RwLock<HashMap<String, i64>>,
+}
+
+impl RollupEngine {
+    fn new() -> Self {
+        Self::default()
+    }
+
+    async fn ingest(&self, run_id: &str, point: RawPoint) {
+        self.pending
+            .write()
+            .await
+            .entry(run_id.to_string())
+            .or_default()
+            .push(point);
+    }
+
+    /// Aggregate every pending point into tiles for all [`RollupTier::ALL`]
+    /// tiers, advancing each run's watermark to its latest point.
+    async fn flush(&self) -> Vec<crate::rollup::RollupTile> {
+        let drained: HashMap<String, Vec<RawPoint>> =
+            std::mem::take(&mut *self.pending.write().await);
+
+        let mut tiles = Vec::new();
+        let mut watermark = self.watermark.write().await;
+
+        for (run_id, points) in drained {
+            let Some(max_ts) = points.iter().map(|p| p.timestamp_unix_ms).max() else {
+                continue;
+            };
+
+            for tier in RollupTier::ALL {
+                tiles.extend(aggregate_tier(&run_id, &points, tier));
+            }
+
+            let entry = watermark.entry(run_id).or_insert(i64::MIN);
+            if max_ts > *entry {
+                *entry = max_ts;
+            }
+        }
+
+        tiles
+    }
+
+    async fn watermark(&self, run_id: &str) -> Option<i64> {
+        self.watermark.read().await.get(run_id).copied()
+    }
+}
+
+/// Ties the cardinality guard and rollup engine together into a single
+/// background subsystem with a configurable tick interval and pluggable
+/// sink.
+pub struct Processor {
+    config: ProcessorConfig,
+    guard: CardinalityGuard,
+    engine: RollupEngine,
+    sink: Arc<dyn RollupSink>,
+}
+
+impl Processor {
+    pub fn new(config: ProcessorConfig, sink: Arc<dyn RollupSink>) -> Self {
+        Self {
+            guard: CardinalityGuard::new(config.max_series_per_run),
+            engine: RollupEngine::new(),
+            config,
+            sink,
+        }
+    }
+
+    /// Register an ingested point for rollup. Refused by the cardinality
+    /// guard if `run_id` has already exhausted its series budget - the
+    /// ingest path can call this (or [`Self::distinct_series_count`] ahead
+    /// of time) to reject an offending batch before it's ever buffered.
+    pub async fn observe_point(&self, run_id: &str, point: RawPoint) -> SeriesGuardResult {
+        let result = self
+            .guard
+            .check_and_register(run_id, &point.metric_name, &point.tags)
+            .await;
+        if result.is_accepted() {
+            self.engine.ingest(run_id, point).await;
+        }
+        result
+    }
+
+    /// Current distinct-series count for `run_id`.
+    pub async fn distinct_series_count(&self, run_id: &str) -> usize {
+        self.guard.distinct_series(run_id).await
+    }
+
+    /// Latest point timestamp rolled up for `run_id`, or `None` if nothing
+    /// has been rolled up yet.
+    pub async fn rollup_watermark(&self, run_id: &str) -> Option<i64> {
+        self.engine.watermark(run_id).await
+    }
+
+    /// Run one rollup pass: aggregate buffered points into tiles and hand
+    /// them to the sink. A no-op tick (nothing pending) never calls the
+    /// sink.
+    pub async fn tick(&self) {
+        let tiles = self.engine.flush().await;
+        if tiles.is_empty() {
+            return;
+        }
+        debug!(tiles = tiles.len(), "Computed rollup tiles");
+        self.sink.write_tiles(&tiles).await;
+    }
+
+    /// Call [`Self::tick`] on `config.tick_interval` forever.
+    pub async fn run(&self) -> ! {
+        let mut interval = tokio::time::interval(self.config.tick_interval);
+        loop {
+            interval.tick().await;
+            self.tick().await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::sync::Mutex;
+
+    #[derive(Default)]
+    struct RecordingSink {
+        tiles: Mutex<Vec<crate::rollup::RollupTile>>,
+    }
+
+    #[async_trait::async_trait]
+    impl RollupSink for RecordingSink {
+        async fn write_tiles(&self, tiles: &[crate::rollup::RollupTile]) {
+            self.tiles.lock().await.extend_from_slice(tiles);
+        }
+    }
+
+    fn point(name: &str, ts: i64, value: f64) -> RawPoint {
+        RawPoint {
+            metric_name: name.to_string(),
+            tags: vec![],
+            timestamp_unix_ms: ts,
+            value,
+        }
+    }
+
+    #[tokio::test]
+    async fn tick_flushes_pending_points_to_sink() {
+        let sink = Arc::new(RecordingSink::default());
+        let processor = Processor::new(ProcessorConfig::default(), sink.clone());
+
+        processor
+            .observe_point("run-1", point("loss", 0, 1.0))
+            .await;
+        processor
+            .observe_point("run-1", point("loss", 500, 2.0))
+            .await;
+        processor.tick().await;
+
+        assert!(!sink.tiles.lock().await.is_empty());
+        assert_eq!(processor.rollup_watermark("run-1").await, Some(500));
+    }
+
+    #[tokio::test]
+    async fn empty_tick_does_not_call_sink() {
+        let sink = Arc::new(RecordingSink::default());
+        let processor = Processor::new(ProcessorConfig::default(), sink.clone());
+
+        processor.tick().await;
+
+        assert!(sink.tiles.lock().await.is_empty());
+        assert_eq!(processor.rollup_watermark("run-1").await, None);
+    }
+
+    #[tokio::test]
+    async fn guard_rejection_keeps_point_out_of_rollup() {
+        let config = ProcessorConfig {
+            max_series_per_run: 1,
+            ..Default::default()
+        };
+        let sink = Arc::new(RecordingSink::default());
+        let processor = Processor::new(config, sink);
+
+        let accepted = processor
+            .observe_point("run-1", point("loss", 0, 1.0))
+            .await;
+        assert!(accepted.is_accepted());
+
+        let mut rejected_point = point("accuracy", 0, 1.0);
+        rejected_point.tags = vec![("shard".to_string(), "b".to_string())];
+        let rejected = processor.observe_point("run-1", rejected_point).await;
+        assert!(!rejected.is_accepted());
+
+        processor.tick().await;
+        assert_eq!(processor.distinct_series_count("run-1").await, 1);
+    }
+}
@@ -0,0 +1,301 @@
+//! Fixed-window downsampled rollup tiers.
+//!
+//! The ingest path writes raw points; querying a long training run at full
+//! resolution would mean scanning and downsampling millions of points on
+//! every request (see `RunMetrics::query` in `apps-api`, which does exactly
+//! that in-memory today). The processor precomputes min/max/mean/last
+//! aggregates per fixed time bucket at three resolutions - 1s, 1m, 1h - so
+//! a query over a wide step range can serve a coarse tier cheaply instead
+//! of downsampling raw points on the read path.
+
+use async_trait::async_trait;
+use tracing::info;
+
+/// A rollup resolution. Coarser tiers cost more query-time precision for
+/// far less storage and are what a dashboard reaches for over a large step
+/// range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RollupTier {
+    OneSecond,
+    OneMinute,
+    OneHour,
+}
+
+impl RollupTier {
+    /// All tiers a point is rolled up into.
+    pub const ALL: [RollupTier; 3] = [Self::OneSecond, Self::OneMinute, Self::OneHour];
+
+    /// Bucket width in milliseconds.
+    pub fn bucket_ms(self) -> i64 {
+        match self {
+            Self::OneSecond => 1_000,
+            Self::OneMinute => 60_000,
+            Self::OneHour => 3_600_000,
+        }
+    }
+
+    /// Short label used in logs and sink output.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::OneSecond => "1s",
+            Self::OneMinute => "1m",
+            Self::OneHour => "1h",
+        }
+    }
+}
+
+/// Floor `timestamp_unix_ms` to the start of its `tier` bucket. A point
+/// exactly on a bucket boundary opens the next bucket rather than closing
+/// the previous one, so every point lands in exactly one bucket.
+pub fn bucket_start_unix_ms(timestamp_unix_ms: i64, tier: RollupTier) -> i64 {
+    let bucket_ms = tier.bucket_ms();
+    timestamp_unix_ms.div_euclid(bucket_ms) * bucket_ms
+}
+
+/// A single raw metric observation - the processor's unit of input.
+#[derive(Debug, Clone)]
+pub struct RawPoint {
+    pub metric_name: String,
+    pub tags: Vec<(String, String)>,
+    pub timestamp_unix_ms: i64,
+    pub value: f64,
+}
+
+/// A min/max/mean/last aggregate for one bucket of one tier.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RollupTile {
+    pub run_id: String,
+    pub metric_name: String,
+    pub tier: RollupTier,
+    pub bucket_start_unix_ms: i64,
+    pub min: f64,
+    pub max: f64,
+    pub mean: f64,
+    pub last: f64,
+    pub count: u64,
+}
+
+/// Destination for computed rollup tiles.
+///
+/// The processor itself is storage-agnostic, the same way `RunStore` keeps
+/// `apps-api` decoupled from the backing database: swap this for a
+/// persistent-metric-store-backed sink once one exists and every tile the
+/// processor computes starts surviving a restart.
+#[async_trait]
+pub trait RollupSink: Send + Sync {
+    async fn write_tiles(&self, tiles: &[RollupTile]);
+}
+
+/// Dev-mode default sink: logs tiles instead of persisting them.
+#[derive(Debug, Default)]
+pub struct LoggingRollupSink;
+
+#[async_trait]
+impl RollupSink for LoggingRollupSink {
+    async fn write_tiles(&self, tiles: &[RollupTile]) {
+        for tile in tiles {
+            info!(
+                run_id = %tile.run_id,
+                metric = %tile.metric_name,
+                tier = tile.tier.label(),
+                bucket_start_unix_ms = tile.bucket_start_unix_ms,
+                min = tile.min,
+                max = tile.max,
+                mean = tile.mean,
+                last = tile.last,
+                count = tile.count,
+                "Computed rollup tile"
+            );
+        }
+    }
+}
+
+/// Running min/max/sum/last/count for one bucket, accumulated point by
+/// point as they arrive in no particular order. `last` tracks the point
+/// with the greatest `timestamp_unix_ms` seen so far, not simply whichever
+/// point was observed most recently - points can arrive out of order
+/// (concurrent ingest requests, retried/interleaved batches).
+#[derive(Debug, Clone, Copy)]
+struct BucketAccumulator {
+    min: f64,
+    max: f64,
+    sum: f64,
+    last: f64,
+    last_timestamp_unix_ms: i64,
+    count: u64,
+}
+
+impl Default for BucketAccumulator {
+    fn default() -> Self {
+        Self {
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+            sum: 0.0,
+            last: 0.0,
+            last_timestamp_unix_ms: i64::MIN,
+            count: 0,
+        }
+    }
+}
+
+impl BucketAccumulator {
+    fn observe(&mut self, timestamp_unix_ms: i64, value: f64) {
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+        self.sum += value;
+        if timestamp_unix_ms >= self.last_timestamp_unix_ms {
+            self.last = value;
+            self.last_timestamp_unix_ms = timestamp_unix_ms;
+        }
+        self.count += 1;
+    }
+
+    fn mean(&self) -> f64 {
+        if self.count == 0 {
+            0.0
+        } else {
+            self.sum / self.count as f64
+        }
+    }
+}
+
+/// Aggregate `points` into `tier`-resolution tiles for `run_id`.
+pub fn aggregate_tier(run_id: &str, points: &[RawPoint], tier: RollupTier) -> Vec<RollupTile> {
+    use std::collections::HashMap;
+
+    let mut buckets: HashMap<(String, i64), BucketAccumulator> = HashMap::new();
+    for point in points {
+        let bucket_start = bucket_start_unix_ms(point.timestamp_unix_ms, tier);
+        buckets
+            .entry((point.metric_name.clone(), bucket_start))
+            .or_default()
+            .observe(point.timestamp_unix_ms, point.value);
+    }
+
+    buckets
+        .into_iter()
+        .map(|((metric_name, bucket_start_unix_ms), acc)| RollupTile {
+            run_id: run_id.to_string(),
+            metric_name,
+            tier,
+            bucket_start_unix_ms,
+            min: acc.min,
+            max: acc.max,
+            mean: acc.mean(),
+            last: acc.last,
+            count: acc.count,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn point_on_bucket_edge_lands_in_exactly_one_bucket() {
+        // 1m tier buckets are 60_000ms wide: a point at exactly 60_000ms
+        // opens the second bucket, not the first.
+        assert_eq!(bucket_start_unix_ms(59_999, RollupTier::OneMinute), 0);
+        assert_eq!(bucket_start_unix_ms(60_000, RollupTier::OneMinute), 60_000);
+        assert_eq!(bucket_start_unix_ms(119_999, RollupTier::OneMinute), 60_000);
+    }
+
+    #[test]
+    fn bucket_edge_point_does_not_split_across_buckets() {
+        let points = vec![
+            RawPoint {
+                metric_name: "loss".to_string(),
+                tags: vec![],
+                timestamp_unix_ms: 59_999,
+                value: 1.0,
+            },
+            RawPoint {
+                metric_name: "loss".to_string(),
+                tags: vec![],
+                timestamp_unix_ms: 60_000,
+                value: 2.0,
+            },
+        ];
+
+        let tiles = aggregate_tier("run-1", &points, RollupTier::OneMinute);
+        assert_eq!(tiles.len(), 2);
+
+        let first = tiles
+            .iter()
+            .find(|t| t.bucket_start_unix_ms == 0)
+            .expect("first bucket present");
+        assert_eq!(first.count, 1);
+        assert_eq!(first.last, 1.0);
+
+        let second = tiles
+            .iter()
+            .find(|t| t.bucket_start_unix_ms == 60_000)
+            .expect("second bucket present");
+        assert_eq!(second.count, 1);
+        assert_eq!(second.last, 2.0);
+    }
+
+    #[test]
+    fn aggregates_min_max_mean_last_within_a_bucket() {
+        let points = vec![
+            RawPoint {
+                metric_name: "loss".to_string(),
+                tags: vec![],
+                timestamp_unix_ms: 0,
+                value: 1.0,
+            },
+            RawPoint {
+                metric_name: "loss".to_string(),
+                tags: vec![],
+                timestamp_unix_ms: 500,
+                value: 3.0,
+            },
+            RawPoint {
+                metric_name: "loss".to_string(),
+                tags: vec![],
+                timestamp_unix_ms: 999,
+                value: 2.0,
+            },
+        ];
+
+        let tiles = aggregate_tier("run-1", &points, RollupTier::OneSecond);
+        assert_eq!(tiles.len(), 1);
+        let tile = &tiles[0];
+        assert_eq!(tile.min, 1.0);
+        assert_eq!(tile.max, 3.0);
+        assert_eq!(tile.last, 2.0);
+        assert!((tile.mean - 2.0).abs() < 1e-9);
+        assert_eq!(tile.count, 3);
+    }
+
+    #[test]
+    fn last_tracks_chronological_order_not_arrival_order() {
+        // Delivered out of timestamp order - e.g. concurrent ingest
+        // requests or a retried/interleaved batch.
+        let points = vec![
+            RawPoint {
+                metric_name: "loss".to_string(),
+                tags: vec![],
+                timestamp_unix_ms: 999,
+                value: 2.0,
+            },
+            RawPoint {
+                metric_name: "loss".to_string(),
+                tags: vec![],
+                timestamp_unix_ms: 0,
+                value: 1.0,
+            },
+            RawPoint {
+                metric_name: "loss".to_string(),
+                tags: vec![],
+                timestamp_unix_ms: 500,
+                value: 3.0,
+            },
+        ];
+
+        let tiles = aggregate_tier("run-1", &points, RollupTier::OneSecond);
+        assert_eq!(tiles.len(), 1);
+        assert_eq!(tiles[0].last, 2.0);
+    }
+}
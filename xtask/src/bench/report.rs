@@ -0,0 +1,81 @@
+//! Benchmark results: a JSON report plus an optional POST to a results
+//! server, for tracking throughput and drop behavior across changes.
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use serde::Serialize;
+
+/// Full report for one workload run, serialized as the JSON emitted by
+/// `cargo xtask bench` and optionally POSTed to `--results-url`.
+#[derive(Debug, Serialize)]
+pub struct BenchReport {
+    pub workload: String,
+    pub batches_replayed: usize,
+    pub batches_per_sec: f64,
+    pub latency_p50_micros: u64,
+    pub latency_p99_micros: u64,
+    pub accepted_tags_total: u64,
+    pub accepted_metric_names_total: u64,
+    /// Dropped tag pairs, keyed by drop reason (`"length"`,
+    /// `"run_key_limit"`, `"project_limit"`).
+    pub dropped_tags_by_reason: BTreeMap<String, u64>,
+    /// Dropped metric names, keyed by drop reason.
+    pub dropped_metric_names_by_reason: BTreeMap<String, u64>,
+    /// Rough peak memory held by the tracker's own run/project maps, in
+    /// bytes - an approximation (element count * estimated per-entry size),
+    /// not an instrumented allocator measurement.
+    pub peak_tracker_memory_bytes_estimate: u64,
+}
+
+impl BenchReport {
+    pub fn render_summary(&self) -> String {
+        format!(
+            "{}: {} batches in {:.2}s ({:.0}/s), p50={}us p99={}us, accepted {} tags / {} metrics, dropped {:?} tags / {:?} metrics, ~{} KB peak tracker memory",
+            self.workload,
+            self.batches_replayed,
+            self.batches_replayed as f64 / self.batches_per_sec.max(f64::MIN_POSITIVE),
+            self.batches_per_sec,
+            self.latency_p50_micros,
+            self.latency_p99_micros,
+            self.accepted_tags_total,
+            self.accepted_metric_names_total,
+            self.dropped_tags_by_reason,
+            self.dropped_metric_names_by_reason,
+            self.peak_tracker_memory_bytes_estimate / 1024,
+        )
+    }
+
+    /// POST the report as JSON to a results-collection server. Best-effort:
+    /// a failed POST is logged and does not fail the benchmark run itself.
+    pub async fn post_to(&self, url: &str) {
+        let client = reqwest::Client::new();
+        match client.post(url).json(self).send().await {
+            Ok(resp) if resp.status().is_success() => {
+                println!("Posted report to {url}");
+            }
+            Ok(resp) => {
+                eprintln!("Results server returned {}: {url}", resp.status());
+            }
+            Err(e) => {
+                eprintln!("Failed to POST report to {url}: {e}");
+            }
+        }
+    }
+}
+
+/// Computes p50/p99 from per-batch latencies, sorting them first.
+pub fn percentiles(mut samples: Vec<Duration>) -> (u64, u64) {
+    if samples.is_empty() {
+        return (0, 0);
+    }
+    samples.sort_unstable();
+
+    let p50_idx = (samples.len() * 50 / 100).min(samples.len() - 1);
+    let p99_idx = (samples.len() * 99 / 100).min(samples.len() - 1);
+
+    (
+        samples[p50_idx].as_micros() as u64,
+        samples[p99_idx].as_micros() as u64,
+    )
+}
@@ -0,0 +1,178 @@
+//! Declarative JSON description of a cardinality-guardrail benchmark run.
+//!
+//! A workload file names the [`LimitsConfig`] to enforce and a sequence of
+//! batch generators to replay against it. Each generator produces tags and
+//! metric names with a controllable duplicate ratio, so the same shape
+//! (`BatchSpec`) can model a steady low-cardinality training job, a
+//! high-cardinality attack, or a mostly-duplicate resubmission pattern
+//! just by varying its numbers - see `xtask/workloads/*.json`.
+
+use mlrun_api::services::{LimitsConfig, TagCardinalityEstimator};
+use rand::Rng;
+use serde::Deserialize;
+
+/// Top-level workload file: what to enforce, and what to replay against it.
+#[derive(Debug, Deserialize)]
+pub struct Workload {
+    /// Human-readable name, carried through into the report.
+    pub name: String,
+    /// Guardrail configuration under test.
+    pub limits: WorkloadLimits,
+    /// Batches to replay, in order.
+    pub batches: Vec<BatchSpec>,
+}
+
+/// Mirrors [`LimitsConfig`]'s limit and estimator fields so workload JSON
+/// stays close to the real config shape; `to_limits_config` does the
+/// conversion, leaving the remaining `LimitsConfig` fields at their defaults.
+#[derive(Debug, Deserialize)]
+pub struct WorkloadLimits {
+    pub max_tag_keys_per_run: usize,
+    pub max_metric_names_per_run: usize,
+    pub max_tags_per_project: usize,
+    pub max_tag_key_length: usize,
+    pub max_tag_value_length: usize,
+    pub max_metric_name_length: usize,
+    /// `"exact"` or `"hyperloglog"` - see [`TagCardinalityEstimator`].
+    pub project_tag_estimator: String,
+}
+
+impl WorkloadLimits {
+    pub fn to_limits_config(&self) -> LimitsConfig {
+        let project_tag_estimator = match self.project_tag_estimator.to_lowercase().as_str() {
+            "hyperloglog" | "hll" => TagCardinalityEstimator::HyperLogLog,
+            _ => TagCardinalityEstimator::Exact,
+        };
+
+        LimitsConfig {
+            max_tag_keys_per_run: self.max_tag_keys_per_run,
+            max_metric_names_per_run: self.max_metric_names_per_run,
+            max_tags_per_project: self.max_tags_per_project,
+            max_tag_key_length: self.max_tag_key_length,
+            max_tag_value_length: self.max_tag_value_length,
+            max_metric_name_length: self.max_metric_name_length,
+            project_tag_estimator,
+            ..Default::default()
+        }
+    }
+}
+
+/// One batch generator, replayed `repeat` times against `project_id`/`run_id`.
+#[derive(Debug, Deserialize)]
+pub struct BatchSpec {
+    pub project_id: String,
+    pub run_id: String,
+    /// How many times to call `validate_batch` with a freshly generated
+    /// batch from this spec.
+    pub repeat: usize,
+    pub tags: TagGenerator,
+    pub metric_names: MetricNameGenerator,
+}
+
+/// Generates a batch's `(tag_key, tag_value)` pairs.
+#[derive(Debug, Deserialize)]
+pub struct TagGenerator {
+    /// Tag pairs per generated batch.
+    pub count: usize,
+    pub key_length: usize,
+    pub value_length: usize,
+    /// Fraction (`0.0..=1.0`) of generated pairs that repeat a pair already
+    /// seen earlier in this generator's own pool, rather than being fresh.
+    /// `0.0` is an unbounded-cardinality attack; close to `1.0` models a
+    /// steady-state run re-logging the same tags every step.
+    pub duplicate_ratio: f64,
+}
+
+impl TagGenerator {
+    /// Generate `count` `(key, value)` pairs. Duplicates are drawn from a
+    /// `pool` of previously generated fresh pairs so repeats are exact
+    /// matches, not just same-length strings.
+    fn generate(&self, pool: &mut Vec<(String, String)>, rng: &mut impl Rng) -> Vec<(String, String)> {
+        let mut batch = Vec::with_capacity(self.count);
+        for _ in 0..self.count {
+            let reuse = !pool.is_empty() && rng.random_bool(self.duplicate_ratio.clamp(0.0, 1.0));
+            let pair = if reuse {
+                pool[rng.random_range(0..pool.len())].clone()
+            } else {
+                let pair = (
+                    random_string(self.key_length, rng),
+                    random_string(self.value_length, rng),
+                );
+                pool.push(pair.clone());
+                pair
+            };
+            batch.push(pair);
+        }
+        batch
+    }
+}
+
+/// Generates a batch's metric names.
+#[derive(Debug, Deserialize)]
+pub struct MetricNameGenerator {
+    pub count: usize,
+    pub name_length: usize,
+    /// Same semantics as [`TagGenerator::duplicate_ratio`].
+    pub duplicate_ratio: f64,
+}
+
+impl MetricNameGenerator {
+    fn generate(&self, pool: &mut Vec<String>, rng: &mut impl Rng) -> Vec<String> {
+        let mut batch = Vec::with_capacity(self.count);
+        for _ in 0..self.count {
+            let reuse = !pool.is_empty() && rng.random_bool(self.duplicate_ratio.clamp(0.0, 1.0));
+            let name = if reuse {
+                pool[rng.random_range(0..pool.len())].clone()
+            } else {
+                let name = random_string(self.name_length, rng);
+                pool.push(name.clone());
+                name
+            };
+            batch.push(name);
+        }
+        batch
+    }
+}
+
+/// One generated batch, ready to hand to `CardinalityTracker::validate_batch`.
+pub struct GeneratedBatch {
+    pub project_id: String,
+    pub run_id: String,
+    pub tags: Vec<(String, String)>,
+    pub metric_names: Vec<String>,
+}
+
+/// Per-spec generator state: each [`BatchSpec`] draws from its own pools, so
+/// duplicate ratios are relative to that spec's own history rather than
+/// shared across specs that happen to target the same run.
+pub struct BatchGenerator<'a> {
+    spec: &'a BatchSpec,
+    tag_pool: Vec<(String, String)>,
+    metric_pool: Vec<String>,
+}
+
+impl<'a> BatchGenerator<'a> {
+    pub fn new(spec: &'a BatchSpec) -> Self {
+        Self {
+            spec,
+            tag_pool: Vec::new(),
+            metric_pool: Vec::new(),
+        }
+    }
+
+    pub fn next(&mut self, rng: &mut impl Rng) -> GeneratedBatch {
+        GeneratedBatch {
+            project_id: self.spec.project_id.clone(),
+            run_id: self.spec.run_id.clone(),
+            tags: self.spec.tags.generate(&mut self.tag_pool, rng),
+            metric_names: self.spec.metric_names.generate(&mut self.metric_pool, rng),
+        }
+    }
+}
+
+fn random_string(len: usize, rng: &mut impl Rng) -> String {
+    const ALPHABET: &[u8] = b"abcdefghijklmnopqrstuvwxyz0123456789";
+    (0..len)
+        .map(|_| ALPHABET[rng.random_range(0..ALPHABET.len())] as char)
+        .collect()
+}
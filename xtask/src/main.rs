@@ -0,0 +1,64 @@
+//! Developer tasks that don't belong in the server binaries themselves.
+//!
+//! Currently just `bench`, which replays a declarative JSON workload
+//! against the ingest cardinality guardrail - see `bench` module doc and
+//! `xtask/workloads/*.json` for the baseline workloads.
+//!
+//! Run via `cargo xtask bench <workload.json> [--results-url <url>]`.
+
+mod bench;
+
+#[tokio::main]
+async fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    match args.first().map(String::as_str) {
+        Some("bench") => run_bench(&args[1..]).await,
+        _ => {
+            eprintln!("Usage: cargo xtask bench <workload.json> [--results-url <url>]");
+            std::process::exit(1);
+        }
+    }
+}
+
+async fn run_bench(args: &[String]) {
+    let mut workload_path = None;
+    let mut results_url = None;
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--results-url" => {
+                results_url = args.get(i + 1).cloned();
+                i += 2;
+            }
+            path => {
+                workload_path = Some(path.to_string());
+                i += 1;
+            }
+        }
+    }
+
+    let Some(workload_path) = workload_path else {
+        eprintln!("Usage: cargo xtask bench <workload.json> [--results-url <url>]");
+        std::process::exit(1);
+    };
+
+    let report = match bench::run(&workload_path).await {
+        Ok(report) => report,
+        Err(e) => {
+            eprintln!("bench failed: {e}");
+            std::process::exit(1);
+        }
+    };
+
+    println!("{}", report.render_summary());
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&report).expect("report serializes to JSON")
+    );
+
+    if let Some(url) = results_url {
+        report.post_to(&url).await;
+    }
+}